@@ -21,12 +21,16 @@ pub struct Coin {
 }
 
 impl Coin {
+    /// The value of `key` in coin.json, strings are returned without quotes
     pub fn get_value(&self, key: &str) -> Option<String> {
-        self.all_info.get(key).map(|x| x.to_string())
+        self.all_info.get(key).map(|x| match x.as_str() {
+            Some(value) => value.to_owned(),
+            None => x.to_string(),
+        })
     }
 
     pub fn get_xpub(&self) -> Option<String> {
-        self.all_info.get("xpub").map(|x| x.to_string())
+        self.get_value("xpub")
     }
 }
 
@@ -50,6 +54,8 @@ impl std::str::FromStr for ProtoCoin {
             "ethereum" => Ok(ProtoCoin::Ethereum),
             "polkadot" => Ok(ProtoCoin::Polkadot),
             "solana" => Ok(ProtoCoin::Solana),
+            "bitcoin" => Ok(ProtoCoin::Bitcoin),
+            "arweave" => Ok(ProtoCoin::Arweave),
             _ => Err(Error::NotSupportedCoin),
         }
     }
@@ -57,9 +63,29 @@ impl std::str::FromStr for ProtoCoin {
 
 #[cfg(test)]
 mod tests {
+    use super::Coin;
     use crate::api::Coin as ProtoCoin;
     #[test]
     fn test_proto_coin_into_str() {
         assert_eq!(ProtoCoin::Ethereum.to_string(), "Ethereum");
     }
+
+    #[test]
+    fn test_get_value() {
+        let coin = Coin {
+            id: "bitcoin".to_owned(),
+            name: "Bitcoin".to_owned(),
+            coin_id: 0,
+            symbol: "BTC".to_owned(),
+            decimals: 8,
+            blockchain: "Bitcoin".to_owned(),
+            derivation_path: "m/84'/0'/0'/0/0".to_owned(),
+            curve: "secp256k1".to_owned(),
+            public_key_type: "secp256k1".to_owned(),
+            all_info: serde_json::from_str(r#"{"p2pkh": 0, "hrp": "bc"}"#).unwrap(),
+        };
+        assert_eq!(coin.get_value("p2pkh").unwrap(), "0");
+        assert_eq!(coin.get_value("hrp").unwrap(), "bc");
+        assert_eq!(coin.get_value("xpub"), None);
+    }
 }
@@ -7,6 +7,8 @@ pub enum ChainImportType {
     PrivateKey = 0,
     Mnemonic = 1,
     KeyStoreJson = 2,
+    Jwk = 3,
+    WatchOnly = 4,
 }
 
 pub enum ChainExportType {
@@ -26,6 +28,69 @@ pub trait Entry {
         p2pkh: &[u8],
         hrp: &[u8],
     ) -> Result<String, Error>;
+    /// Derive the address of `public_key` derived at `derivation_path`, which chains
+    /// choosing the address format by the path override
+    fn derive_address_of_path(
+        &self,
+        coin: &Coin,
+        public_key: &PublicKey,
+        p2pkh: &[u8],
+        hrp: &[u8],
+        _derivation_path: &str,
+    ) -> Result<String, Error> {
+        self.derive_address(coin, public_key, p2pkh, hrp)
+    }
     fn sign(&self, coin: &Coin, private_key: &PrivateKey, payload: &[u8])
         -> Result<Vec<u8>, Error>;
+    /// Sign `message` with the message signing scheme of the chain. If `raw_hash` is set, the
+    /// message is a hash signed as is, which must only be set for hashes of known data
+    fn sign_message(
+        &self,
+        _coin: &Coin,
+        _private_key: &PrivateKey,
+        _message: &[u8],
+        _raw_hash: bool,
+    ) -> Result<Vec<u8>, Error> {
+        Err(Error::NotSupportedMessageSigning)
+    }
+    /// The address of the key signing `message`, recovered from `signature`
+    fn recover_message_signer(
+        &self,
+        _coin: &Coin,
+        _message: &[u8],
+        _signature: &[u8],
+        _raw_hash: bool,
+    ) -> Result<String, Error> {
+        Err(Error::NotSupportedMessageSigning)
+    }
+    /// Sign the JSON `typed_data` with the structured data signing scheme of the chain
+    fn sign_typed_data(
+        &self,
+        _coin: &Coin,
+        _private_key: &PrivateKey,
+        _typed_data: &str,
+    ) -> Result<Vec<u8>, Error> {
+        Err(Error::NotSupportedMessageSigning)
+    }
+    /// Decode the signed or unsigned transaction `data`, the output is the encoded decode output
+    /// proto of the chain, whose signature is verified for `chain_id`
+    fn decode_transaction(
+        &self,
+        _coin: &Coin,
+        _data: &[u8],
+        _chain_id: u64,
+    ) -> Result<Vec<u8>, Error> {
+        Err(Error::NotSupportedTransactionDecoding)
+    }
+    /// Sign `payload` whose parts may be signed by keys at different derivation paths,
+    /// `private_key` is the key of the requested path and `get_key` derives the key at a path
+    fn sign_with_keys(
+        &self,
+        coin: &Coin,
+        private_key: &PrivateKey,
+        _get_key: &dyn Fn(&str) -> Result<PrivateKey, Error>,
+        payload: &[u8],
+    ) -> Result<Vec<u8>, Error> {
+        self.sign(coin, private_key, payload)
+    }
 }
@@ -1,2 +1,6 @@
 pub mod api;
+pub mod arweave;
+pub mod bitcoin;
 pub mod ethereum;
+pub mod polkadot;
+pub mod solana;
\ No newline at end of file
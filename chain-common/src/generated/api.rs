@@ -7,6 +7,46 @@ pub struct GetVersionResp {
     #[prost(string, tag="1")]
     pub version: ::prost::alloc::string::String,
 }
+/// Key derivation function and its cost used to encrypt a StoredKey, Argon2id with the recommended cost if not set.
+/// A cost below the minimum is rejected with the error -3021
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct KdfPolicy {
+    #[prost(oneof="kdf_policy::Policy", tags="1, 2")]
+    pub policy: ::core::option::Option<kdf_policy::Policy>,
+}
+/// Nested message and enum types in `KdfPolicy`.
+pub mod kdf_policy {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Policy {
+        #[prost(message, tag="1")]
+        Argon2id(super::Argon2idPolicy),
+        #[prost(message, tag="2")]
+        Scrypt(super::ScryptPolicy),
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Argon2idPolicy {
+    /// Memory size in KiB, at least 19456
+    #[prost(uint32, tag="1")]
+    pub memory_cost: u32,
+    /// Number of passes, at least 2
+    #[prost(uint32, tag="2")]
+    pub time_cost: u32,
+    /// Number of lanes
+    #[prost(uint32, tag="3")]
+    pub parallelism: u32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ScryptPolicy {
+    /// CPU/memory cost, at least 32768
+    #[prost(uint32, tag="1")]
+    pub n: u32,
+    /// Block size, at least 8
+    #[prost(uint32, tag="2")]
+    pub r: u32,
+    #[prost(uint32, tag="3")]
+    pub p: u32,
+}
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct StoredKeyInfo {
     #[prost(string, tag="1")]
@@ -19,6 +59,9 @@ pub struct StoredKeyInfo {
     /// Raw data of the StoredKey, used in requests required an existing StoredKey
     #[prost(bytes="vec", tag="4")]
     pub data: ::prost::alloc::vec::Vec<u8>,
+    /// Whether the StoredKey has an older version, it's upgraded by the next request with its password which returns the StoredKey, like updating the password or creating an account. Signing and exporting don't upgrade it. A migrated StoredKey has no accounts until the client adds them again
+    #[prost(bool, tag="5")]
+    pub needs_migration: bool,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct StoredKeyAccountInfo {
@@ -40,12 +83,16 @@ pub enum Coin {
     Ethereum = 0,
     Polkadot = 1,
     Solana = 2,
+    Bitcoin = 3,
+    Arweave = 4,
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
 pub enum StoredKeyType {
     PrivateKey = 0,
     Mnemonic = 1,
+    /// Created from a public key, which can't sign or export any private key
+    WatchOnly = 2,
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
@@ -53,6 +100,8 @@ pub enum StoredKeyImportType {
     PrivateKeyImportType = 0,
     MnemonicImportType = 1,
     KeyStoreJsonImportType = 2,
+    JwkImportType = 3,
+    WatchOnlyImportType = 4,
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
@@ -61,6 +110,43 @@ pub enum StoredKeyExportType {
     MnemonicExportType = 1,
     KeyStoreJsonExportType = 2,
 }
+/// SLIP-132 version of a serialized extended public key
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ExtendedPublicKeyVersion {
+    /// The version of the purpose of the derivation path, xpub for any purpose other than 49 and 84
+    DefaultVersion = 0,
+    Xpub = 1,
+    Ypub = 2,
+    Zpub = 3,
+    Tpub = 4,
+    Upub = 5,
+    Vpub = 6,
+}
+/// Key derivation function of an exported V3 KeyStoreJSON
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum KdfType {
+    Scrypt = 0,
+    /// PBKDF2 with HMAC-SHA256
+    Pbkdf2 = 1,
+}
+/// BIP39 wordlist of a mnemonic
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum MnemonicLanguage {
+    /// Detect the language when importing a mnemonic, English when generating one
+    AutoDetect = 0,
+    English = 1,
+    ChineseSimplified = 2,
+    ChineseTraditional = 3,
+    Czech = 4,
+    French = 5,
+    Italian = 6,
+    Japanese = 7,
+    Korean = 8,
+    Spanish = 9,
+}
 /// Create a new account to the StoredKey at specific derivation path. Fail if the StoredKey is not a Hd StoredKey
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CreateStoredKeyNewAccountAtPathParam {
@@ -82,6 +168,60 @@ pub struct CreateStoredKeyNewAccountAtPathResp {
     #[prost(message, optional, tag="2")]
     pub stored_key: ::core::option::Option<StoredKeyInfo>,
 }
+/// Get the accounts of the StoredKey matching every given filter, the password is not required
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetStoredKeyAccountsParam {
+    #[prost(bytes="vec", tag="1")]
+    pub stored_key_data: ::prost::alloc::vec::Vec<u8>,
+    /// Accounts of any coin are returned if empty
+    #[prost(enumeration="Coin", repeated, tag="2")]
+    pub coins: ::prost::alloc::vec::Vec<i32>,
+    /// Accounts at any derivation path are returned if empty
+    #[prost(string, tag="3")]
+    pub derivation_path: ::prost::alloc::string::String,
+    /// Accounts of any address are returned if empty
+    #[prost(string, tag="4")]
+    pub address: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetStoredKeyAccountsResp {
+    #[prost(message, repeated, tag="1")]
+    pub accounts: ::prost::alloc::vec::Vec<StoredKeyAccountInfo>,
+}
+/// Remove the account of the address from the StoredKey
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RemoveStoredKeyAccountParam {
+    #[prost(bytes="vec", tag="1")]
+    pub stored_key_data: ::prost::alloc::vec::Vec<u8>,
+    #[prost(enumeration="Coin", tag="2")]
+    pub coin: i32,
+    #[prost(string, tag="3")]
+    pub address: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RemoveStoredKeyAccountResp {
+    #[prost(message, optional, tag="1")]
+    pub stored_key: ::core::option::Option<StoredKeyInfo>,
+}
+/// Rename the account of the address of the StoredKey
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RenameStoredKeyAccountParam {
+    #[prost(bytes="vec", tag="1")]
+    pub stored_key_data: ::prost::alloc::vec::Vec<u8>,
+    #[prost(enumeration="Coin", tag="2")]
+    pub coin: i32,
+    #[prost(string, tag="3")]
+    pub address: ::prost::alloc::string::String,
+    #[prost(string, tag="4")]
+    pub name: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RenameStoredKeyAccountResp {
+    #[prost(message, optional, tag="1")]
+    pub account: ::core::option::Option<StoredKeyAccountInfo>,
+    #[prost(message, optional, tag="2")]
+    pub stored_key: ::core::option::Option<StoredKeyInfo>,
+}
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GetKeyStoreSupportExportTypeParam {
     #[prost(enumeration="Coin", tag="1")]
@@ -119,6 +259,29 @@ pub struct ExportKeyStorePrivateKeyResp {
     #[prost(string, tag="1")]
     pub private_key: ::prost::alloc::string::String,
 }
+/// Export the public key of the StoredKey at specific derivation path, which is an extended public key for secp256k1 coins and the hex encoded SLIP-10 public key for ed25519 coins. Fail if the StoredKey is not a Hd StoredKey
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetExtendedPublicKeyParam {
+    #[prost(bytes="vec", tag="1")]
+    pub stored_key_data: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag="2")]
+    pub password: ::prost::alloc::string::String,
+    #[prost(enumeration="Coin", tag="3")]
+    pub coin: i32,
+    /// Defaults to the derivation path of the coin
+    #[prost(string, tag="4")]
+    pub derivation_path: ::prost::alloc::string::String,
+    /// Number of indices of the derivation path, defaults to 3 for the account key of m/purpose'/coin'/account'
+    #[prost(uint32, tag="5")]
+    pub depth: u32,
+    #[prost(enumeration="ExtendedPublicKeyVersion", tag="6")]
+    pub version: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetExtendedPublicKeyResp {
+    #[prost(string, tag="1")]
+    pub extended_public_key: ::prost::alloc::string::String,
+}
 /// Export the mnemonic of the StoredKey. Fail if the StoredKey is not a Hd StoredKey
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ExportKeyStoreMnemonicParam {
@@ -145,6 +308,9 @@ pub struct ExportKeyStoreJsonOfAddressParam {
     pub coin: i32,
     #[prost(string, tag="5")]
     pub address: ::prost::alloc::string::String,
+    /// Key derivation function of the exported KeyStoreJSON, defaults to scrypt
+    #[prost(enumeration="KdfType", tag="6")]
+    pub kdf: i32,
 }
 /// Export the V3 KeyStoreJSON string of the StoredKey at specific derivation path. Fail if the StoredKey is not a Hd StoredKey
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -159,6 +325,9 @@ pub struct ExportKeyStoreJsonOfPathParam {
     pub coin: i32,
     #[prost(string, tag="5")]
     pub derivation_path: ::prost::alloc::string::String,
+    /// Key derivation function of the exported KeyStoreJSON, defaults to scrypt
+    #[prost(enumeration="KdfType", tag="6")]
+    pub kdf: i32,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ExportKeyStoreJsonResp {
@@ -181,6 +350,8 @@ pub struct LoadStoredKeyParam {
     #[prost(bytes="vec", repeated, tag="1")]
     pub data: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
 }
+/// StoredKeys of an older version report `needsMigration`. They have no account list, so the client
+/// must add the accounts it uses again by CreateStoredKeyNewAccountAtPathParam, which also migrates them
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct LoadStoredKeyResp {
     #[prost(message, repeated, tag="1")]
@@ -191,6 +362,18 @@ pub struct LoadStoredKeyResp {
 pub struct CreateStoredKeyParam {
     #[prost(string, tag="2")]
     pub password: ::prost::alloc::string::String,
+    /// Optional BIP39 passphrase of the mnemonic
+    #[prost(string, tag="3")]
+    pub passphrase: ::prost::alloc::string::String,
+    /// Number of words of the mnemonic, one of 12, 15, 18, 21 and 24, defaults to 12
+    #[prost(uint32, tag="4")]
+    pub word_count: u32,
+    /// Wordlist of the mnemonic, defaults to English
+    #[prost(enumeration="MnemonicLanguage", tag="5")]
+    pub language: i32,
+    /// Key derivation function of the encrypted StoredKey
+    #[prost(message, optional, tag="6")]
+    pub kdf_policy: ::core::option::Option<KdfPolicy>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CreateStoredKeyResp {
@@ -213,6 +396,9 @@ pub struct ImportPrivateStoredKeyParam {
     pub name: ::prost::alloc::string::String,
     #[prost(enumeration="Coin", tag="4")]
     pub coin: i32,
+    /// Key derivation function of the encrypted StoredKey
+    #[prost(message, optional, tag="5")]
+    pub kdf_policy: ::core::option::Option<KdfPolicy>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ImportPrivateStoredKeyResp {
@@ -226,6 +412,15 @@ pub struct ImportMnemonicStoredKeyParam {
     pub mnemonic: ::prost::alloc::string::String,
     #[prost(string, tag="3")]
     pub password: ::prost::alloc::string::String,
+    /// Optional BIP39 passphrase of the mnemonic
+    #[prost(string, tag="4")]
+    pub passphrase: ::prost::alloc::string::String,
+    /// Wordlist of the mnemonic, detected from the words if not set
+    #[prost(enumeration="MnemonicLanguage", tag="5")]
+    pub language: i32,
+    /// Key derivation function of the encrypted StoredKey
+    #[prost(message, optional, tag="6")]
+    pub kdf_policy: ::core::option::Option<KdfPolicy>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ImportMnemonicStoredKeyResp {
@@ -248,13 +443,53 @@ pub struct ImportJsonStoredKeyParam {
     pub password: ::prost::alloc::string::String,
     #[prost(enumeration="Coin", tag="5")]
     pub coin: i32,
+    /// Key derivation function of the encrypted StoredKey
+    #[prost(message, optional, tag="6")]
+    pub kdf_policy: ::core::option::Option<KdfPolicy>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ImportJsonStoredKeyResp {
     #[prost(message, optional, tag="1")]
     pub stored_key: ::core::option::Option<StoredKeyInfo>,
 }
-/// Update the password of an exisiting StoredKey
+/// Create a new StoredKey with an RSA JSON Web Key (JWK), this request will NOT create any account
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ImportJwkStoredKeyParam {
+    #[prost(string, tag="1")]
+    pub jwk: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub password: ::prost::alloc::string::String,
+    /// Key derivation function of the encrypted StoredKey
+    #[prost(message, optional, tag="3")]
+    pub kdf_policy: ::core::option::Option<KdfPolicy>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ImportJwkStoredKeyResp {
+    #[prost(message, optional, tag="1")]
+    pub stored_key: ::core::option::Option<StoredKeyInfo>,
+}
+/// Create a new watch-only StoredKey, which has no password and can't sign or export any private
+/// key. Either an account-level extended public key (xpub, ypub or zpub) or the hex encoded public
+/// key of a single address is imported, this request will create the first receive address of coin.
+/// An extended public key of another network than coin, or a ypub or zpub of a coin other than
+/// Bitcoin, is rejected with the error -3006
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ImportWatchOnlyStoredKeyParam {
+    #[prost(string, tag="1")]
+    pub extended_public_key: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub public_key: ::prost::alloc::string::String,
+    #[prost(enumeration="Coin", tag="3")]
+    pub coin: i32,
+    #[prost(string, tag="4")]
+    pub name: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ImportWatchOnlyStoredKeyResp {
+    #[prost(message, optional, tag="1")]
+    pub stored_key: ::core::option::Option<StoredKeyInfo>,
+}
+/// Update the password of an exisiting StoredKey, which is re-encrypted with the KdfPolicy
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct UpdateStoredKeyPasswordParam {
     #[prost(bytes="vec", tag="1")]
@@ -263,15 +498,40 @@ pub struct UpdateStoredKeyPasswordParam {
     pub old_password: ::prost::alloc::string::String,
     #[prost(string, tag="3")]
     pub new_password: ::prost::alloc::string::String,
+    /// Key derivation function of the encrypted StoredKey
+    #[prost(message, optional, tag="4")]
+    pub kdf_policy: ::core::option::Option<KdfPolicy>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct UpdateStoredKeyPasswordResp {
     #[prost(message, optional, tag="1")]
     pub stored_key: ::core::option::Option<StoredKeyInfo>,
 }
+/// Re-encrypt a StoredKey with the same password, to upgrade the key derivation function and the cipher of an existing StoredKey
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RekeyStoredKeyParam {
+    #[prost(bytes="vec", tag="1")]
+    pub stored_key_data: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag="2")]
+    pub password: ::prost::alloc::string::String,
+    /// Key derivation function of the re-encrypted StoredKey
+    #[prost(message, optional, tag="3")]
+    pub kdf_policy: ::core::option::Option<KdfPolicy>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RekeyStoredKeyResp {
+    #[prost(message, optional, tag="1")]
+    pub stored_key: ::core::option::Option<StoredKeyInfo>,
+}
 /// Generate a random mnemonic
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GenerateMnemonicParam {
+    /// Number of words of the mnemonic, one of 12, 15, 18, 21 and 24, defaults to 12
+    #[prost(uint32, tag="1")]
+    pub word_count: u32,
+    /// Wordlist of the mnemonic, defaults to English
+    #[prost(enumeration="MnemonicLanguage", tag="2")]
+    pub language: i32,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GenerateMnemonicResp {
@@ -290,7 +550,7 @@ pub struct SignTransactionParam {
     pub password: ::prost::alloc::string::String,
     #[prost(enumeration="Coin", tag="4")]
     pub coin: i32,
-    #[prost(oneof="sign_transaction_param::Input", tags="5")]
+    #[prost(oneof="sign_transaction_param::Input", tags="5, 6, 7, 8, 9")]
     pub input: ::core::option::Option<sign_transaction_param::Input>,
 }
 /// Nested message and enum types in `SignTransactionParam`.
@@ -299,11 +559,19 @@ pub mod sign_transaction_param {
     pub enum Input {
         #[prost(message, tag="5")]
         SignInput(super::super::ethereum::SignInput),
+        #[prost(message, tag="6")]
+        SolanaSignInput(super::super::solana::SignInput),
+        #[prost(message, tag="7")]
+        PolkadotSignInput(super::super::polkadot::SignInput),
+        #[prost(message, tag="8")]
+        BitcoinSignInput(super::super::bitcoin::SignInput),
+        #[prost(message, tag="9")]
+        ArweaveSignInput(super::super::arweave::SignInput),
     }
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SignTransactionResp {
-    #[prost(oneof="sign_transaction_resp::Output", tags="1")]
+    #[prost(oneof="sign_transaction_resp::Output", tags="1, 2, 3, 4, 5")]
     pub output: ::core::option::Option<sign_transaction_resp::Output>,
 }
 /// Nested message and enum types in `SignTransactionResp`.
@@ -312,6 +580,107 @@ pub mod sign_transaction_resp {
     pub enum Output {
         #[prost(message, tag="1")]
         SignOutput(super::super::ethereum::SignOutput),
+        #[prost(message, tag="2")]
+        SolanaSignOutput(super::super::solana::SignOutput),
+        #[prost(message, tag="3")]
+        PolkadotSignOutput(super::super::polkadot::SignOutput),
+        #[prost(message, tag="4")]
+        BitcoinSignOutput(super::super::bitcoin::SignOutput),
+        #[prost(message, tag="5")]
+        ArweaveSignOutput(super::super::arweave::SignOutput),
+    }
+}
+/// Sign a message with the message signing scheme of the coin, which is EIP-191 personal_sign for Ethereum
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignMessageParam {
+    #[prost(bytes="vec", tag="1")]
+    pub stored_key_data: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag="2")]
+    pub derivation_path: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub password: ::prost::alloc::string::String,
+    #[prost(enumeration="Coin", tag="4")]
+    pub coin: i32,
+    #[prost(bytes="vec", tag="5")]
+    pub message: ::prost::alloc::vec::Vec<u8>,
+    /// Sign the message as a raw 32-byte hash like eth_sign, which may be the hash of a transaction,
+    /// so it must only be set for hashes of known data
+    #[prost(bool, tag="6")]
+    pub raw_hash: bool,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignMessageResp {
+    /// 65-byte r || s || v signature for Ethereum, v is 27 or 28
+    #[prost(bytes="vec", tag="1")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+}
+/// Recover the signer of a signed message and verify it's the given address, the StoredKey is not required
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VerifyMessageParam {
+    #[prost(enumeration="Coin", tag="1")]
+    pub coin: i32,
+    #[prost(bytes="vec", tag="2")]
+    pub message: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="3")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag="4")]
+    pub address: ::prost::alloc::string::String,
+    #[prost(bool, tag="5")]
+    pub raw_hash: bool,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VerifyMessageResp {
+    /// Address of the key signing the message
+    #[prost(string, tag="1")]
+    pub signer: ::prost::alloc::string::String,
+    /// Whether the signer is the address of the request
+    #[prost(bool, tag="2")]
+    pub valid: bool,
+}
+/// Sign JSON typed structured data, which is EIP-712 eth_signTypedData_v4 for Ethereum
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignTypedDataParam {
+    #[prost(bytes="vec", tag="1")]
+    pub stored_key_data: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag="2")]
+    pub derivation_path: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub password: ::prost::alloc::string::String,
+    #[prost(enumeration="Coin", tag="4")]
+    pub coin: i32,
+    /// JSON of the types, primaryType, domain and message
+    #[prost(string, tag="5")]
+    pub typed_data: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignTypedDataResp {
+    /// 65-byte r || s || v signature for Ethereum, v is 27 or 28
+    #[prost(bytes="vec", tag="1")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+}
+/// Decode a signed or unsigned transaction, e.g. the encoded transaction of a SignOutput
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DecodeTransactionParam {
+    #[prost(enumeration="Coin", tag="1")]
+    pub coin: i32,
+    /// RLP or EIP-2718 typed transaction for Ethereum
+    #[prost(bytes="vec", tag="2")]
+    pub data: ::prost::alloc::vec::Vec<u8>,
+    /// Chain id the signature is verified for
+    #[prost(uint64, tag="3")]
+    pub chain_id: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DecodeTransactionResp {
+    #[prost(oneof="decode_transaction_resp::Output", tags="1")]
+    pub output: ::core::option::Option<decode_transaction_resp::Output>,
+}
+/// Nested message and enum types in `DecodeTransactionResp`.
+pub mod decode_transaction_resp {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Output {
+        #[prost(message, tag="1")]
+        EthereumDecodeOutput(super::super::ethereum::DecodeOutput),
     }
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -357,7 +726,7 @@ pub struct ValidateResp {
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct MwRequest {
-    #[prost(oneof="mw_request::Request", tags="1, 2, 3, 4, 5, 10, 13, 14, 15, 16, 17, 18, 20, 21, 22, 23, 24, 25")]
+    #[prost(oneof="mw_request::Request", tags="1, 2, 3, 4, 5, 6, 10, 11, 12, 13, 14, 15, 16, 17, 18, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33")]
     pub request: ::core::option::Option<mw_request::Request>,
 }
 /// Nested message and enum types in `MWRequest`.
@@ -374,8 +743,14 @@ pub mod mw_request {
         ParamImportMnemonic(super::ImportMnemonicStoredKeyParam),
         #[prost(message, tag="5")]
         ParamImportJson(super::ImportJsonStoredKeyParam),
+        #[prost(message, tag="6")]
+        ParamGetStoredKeyAccounts(super::GetStoredKeyAccountsParam),
         #[prost(message, tag="10")]
         ParamCreateAccountOfCoinAtPath(super::CreateStoredKeyNewAccountAtPathParam),
+        #[prost(message, tag="11")]
+        ParamRemoveAccount(super::RemoveStoredKeyAccountParam),
+        #[prost(message, tag="12")]
+        ParamRenameAccount(super::RenameStoredKeyAccountParam),
         #[prost(message, tag="13")]
         ParamExportPrivateKey(super::ExportKeyStorePrivateKeyParam),
         #[prost(message, tag="14")]
@@ -400,11 +775,27 @@ pub mod mw_request {
         ParamGetStoredKeyExportType(super::GetKeyStoreSupportExportTypeParam),
         #[prost(message, tag="25")]
         ParamGenerateMnemonic(super::GenerateMnemonicParam),
+        #[prost(message, tag="26")]
+        ParamImportJwk(super::ImportJwkStoredKeyParam),
+        #[prost(message, tag="27")]
+        ParamRekeyStoredKey(super::RekeyStoredKeyParam),
+        #[prost(message, tag="28")]
+        ParamImportWatchOnly(super::ImportWatchOnlyStoredKeyParam),
+        #[prost(message, tag="29")]
+        ParamGetExtendedPublicKey(super::GetExtendedPublicKeyParam),
+        #[prost(message, tag="30")]
+        ParamSignMessage(super::SignMessageParam),
+        #[prost(message, tag="31")]
+        ParamVerifyMessage(super::VerifyMessageParam),
+        #[prost(message, tag="32")]
+        ParamSignTypedData(super::SignTypedDataParam),
+        #[prost(message, tag="33")]
+        ParamDecodeTransaction(super::DecodeTransactionParam),
     }
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct MwResponse {
-    #[prost(oneof="mw_response::Response", tags="1, 2, 3, 4, 5, 6, 11, 14, 15, 16, 17, 19, 20, 21, 22, 23, 24")]
+    #[prost(oneof="mw_response::Response", tags="1, 2, 3, 4, 5, 6, 7, 11, 12, 13, 14, 15, 16, 17, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32")]
     pub response: ::core::option::Option<mw_response::Response>,
 }
 /// Nested message and enum types in `MWResponse`.
@@ -423,8 +814,14 @@ pub mod mw_response {
         RespImportMnemonic(super::ImportMnemonicStoredKeyResp),
         #[prost(message, tag="6")]
         RespImportJson(super::ImportJsonStoredKeyResp),
+        #[prost(message, tag="7")]
+        RespGetStoredKeyAccounts(super::GetStoredKeyAccountsResp),
         #[prost(message, tag="11")]
         RespCreateAccountOfCoinAtPath(super::CreateStoredKeyNewAccountAtPathResp),
+        #[prost(message, tag="12")]
+        RespRemoveAccount(super::RemoveStoredKeyAccountResp),
+        #[prost(message, tag="13")]
+        RespRenameAccount(super::RenameStoredKeyAccountResp),
         #[prost(message, tag="14")]
         RespExportPrivateKey(super::ExportKeyStorePrivateKeyResp),
         #[prost(message, tag="15")]
@@ -445,6 +842,22 @@ pub mod mw_response {
         RespGetStoredKeyExportType(super::GetKeyStoreSupportExportTypeResp),
         #[prost(message, tag="24")]
         RespGenerateMnemonic(super::GenerateMnemonicResp),
+        #[prost(message, tag="25")]
+        RespImportJwk(super::ImportJwkStoredKeyResp),
+        #[prost(message, tag="26")]
+        RespRekeyStoredKey(super::RekeyStoredKeyResp),
+        #[prost(message, tag="27")]
+        RespImportWatchOnly(super::ImportWatchOnlyStoredKeyResp),
+        #[prost(message, tag="28")]
+        RespGetExtendedPublicKey(super::GetExtendedPublicKeyResp),
+        #[prost(message, tag="29")]
+        RespSignMessage(super::SignMessageResp),
+        #[prost(message, tag="30")]
+        RespVerifyMessage(super::VerifyMessageResp),
+        #[prost(message, tag="31")]
+        RespSignTypedData(super::SignTypedDataResp),
+        #[prost(message, tag="32")]
+        RespDecodeTransaction(super::DecodeTransactionResp),
     }
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
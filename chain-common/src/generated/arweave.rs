@@ -0,0 +1,45 @@
+/// A transaction tag, name and value are base64url encoded
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Tag {
+    #[prost(string, tag="1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub value: ::prost::alloc::string::String,
+}
+/// Input data necessary to sign a format 2 transaction.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignInput {
+    /// Base64url encoded address of the recipient, empty if no AR is transferred
+    #[prost(string, tag="1")]
+    pub target: ::prost::alloc::string::String,
+    /// Winston amount transferred to the target, as a decimal string
+    #[prost(string, tag="2")]
+    pub quantity: ::prost::alloc::string::String,
+    /// Winston fee of the transaction, as a decimal string
+    #[prost(string, tag="3")]
+    pub reward: ::prost::alloc::string::String,
+    /// Base64url encoded anchor, the id of the last transaction or a recent block hash
+    #[prost(string, tag="4")]
+    pub last_tx: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag="5")]
+    pub tags: ::prost::alloc::vec::Vec<Tag>,
+    /// Size of the data in bytes, as a decimal string
+    #[prost(string, tag="6")]
+    pub data_size: ::prost::alloc::string::String,
+    /// Base64url encoded merkle root of the data chunks, empty if there's no data
+    #[prost(string, tag="7")]
+    pub data_root: ::prost::alloc::string::String,
+}
+/// Transaction signing output, every field is base64url encoded.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignOutput {
+    /// Transaction id, the SHA-256 hash of the signature
+    #[prost(string, tag="1")]
+    pub id: ::prost::alloc::string::String,
+    /// Public key of the signer, the modulus of the RSA key
+    #[prost(string, tag="2")]
+    pub owner: ::prost::alloc::string::String,
+    /// RSA-PSS signature of the transaction deep hash
+    #[prost(string, tag="3")]
+    pub signature: ::prost::alloc::string::String,
+}
@@ -0,0 +1,22 @@
+/// Input data necessary to sign a partially signed transaction.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignInput {
+    /// BIP174 serialized PSBT, the UTXO spent by each signed input must be set
+    #[prost(bytes="vec", tag="1")]
+    pub psbt: ::prost::alloc::vec::Vec<u8>,
+    /// Derivation path of the key signing each input, in the order of the inputs
+    /// An empty path skips the input, and if no path is given, every input spendable
+    /// by the key of the request is signed
+    #[prost(string, repeated, tag="2")]
+    pub derivation_paths: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+/// Transaction signing output.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignOutput {
+    /// BIP174 serialized PSBT with the partial signatures of the signed inputs
+    #[prost(bytes="vec", tag="1")]
+    pub psbt: ::prost::alloc::vec::Vec<u8>,
+    /// Network serialized transaction, empty unless every input can be finalized
+    #[prost(bytes="vec", tag="2")]
+    pub transaction: ::prost::alloc::vec::Vec<u8>,
+}
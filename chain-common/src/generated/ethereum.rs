@@ -1,3 +1,13 @@
+/// Address and storage slots accessed by a transaction, which are charged as warm accesses
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AccessListItem {
+    /// hex encoded address
+    #[prost(string, tag="1")]
+    pub address: ::prost::alloc::string::String,
+    /// hex encoded 32-byte storage keys
+    #[prost(string, repeated, tag="2")]
+    pub storage_keys: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
 /// Input data necessary to create a signed transaction.
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SignInput {
@@ -24,12 +34,17 @@ pub struct SignInput {
     /// hex encoded amount number
     #[prost(string, tag="7")]
     pub amount: ::prost::alloc::string::String,
-    /// Recipient's address.
+    /// Recipient's address, empty for contract creation transactions whose payload is the init code
     #[prost(string, tag="8")]
     pub to_address: ::prost::alloc::string::String,
     /// payload data
     #[prost(bytes="vec", tag="9")]
     pub payload: ::prost::alloc::vec::Vec<u8>,
+    /// Access list of EIP-2930 and EIP1559 transactions, legacy transactions have none
+    #[prost(message, repeated, tag="10")]
+    pub access_list: ::prost::alloc::vec::Vec<AccessListItem>,
+    #[prost(enumeration="TransactionType", tag="11")]
+    pub tx_type: i32,
 }
 /// Transaction signing output.
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -46,4 +61,68 @@ pub struct SignOutput {
     /// The payload part, supplied in the input or assembled from input parameters
     #[prost(bytes="vec", tag="5")]
     pub data: ::prost::alloc::vec::Vec<u8>,
+    /// Checksummed address of the contract deployed by a contract creation transaction, empty otherwise
+    #[prost(string, tag="6")]
+    pub contract_address: ::prost::alloc::string::String,
+}
+/// Fields of a decoded transaction, numbers are hex encoded like the SignInput
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DecodeOutput {
+    #[prost(enumeration="TransactionType", tag="1")]
+    pub tx_type: i32,
+    /// Chain id of a typed transaction or of the EIP-155 `v` of a legacy one, 0 if there's none
+    #[prost(uint64, tag="2")]
+    pub chain_id: u64,
+    #[prost(string, tag="3")]
+    pub nonce: ::prost::alloc::string::String,
+    /// Legacy and EIP-2930 fee
+    #[prost(string, tag="4")]
+    pub gas_price: ::prost::alloc::string::String,
+    #[prost(string, tag="5")]
+    pub gas_limit: ::prost::alloc::string::String,
+    /// EIP1559 fees
+    #[prost(string, tag="6")]
+    pub max_inclusion_fee_per_gas: ::prost::alloc::string::String,
+    #[prost(string, tag="7")]
+    pub max_fee_per_gas: ::prost::alloc::string::String,
+    #[prost(string, tag="8")]
+    pub amount: ::prost::alloc::string::String,
+    /// Checksummed recipient's address, empty for contract creation transactions
+    #[prost(string, tag="9")]
+    pub to_address: ::prost::alloc::string::String,
+    #[prost(bytes="vec", tag="10")]
+    pub payload: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, repeated, tag="11")]
+    pub access_list: ::prost::alloc::vec::Vec<AccessListItem>,
+    /// Whether the transaction has a signature, the following fields are only set if it does
+    #[prost(bool, tag="12")]
+    pub signed: bool,
+    #[prost(uint32, tag="13")]
+    pub v: u32,
+    #[prost(bytes="vec", tag="14")]
+    pub r: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="15")]
+    pub s: ::prost::alloc::vec::Vec<u8>,
+    /// keccak256 of the encoded transaction, which is the transaction hash if signed, or the hash
+    /// to sign otherwise
+    #[prost(bytes="vec", tag="16")]
+    pub hash: ::prost::alloc::vec::Vec<u8>,
+    /// Checksummed address of the sender recovered from the signature
+    #[prost(string, tag="17")]
+    pub from_address: ::prost::alloc::string::String,
+    /// Whether the sender is recovered and the transaction is signed for the requested chain id
+    #[prost(bool, tag="18")]
+    pub valid_signature: bool,
+}
+/// Type of the signed transaction
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum TransactionType {
+    /// Legacy if gas_price > 0, EIP-2930 as well if there's an access list, otherwise EIP1559
+    Auto = 0,
+    Legacy = 1,
+    /// EIP-2930 (type 1) transaction with the legacy fee and an access list
+    AccessList = 2,
+    /// EIP-1559 (type 2) transaction
+    Eip1559 = 3,
 }
@@ -0,0 +1,68 @@
+/// Mortal era of a transaction, the transaction is immortal if it's not set.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Era {
+    /// Number of the block the era starts from
+    #[prost(uint64, tag="1")]
+    pub block_number: u64,
+    /// Number of blocks the transaction stays valid for
+    #[prost(uint64, tag="2")]
+    pub period: u64,
+}
+/// Transfer of the native balance to another account.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BalanceTransfer {
+    /// SS58 encoded address of the recipient
+    #[prost(string, tag="1")]
+    pub to_address: ::prost::alloc::string::String,
+    /// hex encoded amount number (128-bit number)
+    #[prost(string, tag="2")]
+    pub value: ::prost::alloc::string::String,
+    /// Module and call index of the transfer call
+    /// Polkadot's Balances.transfer [0x05, 0x00] is used if empty
+    #[prost(bytes="vec", tag="3")]
+    pub call_indices: ::prost::alloc::vec::Vec<u8>,
+}
+/// Input data necessary to create a signed extrinsic.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignInput {
+    /// Hash of the block the era starts from, or the genesis hash for an immortal transaction
+    #[prost(bytes="vec", tag="1")]
+    pub block_hash: ::prost::alloc::vec::Vec<u8>,
+    /// Hash of the genesis block of the network
+    #[prost(bytes="vec", tag="2")]
+    pub genesis_hash: ::prost::alloc::vec::Vec<u8>,
+    /// Nonce of the signing account
+    #[prost(uint64, tag="3")]
+    pub nonce: u64,
+    /// Runtime spec version
+    #[prost(uint32, tag="4")]
+    pub spec_version: u32,
+    /// Runtime transaction version
+    #[prost(uint32, tag="5")]
+    pub transaction_version: u32,
+    /// hex encoded tip number (128-bit number)
+    #[prost(string, tag="6")]
+    pub tip: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="7")]
+    pub era: ::core::option::Option<Era>,
+    #[prost(oneof="sign_input::MessageOneof", tags="8")]
+    pub message_oneof: ::core::option::Option<sign_input::MessageOneof>,
+}
+/// Nested message and enum types in `SignInput`.
+pub mod sign_input {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum MessageOneof {
+        #[prost(message, tag="8")]
+        BalanceTransfer(super::BalanceTransfer),
+    }
+}
+/// Transaction signing output.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignOutput {
+    /// SCALE encoded signed extrinsic
+    #[prost(bytes="vec", tag="1")]
+    pub encoded: ::prost::alloc::vec::Vec<u8>,
+    /// Signature of the signing payload
+    #[prost(bytes="vec", tag="2")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+}
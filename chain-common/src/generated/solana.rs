@@ -0,0 +1,51 @@
+/// An account referenced by an instruction.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AccountMeta {
+    /// base58 encoded public key of the account
+    #[prost(string, tag="1")]
+    pub pubkey: ::prost::alloc::string::String,
+    /// Whether the transaction must be signed by this account
+    #[prost(bool, tag="2")]
+    pub is_signer: bool,
+    /// Whether the instruction may modify this account
+    #[prost(bool, tag="3")]
+    pub is_writable: bool,
+}
+/// A single instruction executed by an on-chain program.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Instruction {
+    /// base58 encoded id of the program executing the instruction
+    #[prost(string, tag="1")]
+    pub program_id: ::prost::alloc::string::String,
+    /// Accounts the instruction reads or writes, in the order expected by the program
+    #[prost(message, repeated, tag="2")]
+    pub accounts: ::prost::alloc::vec::Vec<AccountMeta>,
+    /// Opaque instruction data passed to the program
+    #[prost(bytes="vec", tag="3")]
+    pub data: ::prost::alloc::vec::Vec<u8>,
+}
+/// Input data necessary to create a signed transaction.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignInput {
+    /// base58 encoded recent blockhash
+    #[prost(string, tag="1")]
+    pub recent_blockhash: ::prost::alloc::string::String,
+    /// base58 encoded address paying the transaction fee
+    /// The address of the signing key is used if empty
+    #[prost(string, tag="2")]
+    pub fee_payer: ::prost::alloc::string::String,
+    /// Instructions of the transaction, executed in order
+    #[prost(message, repeated, tag="3")]
+    pub instructions: ::prost::alloc::vec::Vec<Instruction>,
+}
+/// Transaction signing output.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignOutput {
+    /// base58 encoded transaction in wire format.
+    /// Signatures of other required signers are left zeroed.
+    #[prost(string, tag="1")]
+    pub encoded: ::prost::alloc::string::String,
+    /// base58 encoded signature of the signing key
+    #[prost(string, tag="2")]
+    pub signature: ::prost::alloc::string::String,
+}
@@ -1,6 +1,10 @@
 mod generated;
 pub use generated::api;
+pub use generated::arweave;
+pub use generated::bitcoin;
 pub use generated::ethereum;
+pub use generated::polkadot;
+pub use generated::solana;
 
 pub mod coin;
 pub mod entry;
@@ -1,25 +1,49 @@
+use super::coin::Coin;
 use super::public_key::PublicKey;
 use crypto::curve::Curve;
 use crypto::public_key::PublicKeyType;
 use crypto::Error as CryptoError;
 use std::str::FromStr;
 use std::string::ToString;
+use zeroize::{Zeroize, Zeroizing};
 
 // The number of bytes in a private key.
 const VALID_SIZE: u8 = 32;
 // The number of bytes in an extended private key.
 const VALID_EXTENDED_SIZE: u8 = 3 * VALID_SIZE;
+// The number of bytes in a sr25519 private key followed by its nonce.
+const VALID_SR25519_SIZE: u8 = 2 * VALID_SIZE;
 
+#[derive(Clone, Copy, PartialEq)]
 pub enum PrivateKeyType {
     PrivateKeyTypeDefault32 = 0,  // 32-byte private key
     PrivateKeyTypeExtended96 = 1, // 3*32-byte extended private key
     PrivateKeyTypeHd = 2,         // 32-byte private key
+    PrivateKeyTypeSr25519 = 3,    // 32-byte private key with a 32-byte nonce
+    PrivateKeyTypeRsa = 4,        // PKCS#1 DER encoded RSA private key
 }
 
+/// The key material of a private key is wiped when it's dropped
+#[derive(Clone)]
 pub struct PrivateKey {
     pub data: Vec<u8>,
     pub extends_data: Vec<u8>,
     pub chain_code_bytes: Vec<u8>,
+    key_type: PrivateKeyType,
+}
+
+impl Zeroize for PrivateKey {
+    fn zeroize(&mut self) {
+        self.data.zeroize();
+        self.extends_data.zeroize();
+        self.chain_code_bytes.zeroize();
+    }
+}
+
+impl Drop for PrivateKey {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
 }
 
 impl PrivateKey {
@@ -27,32 +51,48 @@ impl PrivateKey {
         match curve {
             Curve::Ed25519Extended => PrivateKeyType::PrivateKeyTypeExtended96,
             Curve::Ed25519hd => PrivateKeyType::PrivateKeyTypeHd,
+            Curve::Sr25519 => PrivateKeyType::PrivateKeyTypeSr25519,
             _ => PrivateKeyType::PrivateKeyTypeDefault32,
         }
     }
 
-    fn is_valid_data(data: &[u8]) -> bool {
-        // Check length.  Extended key needs 3*32 bytes.
-        if data.len() as u8 != VALID_SIZE && data.len() as u8 != VALID_EXTENDED_SIZE {
+    /// The type of the private keys of `coin`, RSA coins like Arweave have no curve
+    pub fn get_private_key_type_of_coin(coin: &Coin) -> Result<PrivateKeyType, CryptoError> {
+        if PublicKeyType::from_str(&coin.public_key_type) == Ok(PublicKeyType::Rsa) {
+            return Ok(PrivateKeyType::PrivateKeyTypeRsa);
+        }
+        Ok(Self::get_private_key_type(&Curve::from_str(&coin.curve)?))
+    }
+
+    fn is_valid_data(data: &[u8], key_type: PrivateKeyType) -> bool {
+        // Check length.  Extended key needs 3*32 bytes, sr25519 key needs 2*32 bytes.
+        let size = match key_type {
+            // RSA keys are stored as their PKCS#1 DER encoding
+            PrivateKeyType::PrivateKeyTypeRsa => return crypto::rsa::is_valid_private_key(data),
+            PrivateKeyType::PrivateKeyTypeExtended96 => VALID_EXTENDED_SIZE,
+            PrivateKeyType::PrivateKeyTypeSr25519 => VALID_SR25519_SIZE,
+            _ => VALID_SIZE,
+        };
+        if data.len() != size as usize {
             return false;
         }
         // Check whether data is not all zero
-        return data.iter().any(|&x| x != 0);
+        data.iter().any(|&x| x != 0)
     }
 
     pub fn is_valid(data: &[u8], curve: &str) -> Result<(), CryptoError> {
-        if !Self::is_valid_data(data) {
+        let curve = Curve::from_str(curve).map_err(|_| CryptoError::NotSupportedCurve)?;
+        if !Self::is_valid_data(data, Self::get_private_key_type(&curve)) {
             return Err(CryptoError::InvalidPrivateKey);
         }
-        Curve::from_str(curve)
-            .map_err(|_| CryptoError::NotSupportedCurve)
-            .map(|_| {})
+        Ok(())
     }
 
     fn new_extended(data: &[u8], ext: &[u8], chain_code: &[u8]) -> Result<PrivateKey, CryptoError> {
-        if !Self::is_valid_data(data)
-            || !Self::is_valid_data(ext)
-            || !Self::is_valid_data(chain_code)
+        let key_type = PrivateKeyType::PrivateKeyTypeDefault32;
+        if !Self::is_valid_data(data, key_type)
+            || !Self::is_valid_data(ext, key_type)
+            || !Self::is_valid_data(chain_code, key_type)
         {
             return Err(CryptoError::InvalidPrivateKey);
         }
@@ -60,21 +100,44 @@ impl PrivateKey {
             data: data.to_vec(),
             extends_data: ext.to_vec(),
             chain_code_bytes: chain_code.to_vec(),
+            key_type: PrivateKeyType::PrivateKeyTypeExtended96,
         })
     }
 
+    /// Create a 32-byte private key, or a 3*32-byte extended private key
     pub fn new(data: &[u8]) -> Result<PrivateKey, CryptoError> {
-        if !Self::is_valid_data(data) {
-            return Err(CryptoError::InvalidPrivateKey);
-        }
         if data.len() == VALID_EXTENDED_SIZE as usize {
-            Self::new_extended(&data[0..32], &data[32..64], &data[64..96])
+            Self::new_of_type(data, PrivateKeyType::PrivateKeyTypeExtended96)
         } else {
-            Ok(PrivateKey {
+            Self::new_of_type(data, PrivateKeyType::PrivateKeyTypeDefault32)
+        }
+    }
+
+    /// Create a private key of the curve of `coin`
+    pub fn new_of_coin(data: &[u8], coin: &Coin) -> Result<PrivateKey, CryptoError> {
+        Self::new_of_type(data, Self::get_private_key_type_of_coin(coin)?)
+    }
+
+    pub fn new_of_type(data: &[u8], key_type: PrivateKeyType) -> Result<PrivateKey, CryptoError> {
+        if !Self::is_valid_data(data, key_type) {
+            return Err(CryptoError::InvalidPrivateKey);
+        }
+        match key_type {
+            PrivateKeyType::PrivateKeyTypeExtended96 => {
+                Self::new_extended(&data[0..32], &data[32..64], &data[64..96])
+            }
+            PrivateKeyType::PrivateKeyTypeSr25519 => Ok(PrivateKey {
+                data: data[0..32].to_vec(),
+                extends_data: data[32..64].to_vec(),
+                chain_code_bytes: vec![],
+                key_type,
+            }),
+            _ => Ok(PrivateKey {
                 data: data.to_vec(),
                 extends_data: vec![],
                 chain_code_bytes: vec![],
-            })
+                key_type,
+            }),
         }
     }
 
@@ -101,14 +164,23 @@ impl FromStr for PrivateKey {
 
 impl ToString for PrivateKey {
     fn to_string(&self) -> String {
-        hex::encode(&self.data)
+        match self.key_type {
+            // sr25519 private keys can't be used without their nonce
+            PrivateKeyType::PrivateKeyTypeSr25519 => {
+                let data = Zeroizing::new([&self.data[..], &self.extends_data[..]].concat());
+                hex::encode(data.as_slice())
+            }
+            _ => hex::encode(&self.data),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::private_key::PrivateKey;
+    use crate::private_key::{PrivateKey, PrivateKeyType};
     use hex;
+    use std::str::FromStr;
+    use zeroize::Zeroize;
 
     #[test]
     fn test_get_public_key_secp256k1extended() {
@@ -128,4 +200,53 @@ mod tests {
         let pub_key_hex2 = hex::encode(&pub_key2.data);
         assert_eq!(pub_key_hex2, "0499c6f51ad6f98c9c583f8e92bb7758ab2ca9a04110c0a1126ec43e5453d196c166b489a4b7c491e7688e6ebea3a71fc3a1a48d60f98d5ce84c93b65e423fde91");
     }
+
+    #[test]
+    fn test_key_type() {
+        let sr25519_key = [[1u8; 32], [2u8; 32]].concat();
+        assert!(PrivateKey::new(&sr25519_key).is_err());
+        assert!(PrivateKey::is_valid(&sr25519_key, "secp256k1").is_err());
+        assert!(PrivateKey::is_valid(&sr25519_key, "ed25519").is_err());
+        assert!(PrivateKey::is_valid(&sr25519_key, "sr25519").is_ok());
+        assert!(PrivateKey::is_valid(&sr25519_key[..32], "sr25519").is_err());
+
+        let private_key =
+            PrivateKey::new_of_type(&sr25519_key, PrivateKeyType::PrivateKeyTypeSr25519).unwrap();
+        assert_eq!(private_key.data, [1u8; 32]);
+        assert_eq!(private_key.extends_data, [2u8; 32]);
+        assert_eq!(private_key.to_string(), hex::encode(&sr25519_key));
+
+        let private_key = PrivateKey::new(&[1u8; 32]).unwrap();
+        assert_eq!(private_key.to_string(), hex::encode([1u8; 32]));
+    }
+
+    #[test]
+    fn test_rsa_key_type() {
+        let der =
+            crypto::rsa::Jwk::from_str(include_str!("../../crypto/resource/test_rsa_key.json"))
+                .unwrap()
+                .to_der()
+                .unwrap();
+        assert!(PrivateKey::new(&der).is_err());
+        let private_key = PrivateKey::new_of_type(&der, PrivateKeyType::PrivateKeyTypeRsa).unwrap();
+        assert_eq!(private_key.data, der);
+        assert!(PrivateKey::new_of_type(&[1u8; 32], PrivateKeyType::PrivateKeyTypeRsa).is_err());
+    }
+
+    #[test]
+    fn test_zeroize() {
+        let sr25519_key = [[1u8; 32], [2u8; 32]].concat();
+        let mut private_key =
+            PrivateKey::new_of_type(&sr25519_key, PrivateKeyType::PrivateKeyTypeSr25519).unwrap();
+        let key = private_key.data.as_ptr();
+        let nonce = private_key.extends_data.as_ptr();
+        private_key.zeroize();
+        assert!(private_key.data.is_empty());
+        assert!(private_key.extends_data.is_empty());
+        // The buffers are still owned by the key, so their wiped bytes can be read
+        unsafe {
+            assert_eq!(std::slice::from_raw_parts(key, 32), [0u8; 32]);
+            assert_eq!(std::slice::from_raw_parts(nonce, 32), [0u8; 32]);
+        }
+    }
 }
@@ -0,0 +1,92 @@
+use chain_common::public_key::PublicKey;
+use crypto::public_key::PublicKeyType;
+use crypto::Error;
+use sha2::{Digest, Sha256};
+
+const ADDRESS_SIZE: usize = 32;
+
+pub struct ArweaveAddress {
+    // SHA-256 hash of the owner's RSA modulus
+    pub data: Vec<u8>,
+}
+
+impl ArweaveAddress {
+    pub fn is_valid(address: &str) -> bool {
+        Self::from_base64url(address).is_some()
+    }
+
+    pub fn new(public_key: &PublicKey) -> Result<Self, Error> {
+        if public_key.r#type != PublicKeyType::Rsa {
+            return Err(Error::NotSupportedPublicKeyType);
+        }
+        Ok(ArweaveAddress {
+            data: Sha256::digest(&public_key.data).to_vec(),
+        })
+    }
+
+    pub fn from_base64url(address: &str) -> Option<Self> {
+        let data = decode(address)?;
+        // Reject the padded and other non canonical encodings of the address
+        if data.len() != ADDRESS_SIZE || encode(&data) != address {
+            return None;
+        }
+        Some(ArweaveAddress { data })
+    }
+}
+
+impl ToString for ArweaveAddress {
+    fn to_string(&self) -> String {
+        encode(&self.data)
+    }
+}
+
+/// Unpadded base64url encoding used for every binary value by Arweave
+pub fn encode(data: &[u8]) -> String {
+    base64::encode_config(data, base64::URL_SAFE_NO_PAD)
+}
+
+pub fn decode(encoded: &str) -> Option<Vec<u8>> {
+    base64::decode_config(encoded, base64::URL_SAFE_NO_PAD).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chain_common::private_key::{PrivateKey, PrivateKeyType};
+    use crypto::rsa::Jwk;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_derive_address() {
+        let jwk =
+            Jwk::from_str(include_str!("../../../crypto/resource/test_rsa_key.json")).unwrap();
+        let private_key =
+            PrivateKey::new_of_type(&jwk.to_der().unwrap(), PrivateKeyType::PrivateKeyTypeRsa)
+                .unwrap();
+        let public_key = private_key.get_public_key("rsa").unwrap();
+        let address = ArweaveAddress::new(&public_key).unwrap();
+        assert_eq!(
+            address.to_string(),
+            "qpTbAIgu-kfWmBueOI9u1gze_YD3we2Nwkq4kJb_zNI"
+        );
+
+        let ed25519_key = PublicKey::new(PublicKeyType::Ed25519, &[1u8; 32]).unwrap();
+        assert!(ArweaveAddress::new(&ed25519_key).is_err());
+    }
+
+    #[test]
+    fn test_is_valid() {
+        assert!(ArweaveAddress::is_valid(
+            "qpTbAIgu-kfWmBueOI9u1gze_YD3we2Nwkq4kJb_zNI"
+        ));
+        // Truncated, padded and standard base64 addresses
+        assert!(!ArweaveAddress::is_valid("3oDgWphQ0m6D5lIBaN9ebDIdznwsH6J"));
+        assert!(!ArweaveAddress::is_valid(
+            "qpTbAIgu-kfWmBueOI9u1gze_YD3we2Nwkq4kJb_zNI="
+        ));
+        assert!(!ArweaveAddress::is_valid(
+            "qpTbAIgu+kfWmBueOI9u1gze/YD3we2Nwkq4kJb/zNI"
+        ));
+        assert!(!ArweaveAddress::is_valid(""));
+    }
+}
@@ -0,0 +1,48 @@
+use sha2::{Digest, Sha384};
+
+/// A piece of data hashed by the Arweave deep hash algorithm
+pub enum DeepHashItem {
+    Blob(Vec<u8>),
+    List(Vec<DeepHashItem>),
+}
+
+impl DeepHashItem {
+    /// SHA-384 deep hash, blobs and lists are tagged with their length so that
+    /// different structures of the same bytes can't collide
+    pub fn hash(&self) -> Vec<u8> {
+        match self {
+            DeepHashItem::Blob(data) => {
+                let tag = Sha384::digest(format!("blob{}", data.len()).as_bytes());
+                Sha384::digest(&[&tag[..], &Sha384::digest(data)[..]].concat()).to_vec()
+            }
+            DeepHashItem::List(items) => {
+                let tag = Sha384::digest(format!("list{}", items.len()).as_bytes());
+                items.iter().fold(tag.to_vec(), |acc, item| {
+                    Sha384::digest(&[acc, item.hash()].concat()).to_vec()
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DeepHashItem::*;
+
+    #[test]
+    fn test_deep_hash() {
+        assert_eq!(
+            hex::encode(Blob(b"abc".to_vec()).hash()),
+            "71115a30152ebcffb6defbb643abc8ef76f01fe323f1d62340646085960f6e347cb2d8e9a46ddee655b3012c6131d4e0"
+        );
+        let list = List(vec![
+            Blob(b"a".to_vec()),
+            List(vec![Blob(b"b".to_vec()), Blob(b"c".to_vec())]),
+            List(vec![]),
+        ]);
+        assert_eq!(
+            hex::encode(list.hash()),
+            "e9714131620fcd0c00fcb9121bec9d8f04218190f4430781e64e7310b83fe050e60a9b52d07e4d72e87c88ea5fa5c98d"
+        );
+    }
+}
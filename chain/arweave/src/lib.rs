@@ -0,0 +1,4 @@
+pub mod address;
+mod deep_hash;
+pub mod entry;
+mod signer;
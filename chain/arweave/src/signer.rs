@@ -0,0 +1,158 @@
+use super::address::{decode, encode};
+use super::deep_hash::DeepHashItem::{self, Blob, List};
+use chain_common::arweave::{SignInput, SignOutput};
+use chain_common::private_key::PrivateKey;
+use chain_common::Error;
+use crypto::rsa;
+use sha2::{Digest, Sha256};
+
+// Transactions with a data root and size instead of inline data
+const FORMAT: &str = "2";
+
+pub struct Signer;
+
+impl Signer {
+    pub fn sign(private_key: &PrivateKey, sign_input: &SignInput) -> Result<SignOutput, Error> {
+        let owner = rsa::get_public_key(&private_key.data).map_err(|_| Error::InvalidPrivateKey)?;
+        let message = Self::signature_data(&owner, sign_input)?;
+        let signature =
+            rsa::sign(&private_key.data, &message).map_err(|_| Error::InvalidPrivateKey)?;
+        Ok(SignOutput {
+            id: encode(&Sha256::digest(&signature)),
+            owner: encode(&owner),
+            signature: encode(&signature),
+        })
+    }
+
+    /// Deep hash of the signed fields of the transaction owned by `owner`
+    pub fn signature_data(owner: &[u8], sign_input: &SignInput) -> Result<Vec<u8>, Error> {
+        let tags = sign_input
+            .tags
+            .iter()
+            .map(|tag| {
+                Ok(List(vec![
+                    decode_blob(&tag.name)?,
+                    decode_blob(&tag.value)?,
+                ]))
+            })
+            .collect::<Result<Vec<DeepHashItem>, Error>>()?;
+        let item = List(vec![
+            Blob(FORMAT.as_bytes().to_vec()),
+            Blob(owner.to_vec()),
+            decode_blob(&sign_input.target)?,
+            number_blob(&sign_input.quantity)?,
+            number_blob(&sign_input.reward)?,
+            decode_blob(&sign_input.last_tx)?,
+            List(tags),
+            number_blob(&sign_input.data_size)?,
+            decode_blob(&sign_input.data_root)?,
+        ]);
+        Ok(item.hash())
+    }
+}
+
+fn decode_blob(encoded: &str) -> Result<DeepHashItem, Error> {
+    decode(encoded).map(Blob).ok_or(Error::InvalidSignInput)
+}
+
+// Winston amounts and sizes are signed as decimal strings, an empty one is zero
+fn number_blob(number: &str) -> Result<DeepHashItem, Error> {
+    if number.is_empty() {
+        return Ok(Blob(b"0".to_vec()));
+    }
+    if !number.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(Error::InvalidSignInput);
+    }
+    Ok(Blob(number.as_bytes().to_vec()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chain_common::arweave::Tag;
+    use chain_common::private_key::PrivateKeyType;
+    use crypto::rsa::Jwk;
+    use std::str::FromStr;
+
+    fn private_key() -> PrivateKey {
+        let jwk =
+            Jwk::from_str(include_str!("../../../crypto/resource/test_rsa_key.json")).unwrap();
+        PrivateKey::new_of_type(&jwk.to_der().unwrap(), PrivateKeyType::PrivateKeyTypeRsa).unwrap()
+    }
+
+    fn tag(name: &str, value: &str) -> Tag {
+        Tag {
+            name: encode(name.as_bytes()),
+            value: encode(value.as_bytes()),
+        }
+    }
+
+    fn sign_input() -> SignInput {
+        SignInput {
+            target: "qpTbAIgu-kfWmBueOI9u1gze_YD3we2Nwkq4kJb_zNI".to_owned(),
+            quantity: "1000000000000".to_owned(),
+            reward: "123456".to_owned(),
+            last_tx: "8aAhvRPM5gZpGYTMr7vvpyQwDtx2_RPxkrnSPWS2Pf2Ze1kItYQySptJ_fLmGDQA".to_owned(),
+            tags: vec![
+                tag("Content-Type", "text/plain"),
+                tag("App-Name", "MaskWallet"),
+            ],
+            data_size: "11".to_owned(),
+            data_root: "ZmAt2vJ6K8lxYDKrQfT9AqtvTzqXhOb-_8iNoSD4_mU".to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_signature_data() {
+        let owner = rsa::get_public_key(&private_key().data).unwrap();
+        assert_eq!(
+            hex::encode(Signer::signature_data(&owner, &sign_input()).unwrap()),
+            "594713bdeb27529f2fedfedbd5d55dce734aac8944a6e118c892edf9bb569cd47677288701adca963a150c214752d3a9"
+        );
+
+        let transfer_without_data = SignInput {
+            target: String::new(),
+            quantity: "0".to_owned(),
+            reward: "100".to_owned(),
+            tags: vec![],
+            data_size: String::new(),
+            data_root: String::new(),
+            ..sign_input()
+        };
+        assert_eq!(
+            hex::encode(Signer::signature_data(&owner, &transfer_without_data).unwrap()),
+            "994977826841170b0a3ce3a3f3cf6c681833dc762dcff1ede852a6f36b3687cc47c2797348f5e69531b31155826434e6"
+        );
+    }
+
+    #[test]
+    fn test_sign() {
+        let private_key = private_key();
+        let output = Signer::sign(&private_key, &sign_input()).unwrap();
+
+        let owner = decode(&output.owner).unwrap();
+        assert_eq!(owner, rsa::get_public_key(&private_key.data).unwrap());
+        let signature = decode(&output.signature).unwrap();
+        let message = Signer::signature_data(&owner, &sign_input()).unwrap();
+        assert!(rsa::verify(&owner, &message, &signature));
+        assert_eq!(output.id, encode(&Sha256::digest(&signature)));
+    }
+
+    #[test]
+    fn test_invalid_sign_input() {
+        let invalid_target = SignInput {
+            target: "not base64url!".to_owned(),
+            ..sign_input()
+        };
+        assert!(Signer::sign(&private_key(), &invalid_target).is_err());
+
+        let invalid_quantity = SignInput {
+            quantity: "1.5".to_owned(),
+            ..sign_input()
+        };
+        assert!(Signer::sign(&private_key(), &invalid_quantity).is_err());
+
+        let secp256k1_key = PrivateKey::new(&[1u8; 32]).unwrap();
+        assert!(Signer::sign(&secp256k1_key, &sign_input()).is_err());
+    }
+}
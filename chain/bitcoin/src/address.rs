@@ -0,0 +1,401 @@
+use bech32::{FromBase32, ToBase32, Variant};
+use bitcoin_lib::hashes::{hash160, Hash};
+use bitcoin_lib::util::base58;
+use chain_common::public_key::PublicKey;
+use crypto::public_key::PublicKeyType;
+use crypto::Error;
+use secp256k1::schnorrsig;
+use secp256k1::Secp256k1;
+use sha2::{Digest, Sha256};
+
+const PUBKEY_HASH_SIZE: usize = 20;
+const SCRIPT_HASH_SIZE: usize = 20;
+const TAPROOT_VERSION: u8 = 1;
+const WITNESS_SCRIPT_HASH_SIZE: usize = 32;
+const MAX_WITNESS_VERSION: u8 = 16;
+
+/// Address prefixes of a bitcoin network
+#[derive(Clone, Debug, PartialEq)]
+pub struct Network {
+    pub p2pkh: u8,
+    pub p2sh: u8,
+    pub hrp: String,
+}
+
+impl Network {
+    pub fn bitcoin() -> Self {
+        Network {
+            p2pkh: 0x00,
+            p2sh: 0x05,
+            hrp: "bc".to_owned(),
+        }
+    }
+}
+
+/// The script type an address pays to, each one derived from its own BIP purpose
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AddressType {
+    // BIP44, P2PKH
+    Legacy,
+    // BIP49, P2WPKH nested in P2SH
+    NestedSegwit,
+    // BIP84, P2WPKH
+    NativeSegwit,
+    // BIP86, P2TR key path spending
+    Taproot,
+}
+
+impl AddressType {
+    pub fn from_purpose(purpose: u32) -> Option<Self> {
+        match purpose {
+            44 => Some(AddressType::Legacy),
+            49 => Some(AddressType::NestedSegwit),
+            84 => Some(AddressType::NativeSegwit),
+            86 => Some(AddressType::Taproot),
+            _ => None,
+        }
+    }
+
+    /// The address type of the purpose in `derivation_path`, legacy for unknown purposes
+    pub fn from_derivation_path(derivation_path: &str) -> Self {
+        derivation_path
+            .split('/')
+            .nth(1)
+            .and_then(|purpose| purpose.trim_end_matches('\'').parse().ok())
+            .and_then(Self::from_purpose)
+            .unwrap_or(AddressType::Legacy)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Payload {
+    PubkeyHash(Vec<u8>),
+    ScriptHash(Vec<u8>),
+    WitnessProgram { version: u8, program: Vec<u8> },
+}
+
+impl Payload {
+    pub fn new(public_key: &PublicKey, address_type: AddressType) -> Result<Self, Error> {
+        if public_key.r#type != PublicKeyType::Secp256k1 {
+            return Err(Error::NotSupportedPublicKeyType);
+        }
+        let pubkey_hash = hash160::Hash::hash(&public_key.data).to_vec();
+        let payload = match address_type {
+            AddressType::Legacy => Payload::PubkeyHash(pubkey_hash),
+            AddressType::NestedSegwit => {
+                let redeem_script = Payload::WitnessProgram {
+                    version: 0,
+                    program: pubkey_hash,
+                }
+                .script_pubkey();
+                Payload::ScriptHash(hash160::Hash::hash(&redeem_script).to_vec())
+            }
+            AddressType::NativeSegwit => Payload::WitnessProgram {
+                version: 0,
+                program: pubkey_hash,
+            },
+            AddressType::Taproot => Payload::WitnessProgram {
+                version: TAPROOT_VERSION,
+                program: taproot_output_key(&public_key.data)?,
+            },
+        };
+        Ok(payload)
+    }
+
+    /// The payload of a standard locking script
+    pub fn from_script_pubkey(script: &[u8]) -> Option<Self> {
+        match script {
+            [0x76, 0xa9, 0x14, hash @ .., 0x88, 0xac] if hash.len() == PUBKEY_HASH_SIZE => {
+                Some(Payload::PubkeyHash(hash.to_vec()))
+            }
+            [0xa9, 0x14, hash @ .., 0x87] if hash.len() == SCRIPT_HASH_SIZE => {
+                Some(Payload::ScriptHash(hash.to_vec()))
+            }
+            [opcode, size, program @ ..]
+                if (*opcode == 0x00 || (0x51..=0x60).contains(opcode))
+                    && *size as usize == program.len()
+                    && (2..=40).contains(size) =>
+            {
+                let version = match opcode {
+                    0x00 => 0,
+                    _ => opcode - 0x50,
+                };
+                Some(Payload::WitnessProgram {
+                    version,
+                    program: program.to_vec(),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// The locking script of outputs paying to the payload
+    pub fn script_pubkey(&self) -> Vec<u8> {
+        match self {
+            Payload::PubkeyHash(hash) => [&[0x76, 0xa9, 0x14], &hash[..], &[0x88, 0xac]].concat(),
+            Payload::ScriptHash(hash) => [&[0xa9, 0x14], &hash[..], &[0x87]].concat(),
+            Payload::WitnessProgram { version, program } => {
+                let opcode = match version {
+                    0 => 0x00,
+                    _ => 0x50 + version,
+                };
+                [&[opcode, program.len() as u8], &program[..]].concat()
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BitcoinAddress {
+    pub network: Network,
+    pub payload: Payload,
+}
+
+impl BitcoinAddress {
+    pub fn is_valid(address: &str, network: &Network) -> bool {
+        Self::from_str(address, network).is_some()
+    }
+
+    pub fn new(
+        public_key: &PublicKey,
+        address_type: AddressType,
+        network: &Network,
+    ) -> Result<Self, Error> {
+        Ok(BitcoinAddress {
+            network: network.clone(),
+            payload: Payload::new(public_key, address_type)?,
+        })
+    }
+
+    /// Decode a base58check or bech32(m) address, which must belong to `network`
+    pub fn from_str(address: &str, network: &Network) -> Option<Self> {
+        let payload = match Self::decode_bech32(address, &network.hrp) {
+            Some(payload) => payload,
+            None => Self::decode_base58(address, network)?,
+        };
+        Some(BitcoinAddress {
+            network: network.clone(),
+            payload,
+        })
+    }
+
+    /// The locking script of outputs paying to the address
+    pub fn script_pubkey(&self) -> Vec<u8> {
+        self.payload.script_pubkey()
+    }
+
+    fn decode_base58(address: &str, network: &Network) -> Option<Payload> {
+        let data = base58::from_check(address).ok()?;
+        let (prefix, hash) = data.split_first()?;
+        match *prefix {
+            prefix if prefix == network.p2pkh && hash.len() == PUBKEY_HASH_SIZE => {
+                Some(Payload::PubkeyHash(hash.to_vec()))
+            }
+            prefix if prefix == network.p2sh && hash.len() == SCRIPT_HASH_SIZE => {
+                Some(Payload::ScriptHash(hash.to_vec()))
+            }
+            _ => None,
+        }
+    }
+
+    // Rules of BIP173 and BIP350
+    fn decode_bech32(address: &str, hrp: &str) -> Option<Payload> {
+        let (decoded_hrp, data, variant) = bech32::decode(address).ok()?;
+        if decoded_hrp != hrp.to_lowercase() {
+            return None;
+        }
+        let (version, program) = data.split_first()?;
+        let version = version.to_u8();
+        let program = Vec::<u8>::from_base32(program).ok()?;
+        let valid = match version {
+            0 => {
+                variant == Variant::Bech32
+                    && (program.len() == PUBKEY_HASH_SIZE
+                        || program.len() == WITNESS_SCRIPT_HASH_SIZE)
+            }
+            1..=MAX_WITNESS_VERSION => {
+                variant == Variant::Bech32m && (2..=40).contains(&program.len())
+            }
+            _ => false,
+        };
+        if !valid {
+            return None;
+        }
+        Some(Payload::WitnessProgram { version, program })
+    }
+}
+
+impl ToString for BitcoinAddress {
+    fn to_string(&self) -> String {
+        match &self.payload {
+            Payload::PubkeyHash(hash) => {
+                base58::check_encode_slice(&[&[self.network.p2pkh], &hash[..]].concat())
+            }
+            Payload::ScriptHash(hash) => {
+                base58::check_encode_slice(&[&[self.network.p2sh], &hash[..]].concat())
+            }
+            Payload::WitnessProgram { version, program } => {
+                let variant = match version {
+                    0 => Variant::Bech32,
+                    _ => Variant::Bech32m,
+                };
+                let version = bech32::u5::try_from_u8(*version).expect("valid witness version");
+                let data = [vec![version], program.to_base32()].concat();
+                bech32::encode(&self.network.hrp, data, variant).expect("valid bech32 hrp")
+            }
+        }
+    }
+}
+
+/// BIP341 tagged hash
+pub fn tagged_hash(tag: &str, data: &[u8]) -> Vec<u8> {
+    let tag_hash = Sha256::digest(tag.as_bytes());
+    Sha256::new()
+        .chain(tag_hash)
+        .chain(tag_hash)
+        .chain(data)
+        .finalize()
+        .to_vec()
+}
+
+/// The taproot output key of a key path only output, tweaked from the compressed `public_key` as BIP86
+pub fn taproot_output_key(public_key: &[u8]) -> Result<Vec<u8>, Error> {
+    let internal_key = public_key.get(1..).ok_or(Error::InvalidPublicKey)?;
+    let mut output_key =
+        schnorrsig::PublicKey::from_slice(internal_key).map_err(|_| Error::InvalidPublicKey)?;
+    let tweak = tagged_hash("TapTweak", internal_key);
+    output_key
+        .tweak_add_assign(&Secp256k1::verification_only(), &tweak)
+        .map_err(|_| Error::InvalidPublicKey)?;
+    Ok(output_key.serialize().to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Account 0 key 0 of "abandon abandon ... about" for each purpose
+    const BIP44_PUBLIC_KEY: &str =
+        "03aaeb52dd7494c361049de67cc680e83ebcbbbdbeb13637d92cd845f70308af5e";
+    const BIP49_PUBLIC_KEY: &str =
+        "039b3b694b8fc5b5e07fb069c783cac754f5d38c3e08bed1960e31fdb1dda35c24";
+    const BIP84_PUBLIC_KEY: &str =
+        "0330d54fd0dd420a6e5f8d3624f5f3482cae350f79d5f0753bf5beef9c2d91af3c";
+    const BIP86_PUBLIC_KEY: &str =
+        "03cc8a4bc64d897bddc5fbc2f670f7a8ba0b386779106cf1223c6fc5d7cd6fc115";
+
+    fn derive(public_key: &str, address_type: AddressType) -> String {
+        let public_key =
+            PublicKey::new(PublicKeyType::Secp256k1, &hex::decode(public_key).unwrap()).unwrap();
+        BitcoinAddress::new(&public_key, address_type, &Network::bitcoin())
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn test_derive_address() {
+        assert_eq!(
+            derive(BIP44_PUBLIC_KEY, AddressType::Legacy),
+            "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA"
+        );
+        assert_eq!(
+            derive(BIP49_PUBLIC_KEY, AddressType::NestedSegwit),
+            "37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf"
+        );
+        assert_eq!(
+            derive(BIP84_PUBLIC_KEY, AddressType::NativeSegwit),
+            "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu"
+        );
+        assert_eq!(
+            derive(BIP86_PUBLIC_KEY, AddressType::Taproot),
+            "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr"
+        );
+    }
+
+    #[test]
+    fn test_address_type_from_derivation_path() {
+        let cases = [
+            ("m/44'/0'/0'/0/0", AddressType::Legacy),
+            ("m/49'/0'/0'/0/0", AddressType::NestedSegwit),
+            ("m/84'/0'/0'/0/0", AddressType::NativeSegwit),
+            ("m/86'/0'/0'/0/0", AddressType::Taproot),
+            ("m/0'/0'", AddressType::Legacy),
+            ("", AddressType::Legacy),
+        ];
+        for (path, expected) in cases.iter() {
+            assert_eq!(AddressType::from_derivation_path(path), *expected);
+        }
+    }
+
+    #[test]
+    fn test_is_valid() {
+        let network = Network::bitcoin();
+        let valid = [
+            "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA",
+            "37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf",
+            "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+            "BC1QCR8TE4KR609GCAWUTMRZA0J4XV80JY8Z306FYU",
+            "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr",
+            // BIP350 vectors
+            "bc1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3qccfmv3",
+            "bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kt5nd6y",
+            "BC1SW50QGDZ25J",
+            "bc1zw508d6qejxtdg4y5r3zarvaryvaxxpcs",
+        ];
+        for address in valid.iter() {
+            assert!(BitcoinAddress::is_valid(address, &network), "{}", address);
+        }
+
+        let invalid = [
+            "",
+            "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabB",
+            // Testnet addresses
+            "mkpZhYtJu2r87Js3pDiWJDmPte2NRZ8bJV",
+            "tb1q6rz28mcfaxtmd6v789l9rrlrusdprr9pqcpvkl",
+            // Taproot address with bech32 checksum
+            "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqh2y7hd",
+            // Segwit v0 address with bech32m checksum
+            "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyl",
+            // Mixed case
+            "bc1qCR8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+            // Invalid program lengths
+            "bc1pw5dgrnzv",
+            "bc1zw508d6qejxtdg4y5r3zarvaryvaxxpc0",
+            "bc1gmk9yu",
+        ];
+        for address in invalid.iter() {
+            assert!(!BitcoinAddress::is_valid(address, &network), "{}", address);
+        }
+    }
+
+    #[test]
+    fn test_script_pubkey() {
+        let network = Network::bitcoin();
+        let cases = [
+            (
+                "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA",
+                "76a914d986ed01b7a22225a70edbf2ba7cfb63a15cb3aa88ac",
+            ),
+            (
+                "37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf",
+                "a9143fb6e95812e57bb4691f9a4a628862a61a4f769b87",
+            ),
+            (
+                "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+                "0014c0cebcd6c3d3ca8c75dc5ec62ebe55330ef910e2",
+            ),
+            (
+                "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr",
+                "5120a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c",
+            ),
+        ];
+        for (address, expected) in cases.iter() {
+            let address = BitcoinAddress::from_str(address, &network).unwrap();
+            assert_eq!(hex::encode(address.script_pubkey()), *expected);
+            assert_eq!(
+                Payload::from_script_pubkey(&hex::decode(expected).unwrap()),
+                Some(address.payload)
+            );
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use super::address::{AddressType, BitcoinAddress, Network};
+use super::signer::Signer;
+use bytes::BytesMut;
+use chain_common::bitcoin::SignInput;
+use chain_common::coin::Coin;
+use chain_common::entry::{ChainExportType, ChainImportType, Entry};
+use chain_common::private_key::PrivateKey;
+use chain_common::public_key::PublicKey;
+use crypto::Error;
+use prost::Message;
+
+pub struct BitcoinEntry {
+    // Address prefixes of the network
+    pub network: Network,
+}
+
+impl BitcoinEntry {
+    /// Entry of the network configured by the `p2pkh`, `p2sh` and `hrp` of `coin`, Bitcoin mainnet if absent
+    pub fn new(coin: &Coin) -> Self {
+        let mut network = Network::bitcoin();
+        if let Some(p2pkh) = coin.get_value("p2pkh").and_then(|x| x.parse().ok()) {
+            network.p2pkh = p2pkh;
+        }
+        if let Some(p2sh) = coin.get_value("p2sh").and_then(|x| x.parse().ok()) {
+            network.p2sh = p2sh;
+        }
+        if let Some(hrp) = coin.get_value("hrp") {
+            network.hrp = hrp;
+        }
+        BitcoinEntry { network }
+    }
+
+    // The network with the prefixes passed to `derive_address`, which take precedence over the entry's
+    fn network_with_prefixes(&self, p2pkh: &[u8], hrp: &[u8]) -> Result<Network, Error> {
+        let mut network = self.network.clone();
+        if let Some(p2pkh) = p2pkh.first() {
+            network.p2pkh = *p2pkh;
+        }
+        if !hrp.is_empty() {
+            network.hrp = String::from_utf8(hrp.to_vec()).map_err(|_| Error::InvalidPublicKey)?;
+        }
+        Ok(network)
+    }
+}
+
+impl Entry for BitcoinEntry {
+    fn get_supported_import_types(&self) -> Vec<ChainImportType> {
+        vec![
+            ChainImportType::PrivateKey,
+            ChainImportType::Mnemonic,
+            ChainImportType::WatchOnly,
+        ]
+    }
+
+    fn get_supported_export_types(&self) -> Vec<ChainExportType> {
+        vec![ChainExportType::PrivateKey]
+    }
+
+    fn validate_address(&self, address: &str) -> bool {
+        BitcoinAddress::is_valid(address, &self.network)
+    }
+
+    fn derive_address(
+        &self,
+        coin: &Coin,
+        public_key: &PublicKey,
+        p2pkh: &[u8],
+        hrp: &[u8],
+    ) -> Result<String, Error> {
+        self.derive_address_of_path(coin, public_key, p2pkh, hrp, &coin.derivation_path)
+    }
+
+    fn derive_address_of_path(
+        &self,
+        _coin: &Coin,
+        public_key: &PublicKey,
+        p2pkh: &[u8],
+        hrp: &[u8],
+        derivation_path: &str,
+    ) -> Result<String, Error> {
+        let network = self.network_with_prefixes(p2pkh, hrp)?;
+        let address_type = AddressType::from_derivation_path(derivation_path);
+        let address = BitcoinAddress::new(public_key, address_type, &network)?;
+        Ok(address.to_string())
+    }
+
+    fn sign(
+        &self,
+        coin: &Coin,
+        private_key: &PrivateKey,
+        payload: &[u8],
+    ) -> Result<Vec<u8>, Error> {
+        // Only the key of the request is available
+        let get_key = |_: &str| Err(Error::InvalidPrivateKey);
+        self.sign_with_keys(coin, private_key, &get_key, payload)
+    }
+
+    fn sign_with_keys(
+        &self,
+        _coin: &Coin,
+        private_key: &PrivateKey,
+        get_key: &dyn Fn(&str) -> Result<PrivateKey, Error>,
+        payload: &[u8],
+    ) -> Result<Vec<u8>, Error> {
+        let sign_input: SignInput = match SignInput::decode(payload) {
+            Ok(request) => request,
+            Err(_) => return Err(Error::InvalidPrivateKey),
+        };
+        let output = Signer::sign(private_key, get_key, &sign_input)
+            .map_err(|_| Error::InvalidPrivateKey)?;
+
+        let mut buf = BytesMut::with_capacity(output.encoded_len());
+        output
+            .encode(&mut buf)
+            .expect("Fail to encode the SignOutput");
+        Ok(buf.to_vec())
+    }
+}
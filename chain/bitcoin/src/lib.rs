@@ -0,0 +1,4 @@
+pub mod address;
+pub mod entry;
+mod sighash;
+mod signer;
@@ -0,0 +1,81 @@
+use super::address::tagged_hash;
+use bitcoin_lib::consensus::encode::serialize;
+use bitcoin_lib::{Transaction, TxOut};
+use chain_common::Error;
+use sha2::{Digest, Sha256};
+
+pub const SIGHASH_DEFAULT: u8 = 0x00;
+pub const SIGHASH_NONE: u8 = 0x02;
+pub const SIGHASH_SINGLE: u8 = 0x03;
+pub const SIGHASH_ANYONECANPAY: u8 = 0x80;
+// Bits of the hash type selecting the signed outputs
+const SIGHASH_OUTPUT_MASK: u8 = 0x03;
+
+// Key path spending without annex
+const SPEND_TYPE: u8 = 0x00;
+const SIGHASH_EPOCH: u8 = 0x00;
+
+/// BIP341 signature hash of the key path spending of input `input_index`,
+/// `prevouts` are the outputs spent by all the inputs of `tx`
+pub fn taproot_key_spend_sighash(
+    tx: &Transaction,
+    input_index: usize,
+    prevouts: &[TxOut],
+    hash_type: u8,
+) -> Result<Vec<u8>, Error> {
+    let output_type = hash_type & SIGHASH_OUTPUT_MASK;
+    let anyone_can_pay = hash_type & SIGHASH_ANYONECANPAY != 0;
+    let valid_hash_type = hash_type == SIGHASH_DEFAULT
+        || (hash_type & !(SIGHASH_ANYONECANPAY | SIGHASH_OUTPUT_MASK) == 0
+            && output_type != SIGHASH_DEFAULT);
+    if !valid_hash_type || input_index >= tx.input.len() || prevouts.len() != tx.input.len() {
+        return Err(Error::InvalidSignInput);
+    }
+
+    let mut msg = vec![SIGHASH_EPOCH, hash_type];
+    msg.extend_from_slice(&tx.version.to_le_bytes());
+    msg.extend_from_slice(&tx.lock_time.to_le_bytes());
+    if !anyone_can_pay {
+        let sha_prevouts = sha256_of(
+            tx.input
+                .iter()
+                .map(|input| serialize(&input.previous_output)),
+        );
+        let sha_amounts = sha256_of(prevouts.iter().map(|output| serialize(&output.value)));
+        let sha_script_pubkeys = sha256_of(
+            prevouts
+                .iter()
+                .map(|output| serialize(&output.script_pubkey)),
+        );
+        let sha_sequences = sha256_of(tx.input.iter().map(|input| serialize(&input.sequence)));
+        msg.extend(sha_prevouts);
+        msg.extend(sha_amounts);
+        msg.extend(sha_script_pubkeys);
+        msg.extend(sha_sequences);
+    }
+    if output_type != SIGHASH_NONE && output_type != SIGHASH_SINGLE {
+        msg.extend(sha256_of(tx.output.iter().map(serialize)));
+    }
+    msg.push(SPEND_TYPE);
+    if anyone_can_pay {
+        let input = &tx.input[input_index];
+        msg.extend(serialize(&input.previous_output));
+        msg.extend(serialize(&prevouts[input_index]));
+        msg.extend(serialize(&input.sequence));
+    } else {
+        msg.extend_from_slice(&(input_index as u32).to_le_bytes());
+    }
+    if output_type == SIGHASH_SINGLE {
+        let output = tx.output.get(input_index).ok_or(Error::InvalidSignInput)?;
+        msg.extend(Sha256::digest(&serialize(output)));
+    }
+    Ok(tagged_hash("TapSighash", &msg))
+}
+
+fn sha256_of<I: Iterator<Item = Vec<u8>>>(items: I) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    for item in items {
+        hasher.update(item);
+    }
+    hasher.finalize().to_vec()
+}
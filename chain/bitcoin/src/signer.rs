@@ -0,0 +1,449 @@
+use super::address::{tagged_hash, AddressType, Payload};
+use super::sighash::{taproot_key_spend_sighash, SIGHASH_DEFAULT};
+use bitcoin_lib::blockdata::script::Builder;
+use bitcoin_lib::consensus::encode::{deserialize, serialize};
+use bitcoin_lib::hashes::{hash160, Hash};
+use bitcoin_lib::util::bip143::SigHashCache;
+use bitcoin_lib::util::psbt::{raw, PartiallySignedTransaction};
+use bitcoin_lib::{SigHashType, Transaction, TxOut};
+use chain_common::bitcoin::{SignInput, SignOutput};
+use chain_common::private_key::PrivateKey;
+use chain_common::Error;
+use crypto::Error as CryptoError;
+use secp256k1::{schnorrsig, Message, Secp256k1, SecretKey};
+
+// PSBT_IN_TAP_KEY_SIG of BIP371, unknown to rust-bitcoin 0.26
+const PSBT_IN_TAP_KEY_SIG: u8 = 0x13;
+
+const ADDRESS_TYPES: [AddressType; 4] = [
+    AddressType::Legacy,
+    AddressType::NestedSegwit,
+    AddressType::NativeSegwit,
+    AddressType::Taproot,
+];
+
+pub struct Signer;
+
+impl Signer {
+    /// Sign the inputs of the PSBT in `sign_input` with the keys at their derivation paths,
+    /// `private_key` signs every input it can spend if no path is given
+    pub fn sign(
+        private_key: &PrivateKey,
+        get_key: &dyn Fn(&str) -> Result<PrivateKey, CryptoError>,
+        sign_input: &SignInput,
+    ) -> Result<SignOutput, Error> {
+        let mut psbt: PartiallySignedTransaction =
+            deserialize(&sign_input.psbt).map_err(|_| Error::InvalidSignInput)?;
+        let input_count = psbt.inputs.len();
+        if sign_input.derivation_paths.is_empty() {
+            let signed_count = (0..input_count)
+                .filter(|index| Self::sign_input(&mut psbt, *index, private_key).is_ok())
+                .count();
+            if signed_count == 0 {
+                return Err(Error::InvalidSignInput);
+            }
+        } else {
+            if sign_input.derivation_paths.len() != input_count {
+                return Err(Error::InvalidSignInput);
+            }
+            for (index, derivation_path) in sign_input.derivation_paths.iter().enumerate() {
+                if derivation_path.is_empty() {
+                    continue;
+                }
+                let key = get_key(derivation_path).map_err(|_| Error::InvalidPrivateKey)?;
+                Self::sign_input(&mut psbt, index, &key)?;
+            }
+        }
+        let transaction = Self::finalize(&psbt)
+            .map(|tx| serialize(&tx))
+            .unwrap_or_default();
+        Ok(SignOutput {
+            psbt: serialize(&psbt),
+            transaction,
+        })
+    }
+
+    /// Add the signature of `private_key` to input `index`, which must spend an output of the key
+    fn sign_input(
+        psbt: &mut PartiallySignedTransaction,
+        index: usize,
+        private_key: &PrivateKey,
+    ) -> Result<(), Error> {
+        let secp = Secp256k1::new();
+        let secret_key =
+            SecretKey::from_slice(&private_key.data).map_err(|_| Error::InvalidPrivateKey)?;
+        let public_key = private_key
+            .get_public_key("secp256k1")
+            .map_err(|_| Error::InvalidPrivateKey)?;
+        let utxo = spent_output(psbt, index).ok_or(Error::InvalidSignInput)?;
+        let payload = Payload::from_script_pubkey(utxo.script_pubkey.as_bytes())
+            .ok_or(Error::InvalidSignInput)?;
+        let address_type = ADDRESS_TYPES
+            .iter()
+            .copied()
+            .find(|address_type| Payload::new(&public_key, *address_type).as_ref() == Ok(&payload))
+            .ok_or(Error::InvalidSignInput)?;
+
+        let tx = &psbt.global.unsigned_tx;
+        let input = &psbt.inputs[index];
+        if address_type == AddressType::Taproot {
+            let prevouts = (0..tx.input.len())
+                .map(|index| spent_output(psbt, index))
+                .collect::<Option<Vec<TxOut>>>()
+                .ok_or(Error::InvalidSignInput)?;
+            let hash_type = input
+                .sighash_type
+                .map(|sighash_type| sighash_type.as_u32() as u8)
+                .unwrap_or(SIGHASH_DEFAULT);
+            let sighash = taproot_key_spend_sighash(tx, index, &prevouts, hash_type)?;
+            let mut keypair = schnorrsig::KeyPair::from_secret_key(&secp, secret_key);
+            let tweak = tagged_hash("TapTweak", &public_key.data[1..]);
+            keypair
+                .tweak_add_assign(&secp, &tweak)
+                .map_err(|_| Error::InvalidPrivateKey)?;
+            let message = Message::from_slice(&sighash).map_err(|_| Error::InvalidSignInput)?;
+            // Deterministic signature without auxiliary randomness
+            let mut signature = secp
+                .schnorrsig_sign_no_aux_rand(&message, &keypair)
+                .as_ref()
+                .to_vec();
+            if hash_type != SIGHASH_DEFAULT {
+                signature.push(hash_type);
+            }
+            let key = raw::Key {
+                type_value: PSBT_IN_TAP_KEY_SIG,
+                key: vec![],
+            };
+            psbt.inputs[index].unknown.insert(key, signature);
+            return Ok(());
+        }
+
+        let sighash_type = input.sighash_type.unwrap_or(SigHashType::All);
+        let pubkey_hash = hash160::Hash::hash(&public_key.data).to_vec();
+        let script_code =
+            bitcoin_lib::Script::from(Payload::PubkeyHash(pubkey_hash.clone()).script_pubkey());
+        let sighash = match address_type {
+            AddressType::Legacy => {
+                tx.signature_hash(index, &utxo.script_pubkey, sighash_type.as_u32())
+            }
+            _ => {
+                SigHashCache::new(tx).signature_hash(index, &script_code, utxo.value, sighash_type)
+            }
+        };
+        let message = Message::from_slice(&sighash).map_err(|_| Error::InvalidSignInput)?;
+        let mut signature = secp.sign(&message, &secret_key).serialize_der().to_vec();
+        signature.push(sighash_type.as_u32() as u8);
+
+        let input = &mut psbt.inputs[index];
+        if address_type == AddressType::NestedSegwit {
+            let redeem_script = Payload::WitnessProgram {
+                version: 0,
+                program: pubkey_hash,
+            }
+            .script_pubkey();
+            input.redeem_script = Some(redeem_script.into());
+        }
+        input.partial_sigs.insert(
+            bitcoin_lib::PublicKey {
+                compressed: true,
+                key: secp256k1::PublicKey::from_secret_key(&secp, &secret_key),
+            },
+            signature,
+        );
+        Ok(())
+    }
+
+    /// The network transaction of `psbt` if every input has been finalized or is single-key and signed
+    pub fn finalize(psbt: &PartiallySignedTransaction) -> Option<Transaction> {
+        let mut tx = psbt.global.unsigned_tx.clone();
+        for (index, input) in psbt.inputs.iter().enumerate() {
+            let (script_sig, witness) =
+                if input.final_script_sig.is_some() || input.final_script_witness.is_some() {
+                    (
+                        input.final_script_sig.clone().unwrap_or_default(),
+                        input.final_script_witness.clone().unwrap_or_default(),
+                    )
+                } else {
+                    Self::finalize_input(psbt, index)?
+                };
+            tx.input[index].script_sig = script_sig;
+            tx.input[index].witness = witness;
+        }
+        Some(tx)
+    }
+
+    fn finalize_input(
+        psbt: &PartiallySignedTransaction,
+        index: usize,
+    ) -> Option<(bitcoin_lib::Script, Vec<Vec<u8>>)> {
+        let input = &psbt.inputs[index];
+        let utxo = spent_output(psbt, index)?;
+        // The signature of the key hashed to `hash`
+        let signature_of = |hash: &[u8]| {
+            input.partial_sigs.iter().find(|(public_key, _)| {
+                public_key.compressed && hash160::Hash::hash(&public_key.to_bytes())[..] == *hash
+            })
+        };
+        match Payload::from_script_pubkey(utxo.script_pubkey.as_bytes())? {
+            Payload::PubkeyHash(hash) => {
+                let (public_key, signature) = signature_of(&hash)?;
+                let script_sig = Builder::new()
+                    .push_slice(signature)
+                    .push_key(public_key)
+                    .into_script();
+                Some((script_sig, vec![]))
+            }
+            Payload::ScriptHash(hash) => {
+                let redeem_script = input.redeem_script.as_ref()?;
+                if hash160::Hash::hash(redeem_script.as_bytes())[..] != hash[..] {
+                    return None;
+                }
+                match Payload::from_script_pubkey(redeem_script.as_bytes())? {
+                    Payload::WitnessProgram {
+                        version: 0,
+                        program,
+                    } => {
+                        let (public_key, signature) = signature_of(&program)?;
+                        let script_sig = Builder::new()
+                            .push_slice(redeem_script.as_bytes())
+                            .into_script();
+                        Some((script_sig, vec![signature.clone(), public_key.to_bytes()]))
+                    }
+                    _ => None,
+                }
+            }
+            Payload::WitnessProgram {
+                version: 0,
+                program,
+            } => {
+                let (public_key, signature) = signature_of(&program)?;
+                Some((
+                    bitcoin_lib::Script::new(),
+                    vec![signature.clone(), public_key.to_bytes()],
+                ))
+            }
+            Payload::WitnessProgram { version: 1, .. } => {
+                let key = raw::Key {
+                    type_value: PSBT_IN_TAP_KEY_SIG,
+                    key: vec![],
+                };
+                let signature = input.unknown.get(&key)?;
+                Some((bitcoin_lib::Script::new(), vec![signature.clone()]))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The output spent by input `index`, taken from its UTXO in the PSBT
+fn spent_output(psbt: &PartiallySignedTransaction, index: usize) -> Option<TxOut> {
+    let input = psbt.inputs.get(index)?;
+    if let Some(output) = &input.witness_utxo {
+        return Some(output.clone());
+    }
+    let previous_output = psbt.global.unsigned_tx.input.get(index)?.previous_output;
+    let previous_tx = input.non_witness_utxo.as_ref()?;
+    if previous_tx.txid() != previous_output.txid {
+        return None;
+    }
+    previous_tx
+        .output
+        .get(previous_output.vout as usize)
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sighash::{SIGHASH_ANYONECANPAY, SIGHASH_NONE, SIGHASH_SINGLE};
+
+    // Spends outputs of "abandon abandon ... about" at m/{44,49,84,86}'/0'/0'/0/0 in turn,
+    // the first one with its previous transaction and the others with their witness UTXOs
+    const UNSIGNED_PSBT: &str = "70736274ff0100f8020000000496c8b9266068d68e9721f95eacff4aa4cbf904c03aa692aa0573fd9ef2675e170000000000fdffffff11111111111111111111111111111111111111111111111111111111111111110100000000fdffffff22222222222222222222222222222222222222222222222222222222222222220000000000fdffffff33333333333333333333333333333333333333333333333333333333333333330200000000fdffffff02a0bb0d00000000002200201863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262905f010000000000160014c0cebcd6c3d3ca8c75dc5ec62ebe55330ef910e2000000000001005501000000010000000000000000000000000000000000000000000000000000000000000000ffffffff00ffffffff01a0860100000000001976a914d986ed01b7a22225a70edbf2ba7cfb63a15cb3aa88ac0000000000010120400d03000000000017a9143fb6e95812e57bb4691f9a4a628862a61a4f769b870001011fe093040000000000160014c0cebcd6c3d3ca8c75dc5ec62ebe55330ef910e20001012b801a060000000000225120a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c000000";
+    const SIGNED_TRANSACTION: &str = "0200000000010496c8b9266068d68e9721f95eacff4aa4cbf904c03aa692aa0573fd9ef2675e17000000006b483045022100f4de1b30cb9c8c64dc9491f12f00728b8f9a1c972c66926ca57591281d878433022021a32602aa7b2e0455273f23cabf30e9f936533c51fc277362cf8d2d849805e9012103aaeb52dd7494c361049de67cc680e83ebcbbbdbeb13637d92cd845f70308af5efdffffff11111111111111111111111111111111111111111111111111111111111111110100000017160014f990679acafe25c27615373b40bf22446d24ff44fdffffff22222222222222222222222222222222222222222222222222222222222222220000000000fdffffff33333333333333333333333333333333333333333333333333333333333333330200000000fdffffff02a0bb0d00000000002200201863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262905f010000000000160014c0cebcd6c3d3ca8c75dc5ec62ebe55330ef910e20002473044022034afb3e08b5a935ca130897b21e03d16db17167c081933ed00866cd824ec379302205143040ce6239815d2ef00a44454ea045a39eedef1287d7c0e00532273576bcb0121039b3b694b8fc5b5e07fb069c783cac754f5d38c3e08bed1960e31fdb1dda35c240247304402204705e5116a3c6dfecb250570aa26727dbd4b40f8b13ffe9b1a08fd631a8caaa2022015bc9f60732ca0f20f46ebe6011f5989b0c43fbf5a2d9c7a8236980a6b21a22f01210330d54fd0dd420a6e5f8d3624f5f3482cae350f79d5f0753bf5beef9c2d91af3c01401a73005fc0b288229b8d542346c9d0f8a19a50294c67060c6006c8d7d3f192f4b7d86080b94e2f089d55eef333725976399aa395f3dd25f1aa9db86e85c4fca900000000";
+
+    const PATHS: [&str; 4] = [
+        "m/44'/0'/0'/0/0",
+        "m/49'/0'/0'/0/0",
+        "m/84'/0'/0'/0/0",
+        "m/86'/0'/0'/0/0",
+    ];
+
+    fn get_key(path: &str) -> Result<PrivateKey, CryptoError> {
+        let key = match path {
+            "m/44'/0'/0'/0/0" => "e284129cc0922579a535bbf4d1a3b25773090d28c909bc0fed73b5e0222cc372",
+            "m/49'/0'/0'/0/0" => "508c73a06f6b6c817238ba61be232f5080ea4616c54f94771156934666d38ee3",
+            "m/84'/0'/0'/0/0" => "4604b4b710fe91f584fff084e1a9159fe4f8408fff380596a604948474ce4fa3",
+            "m/86'/0'/0'/0/0" => "41f41d69260df4cf277826a9b65a3717e4eeddbeedf637f212ca096576479361",
+            _ => return Err(CryptoError::InvalidDerivationpath),
+        };
+        PrivateKey::new(&hex::decode(key).unwrap())
+    }
+
+    fn sign_input(psbt: Vec<u8>, derivation_paths: &[&str]) -> SignInput {
+        SignInput {
+            psbt,
+            derivation_paths: derivation_paths
+                .iter()
+                .map(|path| path.to_string())
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_sign_psbt() {
+        let request_key = get_key(PATHS[0]).unwrap();
+        let input = sign_input(hex::decode(UNSIGNED_PSBT).unwrap(), &PATHS);
+        let output = Signer::sign(&request_key, &get_key, &input).unwrap();
+        assert_eq!(hex::encode(&output.transaction), SIGNED_TRANSACTION);
+
+        let psbt: PartiallySignedTransaction = deserialize(&output.psbt).unwrap();
+        let partial_sig = |index: usize| {
+            let partial_sigs: Vec<&Vec<u8>> = psbt.inputs[index].partial_sigs.values().collect();
+            hex::encode(partial_sigs[0])
+        };
+        assert_eq!(partial_sig(0), "3045022100f4de1b30cb9c8c64dc9491f12f00728b8f9a1c972c66926ca57591281d878433022021a32602aa7b2e0455273f23cabf30e9f936533c51fc277362cf8d2d849805e901");
+        assert_eq!(partial_sig(1), "3044022034afb3e08b5a935ca130897b21e03d16db17167c081933ed00866cd824ec379302205143040ce6239815d2ef00a44454ea045a39eedef1287d7c0e00532273576bcb01");
+        assert_eq!(partial_sig(2), "304402204705e5116a3c6dfecb250570aa26727dbd4b40f8b13ffe9b1a08fd631a8caaa2022015bc9f60732ca0f20f46ebe6011f5989b0c43fbf5a2d9c7a8236980a6b21a22f01");
+        assert_eq!(
+            hex::encode(psbt.inputs[1].redeem_script.as_ref().unwrap().as_bytes()),
+            "0014f990679acafe25c27615373b40bf22446d24ff44"
+        );
+        let tap_key_sig = psbt.inputs[3].unknown.values().next().unwrap();
+        let output_key = schnorrsig::PublicKey::from_slice(
+            &hex::decode("a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c")
+                .unwrap(),
+        )
+        .unwrap();
+        let sighash =
+            hex::decode("22825ab74caeb6019368bfdf38b04578399798edcb907c61d3362d31a73a1fa6")
+                .unwrap();
+        assert!(Secp256k1::new()
+            .schnorrsig_verify(
+                &schnorrsig::Signature::from_slice(tap_key_sig).unwrap(),
+                &Message::from_slice(&sighash).unwrap(),
+                &output_key
+            )
+            .is_ok());
+        assert_eq!(hex::encode(tap_key_sig), "1a73005fc0b288229b8d542346c9d0f8a19a50294c67060c6006c8d7d3f192f4b7d86080b94e2f089d55eef333725976399aa395f3dd25f1aa9db86e85c4fca9");
+    }
+
+    #[test]
+    fn test_sign_psbt_in_rounds() {
+        let request_key = get_key(PATHS[0]).unwrap();
+        let input = sign_input(
+            hex::decode(UNSIGNED_PSBT).unwrap(),
+            &["", "", PATHS[2], PATHS[3]],
+        );
+        let output = Signer::sign(&request_key, &get_key, &input).unwrap();
+        // The first two inputs are unsigned
+        assert!(output.transaction.is_empty());
+
+        let input = sign_input(output.psbt, &[PATHS[0], PATHS[1], "", ""]);
+        let output = Signer::sign(&request_key, &get_key, &input).unwrap();
+        assert_eq!(hex::encode(&output.transaction), SIGNED_TRANSACTION);
+    }
+
+    #[test]
+    fn test_sign_psbt_with_request_key() {
+        // Only the input spendable by the key is signed
+        let request_key = get_key(PATHS[2]).unwrap();
+        let input = sign_input(hex::decode(UNSIGNED_PSBT).unwrap(), &[]);
+        let output = Signer::sign(&request_key, &get_key, &input).unwrap();
+        assert!(output.transaction.is_empty());
+        let psbt: PartiallySignedTransaction = deserialize(&output.psbt).unwrap();
+        let signed: Vec<bool> = psbt
+            .inputs
+            .iter()
+            .map(|input| !input.partial_sigs.is_empty() || !input.unknown.is_empty())
+            .collect();
+        assert_eq!(signed, vec![false, false, true, false]);
+    }
+
+    #[test]
+    fn test_sign_psbt_invalid_input() {
+        let request_key = get_key(PATHS[0]).unwrap();
+        let psbt = hex::decode(UNSIGNED_PSBT).unwrap();
+
+        // The key of the path doesn't own the input
+        let input = sign_input(psbt.clone(), &[PATHS[1], "", "", ""]);
+        let sign_error = Signer::sign(&request_key, &get_key, &input);
+        assert_eq!(sign_error.err().unwrap(), Error::InvalidSignInput);
+
+        // A path of every input is required
+        let input = sign_input(psbt.clone(), &[PATHS[0]]);
+        let sign_error = Signer::sign(&request_key, &get_key, &input);
+        assert_eq!(sign_error.err().unwrap(), Error::InvalidSignInput);
+
+        let input = sign_input(psbt.clone(), &["m/0", "", "", ""]);
+        let sign_error = Signer::sign(&request_key, &get_key, &input);
+        assert_eq!(sign_error.err().unwrap(), Error::InvalidPrivateKey);
+
+        // No input is spendable by the request key
+        let foreign_key = PrivateKey::new(&[1; 32]).unwrap();
+        let input = sign_input(psbt.clone(), &[]);
+        let sign_error = Signer::sign(&foreign_key, &get_key, &input);
+        assert_eq!(sign_error.err().unwrap(), Error::InvalidSignInput);
+
+        let input = sign_input(psbt[..psbt.len() - 1].to_vec(), &PATHS);
+        let sign_error = Signer::sign(&request_key, &get_key, &input);
+        assert_eq!(sign_error.err().unwrap(), Error::InvalidSignInput);
+    }
+
+    #[test]
+    fn test_taproot_key_spend_sighash() {
+        let psbt: PartiallySignedTransaction =
+            deserialize(&hex::decode(UNSIGNED_PSBT).unwrap()).unwrap();
+        let prevouts: Vec<TxOut> = (0..4)
+            .map(|index| spent_output(&psbt, index).unwrap())
+            .collect();
+        let cases = [
+            (
+                3,
+                SIGHASH_DEFAULT,
+                "22825ab74caeb6019368bfdf38b04578399798edcb907c61d3362d31a73a1fa6",
+            ),
+            (
+                3,
+                0x01,
+                "e910f43659cc48b707006ed80c0e6b64f00cf5941cf037f3e1701c61e840e040",
+            ),
+            (
+                3,
+                SIGHASH_NONE,
+                "c446f28640ccaa9a655c1425b3b414dfd9c3648fa5ae521cbcea38000b96048b",
+            ),
+            (
+                1,
+                SIGHASH_SINGLE,
+                "58976f6cab293c6bf377a3eae314ef8925cfd4630e90187ca6a358b9c3eba0fd",
+            ),
+            (
+                3,
+                0x01 | SIGHASH_ANYONECANPAY,
+                "51a0560c05022de6fa7b213af78db71190a82efe65754938dfb7485d4535c80a",
+            ),
+            (
+                1,
+                SIGHASH_SINGLE | SIGHASH_ANYONECANPAY,
+                "6ce4498053ef2a0b0375e058d8734d5793e4d2446512b3258addf612fe754fe8",
+            ),
+            (
+                0,
+                SIGHASH_NONE | SIGHASH_ANYONECANPAY,
+                "f94c256a36b6ce7b014d210f22662ad5768fd6075ea0d82b64b8fb3cccb4e30b",
+            ),
+        ];
+        let tx = &psbt.global.unsigned_tx;
+        for (index, hash_type, expected) in cases.iter() {
+            let sighash = taproot_key_spend_sighash(tx, *index, &prevouts, *hash_type).unwrap();
+            assert_eq!(hex::encode(sighash), *expected);
+        }
+
+        // No output is signed by SIGHASH_SINGLE of the input
+        assert!(taproot_key_spend_sighash(tx, 3, &prevouts, SIGHASH_SINGLE).is_err());
+        assert!(taproot_key_spend_sighash(tx, 3, &prevouts, 0x04).is_err());
+        assert!(taproot_key_spend_sighash(tx, 3, &prevouts, SIGHASH_ANYONECANPAY).is_err());
+        assert!(taproot_key_spend_sighash(tx, 3, &prevouts[1..], SIGHASH_DEFAULT).is_err());
+    }
+}
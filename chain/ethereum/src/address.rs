@@ -1,8 +1,10 @@
 use super::address_checksum::{checksum, ChecksumType};
 use chain_common::public_key::PublicKey;
-use crypto::hash::Keccak256;
+use crypto::hash::{Hasher, Keccak256};
 use crypto::public_key::PublicKeyType;
 use crypto::Error;
+use ethereum_types::U256;
+use rlp::RlpStream;
 use std::string::ToString;
 
 const ADDRESS_SIZE: usize = 20;
@@ -41,6 +43,20 @@ impl EthereumAddress {
             data: hash[begin..].to_vec(),
         })
     }
+
+    /// The address of the contract created by the transaction of `sender` with `nonce`, which is
+    /// the last 20 bytes of `keccak256(rlp([sender, nonce]))`
+    pub fn new_contract(sender: &EthereumAddress, nonce: &U256) -> Result<Self, Error> {
+        let mut rlp_stream = RlpStream::new_list(2);
+        rlp_stream.append(&sender.data);
+        rlp_stream.append(nonce);
+        let hash = Hasher::hash(Keccak256, &rlp_stream.out())?;
+        let begin = hash.len() - ADDRESS_SIZE;
+        Ok(EthereumAddress {
+            coin_id: sender.coin_id.to_owned(),
+            data: hash[begin..].to_vec(),
+        })
+    }
 }
 
 impl ToString for EthereumAddress {
@@ -59,6 +75,7 @@ mod tests {
     use crate::address::EthereumAddress;
     use chain_common::public_key::PublicKey;
     use crypto::public_key::PublicKeyType;
+    use ethereum_types::U256;
 
     #[test]
     fn test_validate_address() {
@@ -89,4 +106,22 @@ mod tests {
         let address_str = address.unwrap().to_string();
         assert_eq!(address_str, "0xAc1ec44E4f0ca7D172B7803f6836De87Fb72b309");
     }
+
+    #[test]
+    fn test_contract_address() {
+        let sender = EthereumAddress {
+            coin_id: "ethereum".to_owned(),
+            data: hex::decode("6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0").unwrap(),
+        };
+        let contract = EthereumAddress::new_contract(&sender, &U256::zero()).unwrap();
+        assert_eq!(
+            contract.to_string(),
+            "0xcd234A471b72ba2F1Ccf0A70FCABA648a5eeCD8d"
+        );
+        let contract = EthereumAddress::new_contract(&sender, &U256::one()).unwrap();
+        assert_eq!(
+            contract.to_string(),
+            "0x343c43A37D37dfF08AE8C4A11544c718AbB4fCF8"
+        );
+    }
 }
@@ -0,0 +1,419 @@
+use super::address::EthereumAddress;
+use chain_common::ethereum::{AccessListItem, DecodeOutput, TransactionType};
+use chain_common::public_key::PublicKey;
+use crypto::hash::{Hasher, Keccak256};
+use crypto::public_key::PublicKeyType;
+use crypto::Error;
+use ethereum_types::U256;
+use rlp::{Rlp, RlpStream};
+use secp256k1::recovery::{RecoverableSignature, RecoveryId};
+use secp256k1::{Message, Secp256k1};
+
+const EIP2930_TYPE: u8 = 0x01;
+const EIP1559_TYPE: u8 = 0x02;
+// RLP lists start from 0xc0, other first bytes are EIP-2718 transaction types
+const RLP_LIST_OFFSET: u8 = 0xc0;
+// Fields of unsigned transactions, signed ones have `v`, `r` and `s` as well
+const LEGACY_FIELDS: usize = 6;
+const EIP2930_FIELDS: usize = 8;
+const EIP1559_FIELDS: usize = 9;
+const SIGNATURE_FIELDS: usize = 3;
+const ADDRESS_SIZE: usize = 20;
+const WORD_SIZE: usize = 32;
+// `v` of legacy transactions is the recovery id plus 27, or plus `chain_id * 2 + 35` (EIP-155)
+const V_OFFSET: u64 = 27;
+const EIP155_V_OFFSET: u64 = 35;
+
+pub struct Decoder;
+
+impl Decoder {
+    /// Decode a legacy, EIP-2930 or EIP1559 transaction, which may be unsigned. Unsigned legacy
+    /// transactions are either the 6 fields or the EIP-155 signing data ending with
+    /// `chain_id, 0, 0`. The signature is valid if the sender can be recovered and the chain id
+    /// of the transaction is `chain_id`
+    pub fn decode(data: &[u8], chain_id: u64) -> Result<DecodeOutput, Error> {
+        let (tx_type, rlp_data) = match data.first() {
+            Some(&EIP2930_TYPE) => (TransactionType::AccessList, &data[1..]),
+            Some(&EIP1559_TYPE) => (TransactionType::Eip1559, &data[1..]),
+            Some(&byte) if byte >= RLP_LIST_OFFSET => (TransactionType::Legacy, data),
+            _ => return Err(Error::InvalidEncodedTransaction),
+        };
+        let rlp = Rlp::new(rlp_data);
+        let payload_info = rlp
+            .payload_info()
+            .map_err(|_| Error::InvalidEncodedTransaction)?;
+        if payload_info.header_len + payload_info.value_len != rlp_data.len() {
+            return Err(Error::InvalidEncodedTransaction);
+        }
+        let fields = match tx_type {
+            TransactionType::AccessList => EIP2930_FIELDS,
+            TransactionType::Eip1559 => EIP1559_FIELDS,
+            _ => LEGACY_FIELDS,
+        };
+        let item_count = rlp
+            .item_count()
+            .map_err(|_| Error::InvalidEncodedTransaction)?;
+        if item_count != fields && item_count != fields + SIGNATURE_FIELDS {
+            return Err(Error::InvalidEncodedTransaction);
+        }
+
+        let mut output = DecodeOutput {
+            tx_type: tx_type as i32,
+            hash: Hasher::hash(Keccak256, data)?,
+            ..Default::default()
+        };
+        // Typed transactions start with the chain id, and EIP1559 ones have two fees
+        let mut index = 0;
+        if tx_type != TransactionType::Legacy {
+            output.chain_id = rlp
+                .val_at(0)
+                .map_err(|_| Error::InvalidEncodedTransaction)?;
+            index += 1;
+        }
+        output.nonce = decode_number(&rlp, index)?;
+        index += 1;
+        if tx_type == TransactionType::Eip1559 {
+            output.max_inclusion_fee_per_gas = decode_number(&rlp, index)?;
+            output.max_fee_per_gas = decode_number(&rlp, index + 1)?;
+            index += 2;
+        } else {
+            output.gas_price = decode_number(&rlp, index)?;
+            index += 1;
+        }
+        output.gas_limit = decode_number(&rlp, index)?;
+        let receiver = decode_bytes(&rlp, index + 1)?;
+        output.to_address = match receiver.len() {
+            0 => "".to_owned(),
+            ADDRESS_SIZE => checksum_address(receiver),
+            _ => return Err(Error::InvalidEncodedTransaction),
+        };
+        output.amount = decode_number(&rlp, index + 2)?;
+        output.payload = decode_bytes(&rlp, index + 3)?.to_vec();
+        index += 4;
+        if tx_type != TransactionType::Legacy {
+            let access_list = rlp
+                .at(index)
+                .map_err(|_| Error::InvalidEncodedTransaction)?;
+            output.access_list = decode_access_list(&access_list)?;
+        }
+        if item_count == fields {
+            return Ok(output);
+        }
+
+        let v: u64 = rlp
+            .val_at(fields)
+            .map_err(|_| Error::InvalidEncodedTransaction)?;
+        let r = decode_bytes(&rlp, fields + 1)?;
+        let s = decode_bytes(&rlp, fields + 2)?;
+        if r.len() > WORD_SIZE || s.len() > WORD_SIZE {
+            return Err(Error::InvalidEncodedTransaction);
+        }
+        // The EIP-155 signing data of a legacy transaction has `chain_id, 0, 0` as the signature
+        if tx_type == TransactionType::Legacy && r.is_empty() && s.is_empty() {
+            output.chain_id = v;
+            return Ok(output);
+        }
+        let recovery_id = match tx_type {
+            TransactionType::Legacy if v >= EIP155_V_OFFSET => {
+                output.chain_id = (v - EIP155_V_OFFSET) / 2;
+                Some((v - EIP155_V_OFFSET) % 2)
+            }
+            TransactionType::Legacy if v == V_OFFSET || v == V_OFFSET + 1 => Some(v - V_OFFSET),
+            TransactionType::Legacy => None,
+            _ if v <= 1 => Some(v),
+            _ => None,
+        };
+        output.signed = true;
+        output.v = v as u32;
+        output.r = r.to_vec();
+        output.s = s.to_vec();
+
+        let hash = signing_hash(&rlp, data, tx_type, fields, output.chain_id)?;
+        if let Some(signer) = recovery_id.and_then(|id| recover_signer(&hash, id, r, s)) {
+            output.from_address = signer;
+            // Legacy transactions without EIP-155 replay protection are valid on every chain
+            output.valid_signature = output.chain_id == chain_id
+                || (tx_type == TransactionType::Legacy && output.chain_id == 0);
+        }
+        Ok(output)
+    }
+}
+
+fn decode_number(rlp: &Rlp, index: usize) -> Result<String, Error> {
+    let value: U256 = rlp
+        .val_at(index)
+        .map_err(|_| Error::InvalidEncodedTransaction)?;
+    Ok(format!("0x{:x}", value))
+}
+
+fn decode_bytes<'a>(rlp: &Rlp<'a>, index: usize) -> Result<&'a [u8], Error> {
+    let item = rlp
+        .at(index)
+        .map_err(|_| Error::InvalidEncodedTransaction)?;
+    if item.is_list() {
+        return Err(Error::InvalidEncodedTransaction);
+    }
+    item.data().map_err(|_| Error::InvalidEncodedTransaction)
+}
+
+fn decode_access_list(rlp: &Rlp) -> Result<Vec<AccessListItem>, Error> {
+    if !rlp.is_list() {
+        return Err(Error::InvalidEncodedTransaction);
+    }
+    let mut access_list = vec![];
+    for item in rlp.iter() {
+        let address = decode_bytes(&item, 0)?;
+        let storage_keys = item.at(1).map_err(|_| Error::InvalidEncodedTransaction)?;
+        if address.len() != ADDRESS_SIZE || item.item_count() != Ok(2) || !storage_keys.is_list() {
+            return Err(Error::InvalidEncodedTransaction);
+        }
+        let mut keys = vec![];
+        for key in storage_keys.iter() {
+            let key = key.data().map_err(|_| Error::InvalidEncodedTransaction)?;
+            if key.len() != WORD_SIZE {
+                return Err(Error::InvalidEncodedTransaction);
+            }
+            keys.push(format!("0x{}", hex::encode(key)));
+        }
+        access_list.push(AccessListItem {
+            address: checksum_address(address),
+            storage_keys: keys,
+        });
+    }
+    Ok(access_list)
+}
+
+// The hash signed by the sender, which is the hash of the transaction without the signature,
+// with `chain_id, 0, 0` in place of the signature for EIP-155 legacy transactions
+fn signing_hash(
+    rlp: &Rlp,
+    data: &[u8],
+    tx_type: TransactionType,
+    fields: usize,
+    chain_id: u64,
+) -> Result<Vec<u8>, Error> {
+    let replay_protected = tx_type == TransactionType::Legacy && chain_id != 0;
+    let mut rlp_stream = match replay_protected {
+        true => RlpStream::new_list(fields + SIGNATURE_FIELDS),
+        false => RlpStream::new_list(fields),
+    };
+    for index in 0..fields {
+        let item = rlp
+            .at(index)
+            .map_err(|_| Error::InvalidEncodedTransaction)?;
+        rlp_stream.append_raw(item.as_raw(), 1);
+    }
+    if replay_protected {
+        rlp_stream.append(&chain_id);
+        rlp_stream.append(&U256::zero());
+        rlp_stream.append(&U256::zero());
+    }
+    let mut encoded = vec![];
+    if tx_type != TransactionType::Legacy {
+        encoded.push(data[0]);
+    }
+    encoded.extend_from_slice(&rlp_stream.out());
+    Hasher::hash(Keccak256, &encoded)
+}
+
+// The checksummed address of the signer, `None` if the signature is invalid or has a high `s`
+// which is rejected since Homestead (EIP-2)
+fn recover_signer(hash: &[u8], recovery_id: u64, r: &[u8], s: &[u8]) -> Option<String> {
+    let mut compact = [0u8; 2 * WORD_SIZE];
+    compact[WORD_SIZE - r.len()..WORD_SIZE].copy_from_slice(r);
+    compact[2 * WORD_SIZE - s.len()..].copy_from_slice(s);
+    let recovery_id = RecoveryId::from_i32(recovery_id as i32).ok()?;
+    let signature = RecoverableSignature::from_compact(&compact, recovery_id).ok()?;
+    let mut normalized = signature.to_standard();
+    normalized.normalize_s();
+    if normalized != signature.to_standard() {
+        return None;
+    }
+    let message = Message::from_slice(hash).ok()?;
+    let public_key = Secp256k1::verification_only()
+        .recover(&message, &signature)
+        .ok()?;
+    let public_key = PublicKey::new(
+        PublicKeyType::Secp256k1Extended,
+        &public_key.serialize_uncompressed(),
+    )
+    .ok()?;
+    Some(
+        EthereumAddress::new(&public_key, "ethereum")
+            .ok()?
+            .to_string(),
+    )
+}
+
+fn checksum_address(data: &[u8]) -> String {
+    EthereumAddress {
+        coin_id: "ethereum".to_owned(),
+        data: data.to_vec(),
+    }
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::Signer;
+    use chain_common::ethereum::SignInput;
+    use chain_common::private_key::PrivateKey;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_decode_legacy() {
+        // The example of EIP-155
+        let data = hex::decode("f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83").unwrap();
+        let output = Decoder::decode(&data, 1).unwrap();
+        assert_eq!(output.tx_type, TransactionType::Legacy as i32);
+        assert_eq!(output.chain_id, 1);
+        assert_eq!(output.nonce, "0x9");
+        assert_eq!(output.gas_price, "0x4a817c800");
+        assert_eq!(output.gas_limit, "0x5208");
+        assert_eq!(output.amount, "0xde0b6b3a7640000");
+        assert_eq!(
+            output.to_address,
+            "0x3535353535353535353535353535353535353535"
+        );
+        assert!(output.payload.is_empty());
+        assert!(output.signed);
+        assert_eq!(output.v, 37);
+        assert_eq!(
+            hex::encode(&output.hash),
+            "33469b22e9f636356c4160a87eb19df52b7412e8eac32a4a55ffe88ea8350788"
+        );
+        assert_eq!(
+            output.from_address,
+            "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"
+        );
+        assert!(output.valid_signature);
+        assert!(!Decoder::decode(&data, 3).unwrap().valid_signature);
+
+        // The EIP-155 signing data of the transaction
+        let unsigned = hex::decode("ec098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a764000080018080").unwrap();
+        let output = Decoder::decode(&unsigned, 1).unwrap();
+        assert!(!output.signed);
+        assert_eq!(output.chain_id, 1);
+        assert_eq!(
+            hex::encode(&output.hash),
+            "daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53"
+        );
+        assert_eq!(output.from_address, "");
+
+        // Signed without replay protection, which is valid on every chain
+        let pre_eip155 = hex::decode("f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a7640000801ba08383adc8b8ae116f918fb44ca7ff9dfd8012596a5c130c6246a2cc717ba41cdaa053ddfacf5bd4aa7e46d1575acf52636ea659b91f29e2fb91c75567a279738f38").unwrap();
+        let output = Decoder::decode(&pre_eip155, 3).unwrap();
+        assert_eq!(output.chain_id, 0);
+        assert_eq!(output.v, 27);
+        assert_eq!(
+            output.from_address,
+            "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"
+        );
+        assert!(output.valid_signature);
+    }
+
+    #[test]
+    fn test_decode_eip1559() {
+        let data = hex::decode("02f8710306847735940084b2d05e0082526c94b9f5771c27664bf2282d98e09d7f50cec7cb01a78701ee0c29f50cb180c080a092c336138f7d0231fe9422bb30ee9ef10bf222761fe9e04442e3a11e88880c64a06487026011dae03dc281bc21c7d7ede5c2226d197befb813a4ecad686b559e58").unwrap();
+        let output = Decoder::decode(&data, 3).unwrap();
+        assert_eq!(output.tx_type, TransactionType::Eip1559 as i32);
+        assert_eq!(output.chain_id, 3);
+        assert_eq!(output.nonce, "0x6");
+        assert_eq!(output.max_inclusion_fee_per_gas, "0x77359400");
+        assert_eq!(output.max_fee_per_gas, "0xb2d05e00");
+        assert_eq!(output.gas_limit, "0x526c");
+        assert_eq!(output.amount, "0x1ee0c29f50cb1");
+        assert_eq!(
+            hex::encode(&output.hash),
+            "14429509307efebfdaa05227d84c147450d168c68539351fbc01ed87c916ab2e"
+        );
+        assert_eq!(
+            output.from_address,
+            "0xB9F5771C27664bF2282D98E09D7F50cEc7cB01a7"
+        );
+        assert!(output.valid_signature);
+        assert!(!Decoder::decode(&data, 1).unwrap().valid_signature);
+
+        let unsigned = hex::decode("02ee0306847735940084b2d05e0082526c94b9f5771c27664bf2282d98e09d7f50cec7cb01a78701ee0c29f50cb180c0").unwrap();
+        let output = Decoder::decode(&unsigned, 3).unwrap();
+        assert!(!output.signed);
+        assert!(!output.valid_signature);
+        assert_eq!(
+            hex::encode(&output.hash),
+            "6468eb103d51c9a683b51818fdb73390151c9973831d2cfb4e9587ad54273155"
+        );
+
+        let mut trailing = data.clone();
+        trailing.push(0);
+        assert_eq!(
+            Decoder::decode(&trailing, 3).err(),
+            Some(Error::InvalidEncodedTransaction)
+        );
+        let mut unknown_type = data;
+        unknown_type[0] = 0x03;
+        assert_eq!(
+            Decoder::decode(&unknown_type, 3).err(),
+            Some(Error::InvalidEncodedTransaction)
+        );
+    }
+
+    #[test]
+    fn test_decode_signed_transaction() {
+        let input = SignInput {
+            chain_id: 1,
+            nonce: "0x3".to_owned(),
+            gas_limit: "0x186a0".to_owned(),
+            gas_price: "0x3b9aca00".to_owned(),
+            amount: "0x0".to_owned(),
+            payload: hex::decode("a9059cbb").unwrap(),
+            to_address: "0xdac17f958d2ee523a2206206994597c13d831ec7".to_owned(),
+            access_list: vec![AccessListItem {
+                address: "0xdac17f958d2ee523a2206206994597c13d831ec7".to_owned(),
+                storage_keys: vec![
+                    "0x0000000000000000000000000000000000000000000000000000000000000001".to_owned(),
+                ],
+            }],
+            ..Default::default()
+        };
+        let private_key = PrivateKey::from_str(
+            "4646464646464646464646464646464646464646464646464646464646464646",
+        )
+        .unwrap();
+        let signed = Signer::sign(&private_key, &input).unwrap();
+        let output = Decoder::decode(&signed.encoded, 1).unwrap();
+        assert_eq!(output.tx_type, TransactionType::AccessList as i32);
+        assert_eq!(output.gas_price, input.gas_price);
+        assert_eq!(output.payload, input.payload);
+        assert_eq!(
+            output.to_address,
+            "0xdAC17F958D2ee523a2206206994597C13D831ec7"
+        );
+        assert_eq!(output.access_list.len(), 1);
+        assert_eq!(
+            output.access_list[0].storage_keys,
+            input.access_list[0].storage_keys
+        );
+        assert_eq!(
+            (output.v, &output.r, &output.s),
+            (signed.v, &signed.r, &signed.s)
+        );
+        assert_eq!(
+            output.from_address,
+            "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"
+        );
+        assert!(output.valid_signature);
+
+        // Contract creation transactions have no recipient
+        let contract_creation = SignInput {
+            to_address: "".to_owned(),
+            access_list: vec![],
+            ..input
+        };
+        let signed = Signer::sign(&private_key, &contract_creation).unwrap();
+        let output = Decoder::decode(&signed.encoded, 1).unwrap();
+        assert_eq!(output.tx_type, TransactionType::Legacy as i32);
+        assert_eq!(output.to_address, "");
+        assert!(output.valid_signature);
+    }
+}
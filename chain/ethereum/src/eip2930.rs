@@ -1,5 +1,9 @@
+use chain_common::ethereum::AccessListItem as ProtoAccessListItem;
+use chain_common::Error;
 use ethereum_types::{Address, H256};
 use rlp_derive::{RlpEncodable, RlpEncodableWrapper};
+use std::convert::TryFrom;
+use std::str::FromStr;
 
 #[derive(RlpEncodableWrapper)]
 pub struct AccessList(pub Vec<AccessListItem>);
@@ -10,6 +14,33 @@ impl From<Vec<AccessListItem>> for AccessList {
     }
 }
 
+impl TryFrom<&[ProtoAccessListItem]> for AccessList {
+    type Error = Error;
+    fn try_from(items: &[ProtoAccessListItem]) -> Result<Self, Self::Error> {
+        let items: Result<Vec<AccessListItem>, Error> = items
+            .iter()
+            .map(|item| {
+                let storage_keys: Result<Vec<H256>, Error> = item
+                    .storage_keys
+                    .iter()
+                    .map(|key| H256::from_str(trim_hex_prefix(key)))
+                    .map(|key| key.map_err(|_| Error::InvalidSignInput))
+                    .collect();
+                Ok(AccessListItem {
+                    address: Address::from_str(trim_hex_prefix(&item.address))
+                        .map_err(|_| Error::InvalidSignInput)?,
+                    storage_keys: storage_keys?,
+                })
+            })
+            .collect();
+        Ok(AccessList(items?))
+    }
+}
+
+fn trim_hex_prefix(hex: &str) -> &str {
+    hex.strip_prefix("0x").unwrap_or(hex)
+}
+
 /// Access list item
 #[derive(RlpEncodable)]
 pub struct AccessListItem {
@@ -0,0 +1,480 @@
+use crypto::hash::{Hasher, Keccak256};
+use crypto::Error;
+use ethereum_types::U256;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::str::FromStr;
+
+const DOMAIN_TYPE: &str = "EIP712Domain";
+const WORD_SIZE: usize = 32;
+
+/// The JSON typed data of `eth_signTypedData_v4`
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypedData {
+    pub types: BTreeMap<String, Vec<TypedDataField>>,
+    pub primary_type: String,
+    pub domain: Value,
+    pub message: Value,
+}
+
+#[derive(Deserialize)]
+pub struct TypedDataField {
+    pub name: String,
+    pub r#type: String,
+}
+
+impl FromStr for TypedData {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s).map_err(|_| Error::InvalidTypedData)
+    }
+}
+
+impl TypedData {
+    /// The hash signed by `eth_signTypedData_v4`, `keccak256("\x19\x01" ‖ domainSeparator ‖
+    /// hashStruct(message))`
+    pub fn hash(&self) -> Result<Vec<u8>, Error> {
+        let mut data = vec![0x19, 0x01];
+        data.extend(self.hash_struct(DOMAIN_TYPE, &self.domain)?);
+        // A message of the domain type itself has only the domain separator
+        if self.primary_type != DOMAIN_TYPE {
+            data.extend(self.hash_struct(&self.primary_type, &self.message)?);
+        }
+        Hasher::hash(Keccak256, &data)
+    }
+
+    pub fn domain_separator(&self) -> Result<Vec<u8>, Error> {
+        self.hash_struct(DOMAIN_TYPE, &self.domain)
+    }
+
+    /// `keccak256(typeHash ‖ encodeData(data))` of the struct `data` of `type_name`
+    pub fn hash_struct(&self, type_name: &str, data: &Value) -> Result<Vec<u8>, Error> {
+        Hasher::hash(Keccak256, &self.encode_data(type_name, data)?)
+    }
+
+    pub fn type_hash(&self, type_name: &str) -> Result<Vec<u8>, Error> {
+        Hasher::hash(Keccak256, self.encode_type(type_name)?.as_bytes())
+    }
+
+    /// The type of `type_name` followed by the types it references in alphabetical order, e.g.
+    /// `Mail(Person from,Person to,string contents)Person(string name,address wallet)`
+    pub fn encode_type(&self, type_name: &str) -> Result<String, Error> {
+        let mut dependencies = BTreeSet::new();
+        self.find_dependencies(type_name, &mut dependencies)?;
+        dependencies.remove(type_name);
+        let mut encoded = String::new();
+        for name in std::iter::once(type_name).chain(dependencies) {
+            let fields: Vec<String> = self.types[name]
+                .iter()
+                .map(|field| format!("{} {}", field.r#type, field.name))
+                .collect();
+            encoded.push_str(&format!("{}({})", name, fields.join(",")));
+        }
+        Ok(encoded)
+    }
+
+    fn find_dependencies<'a>(
+        &'a self,
+        type_name: &'a str,
+        dependencies: &mut BTreeSet<&'a str>,
+    ) -> Result<(), Error> {
+        let fields = self.types.get(type_name).ok_or(Error::InvalidTypedData)?;
+        if !dependencies.insert(type_name) {
+            return Ok(());
+        }
+        for field in fields {
+            let base_type = field.r#type.split('[').next().unwrap_or_default();
+            if self.types.contains_key(base_type) {
+                self.find_dependencies(base_type, dependencies)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn encode_data(&self, type_name: &str, data: &Value) -> Result<Vec<u8>, Error> {
+        let fields = self.types.get(type_name).ok_or(Error::InvalidTypedData)?;
+        let data = data.as_object().ok_or(Error::InvalidTypedData)?;
+        let mut encoded = self.type_hash(type_name)?;
+        for field in fields {
+            let value = data.get(&field.name).unwrap_or(&Value::Null);
+            encoded.extend(self.encode_value(&field.r#type, value)?);
+        }
+        Ok(encoded)
+    }
+
+    // The 32-byte encoding of `value` of `type_name`. Structs and arrays are hashed, missing
+    // structs are encoded as zeros like other implementations of `eth_signTypedData_v4`
+    fn encode_value(&self, type_name: &str, value: &Value) -> Result<Vec<u8>, Error> {
+        if let Some(item_type) = array_item_type(type_name) {
+            let items = value.as_array().ok_or(Error::InvalidTypedData)?;
+            let length = &type_name[item_type.len() + 1..type_name.len() - 1];
+            if !length.is_empty() && length.parse::<usize>().ok() != Some(items.len()) {
+                return Err(Error::InvalidTypedData);
+            }
+            let mut encoded = vec![];
+            for item in items {
+                encoded.extend(self.encode_value(item_type, item)?);
+            }
+            return Hasher::hash(Keccak256, &encoded);
+        }
+        if self.types.contains_key(type_name) {
+            if value.is_null() {
+                return Ok(vec![0u8; WORD_SIZE]);
+            }
+            return self.hash_struct(type_name, value);
+        }
+        encode_atomic_value(type_name, value)
+    }
+}
+
+// The item type of an array type, e.g. `Person` of `Person[]` and `uint8[2]` of `uint8[2][3]`
+fn array_item_type(type_name: &str) -> Option<&str> {
+    if !type_name.ends_with(']') {
+        return None;
+    }
+    type_name.rfind('[').map(|index| &type_name[..index])
+}
+
+fn encode_atomic_value(type_name: &str, value: &Value) -> Result<Vec<u8>, Error> {
+    match type_name {
+        "string" => {
+            let value = value.as_str().ok_or(Error::InvalidTypedData)?;
+            Hasher::hash(Keccak256, value.as_bytes())
+        }
+        "bytes" => Hasher::hash(Keccak256, &decode_hex(value)?),
+        "bool" => {
+            let value = value.as_bool().ok_or(Error::InvalidTypedData)?;
+            Ok(encode_word(U256::from(value as u8)))
+        }
+        "address" => {
+            let address = decode_hex(value)?;
+            if address.len() != 20 {
+                return Err(Error::InvalidTypedData);
+            }
+            Ok(left_pad(&address))
+        }
+        _ if type_name.starts_with("bytes") => {
+            let size = parse_size(&type_name[5..], 1, WORD_SIZE)?;
+            let mut bytes = decode_hex(value)?;
+            if bytes.len() != size {
+                return Err(Error::InvalidTypedData);
+            }
+            bytes.resize(WORD_SIZE, 0);
+            Ok(bytes)
+        }
+        _ if type_name.starts_with("uint") => {
+            let bits = parse_integer_bits(&type_name[4..])?;
+            let (negative, value) = parse_integer(value)?;
+            if negative || value.bits() > bits {
+                return Err(Error::InvalidTypedData);
+            }
+            Ok(encode_word(value))
+        }
+        _ if type_name.starts_with("int") => {
+            let bits = parse_integer_bits(&type_name[3..])?;
+            let (negative, value) = parse_integer(value)?;
+            // The range of a signed integer is [-2^(bits-1), 2^(bits-1))
+            let limit = U256::one() << (bits - 1);
+            if (negative && value > limit) || (!negative && value >= limit) {
+                return Err(Error::InvalidTypedData);
+            }
+            match negative {
+                true => Ok(encode_word(value.overflowing_neg().0)),
+                false => Ok(encode_word(value)),
+            }
+        }
+        _ => Err(Error::InvalidTypedData),
+    }
+}
+
+fn parse_size(size: &str, min: usize, max: usize) -> Result<usize, Error> {
+    match size.parse::<usize>() {
+        Ok(size) if size >= min && size <= max => Ok(size),
+        _ => Err(Error::InvalidTypedData),
+    }
+}
+
+// Bits of an integer type of the size suffix, 256 without any suffix
+fn parse_integer_bits(size: &str) -> Result<usize, Error> {
+    if size.is_empty() {
+        return Ok(256);
+    }
+    let bits = parse_size(size, 8, 256)?;
+    if bits % 8 != 0 {
+        return Err(Error::InvalidTypedData);
+    }
+    Ok(bits)
+}
+
+// The sign and magnitude of a JSON number or a decimal or `0x` prefixed hex string
+fn parse_integer(value: &Value) -> Result<(bool, U256), Error> {
+    let text = match value {
+        Value::Number(number) => number.to_string(),
+        Value::String(text) => text.to_owned(),
+        _ => return Err(Error::InvalidTypedData),
+    };
+    let (negative, magnitude) = match text.strip_prefix('-') {
+        Some(magnitude) => (true, magnitude),
+        None => (false, text.as_str()),
+    };
+    let value = match magnitude.strip_prefix("0x") {
+        Some(hex) => U256::from_str_radix(hex, 16).map_err(|_| Error::InvalidTypedData)?,
+        None => U256::from_dec_str(magnitude).map_err(|_| Error::InvalidTypedData)?,
+    };
+    Ok((negative && !value.is_zero(), value))
+}
+
+fn decode_hex(value: &Value) -> Result<Vec<u8>, Error> {
+    let text = value.as_str().ok_or(Error::InvalidTypedData)?;
+    let hex = text.strip_prefix("0x").unwrap_or(text);
+    hex::decode(hex).map_err(|_| Error::InvalidTypedData)
+}
+
+fn encode_word(value: U256) -> Vec<u8> {
+    let mut word = vec![0u8; WORD_SIZE];
+    value.to_big_endian(&mut word);
+    word
+}
+
+fn left_pad(data: &[u8]) -> Vec<u8> {
+    let mut word = vec![0u8; WORD_SIZE - data.len()];
+    word.extend_from_slice(data);
+    word
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::sign_message;
+    use chain_common::private_key::PrivateKey;
+
+    // The example of EIP-712
+    const MAIL: &str = r#"{
+        "types": {
+            "EIP712Domain": [
+                {
+                    "name": "name",
+                    "type": "string"
+                },
+                {
+                    "name": "version",
+                    "type": "string"
+                },
+                {
+                    "name": "chainId",
+                    "type": "uint256"
+                },
+                {
+                    "name": "verifyingContract",
+                    "type": "address"
+                }
+            ],
+            "Person": [
+                {
+                    "name": "name",
+                    "type": "string"
+                },
+                {
+                    "name": "wallet",
+                    "type": "address"
+                }
+            ],
+            "Mail": [
+                {
+                    "name": "from",
+                    "type": "Person"
+                },
+                {
+                    "name": "to",
+                    "type": "Person"
+                },
+                {
+                    "name": "contents",
+                    "type": "string"
+                }
+            ]
+        },
+        "primaryType": "Mail",
+        "domain": {
+            "name": "Ether Mail",
+            "version": "1",
+            "chainId": 1,
+            "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+        },
+        "message": {
+            "from": {
+                "name": "Cow",
+                "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"
+            },
+            "to": {
+                "name": "Bob",
+                "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"
+            },
+            "contents": "Hello, Bob!"
+        }
+    }"#;
+
+    // The example of `eth_signTypedData_v4` with arrays of structs and addresses
+    const MAIL_ARRAYS: &str = r#"{
+        "types": {
+            "EIP712Domain": [
+                {
+                    "name": "name",
+                    "type": "string"
+                },
+                {
+                    "name": "version",
+                    "type": "string"
+                },
+                {
+                    "name": "chainId",
+                    "type": "uint256"
+                },
+                {
+                    "name": "verifyingContract",
+                    "type": "address"
+                }
+            ],
+            "Group": [
+                {
+                    "name": "name",
+                    "type": "string"
+                },
+                {
+                    "name": "members",
+                    "type": "Person[]"
+                }
+            ],
+            "Mail": [
+                {
+                    "name": "from",
+                    "type": "Person"
+                },
+                {
+                    "name": "to",
+                    "type": "Person[]"
+                },
+                {
+                    "name": "contents",
+                    "type": "string"
+                }
+            ],
+            "Person": [
+                {
+                    "name": "name",
+                    "type": "string"
+                },
+                {
+                    "name": "wallets",
+                    "type": "address[]"
+                }
+            ]
+        },
+        "primaryType": "Mail",
+        "domain": {
+            "name": "Ether Mail",
+            "version": "1",
+            "chainId": 1,
+            "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+        },
+        "message": {
+            "from": {
+                "name": "Cow",
+                "wallets": [
+                    "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826",
+                    "0xDeaDbeefdEAdbeefdEadbEEFdeadbeEFdEaDbeeF"
+                ]
+            },
+            "to": [
+                {
+                    "name": "Bob",
+                    "wallets": [
+                        "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB",
+                        "0xB0BdaBea57B0BDABeA57b0bdABEA57b0BDabEa57",
+                        "0xB0B0b0b0b0b0B000000000000000000000000000"
+                    ]
+                }
+            ],
+            "contents": "Hello, Bob!"
+        }
+    }"#;
+
+    #[test]
+    fn test_hash_typed_data() {
+        let typed_data = TypedData::from_str(MAIL).unwrap();
+        assert_eq!(
+            typed_data.encode_type("Mail").unwrap(),
+            "Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+        );
+        assert_eq!(
+            hex::encode(typed_data.type_hash("Mail").unwrap()),
+            "a0cedeb2dc280ba39b857546d74f5549c3a1d7bdc2dd96bf881f76108e23dac2"
+        );
+        assert_eq!(
+            hex::encode(typed_data.hash_struct("Mail", &typed_data.message).unwrap()),
+            "c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"
+        );
+        assert_eq!(
+            hex::encode(typed_data.domain_separator().unwrap()),
+            "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+        );
+        assert_eq!(
+            hex::encode(typed_data.hash().unwrap()),
+            "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+        );
+
+        let typed_data = TypedData::from_str(MAIL_ARRAYS).unwrap();
+        assert_eq!(
+            typed_data.encode_type("Mail").unwrap(),
+            "Mail(Person from,Person[] to,string contents)Person(string name,address[] wallets)"
+        );
+        assert_eq!(
+            hex::encode(typed_data.hash().unwrap()),
+            "a85c2e2b118698e88db68a8105b794a8cc7cec074e89ef991cb4f5f533819cc2"
+        );
+    }
+
+    #[test]
+    fn test_sign_typed_data() {
+        let private_key = PrivateKey::new(&Hasher::hash(Keccak256, b"cow").unwrap()).unwrap();
+        let hash = TypedData::from_str(MAIL).unwrap().hash().unwrap();
+        assert_eq!(
+            hex::encode(sign_message(&private_key, &hash, true).unwrap()),
+            "4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b915621c"
+        );
+    }
+
+    #[test]
+    fn test_encode_atomic_value() {
+        assert_eq!(
+            hex::encode(encode_atomic_value("int8", &Value::from(-1)).unwrap()),
+            "ff".repeat(32)
+        );
+        assert_eq!(
+            hex::encode(encode_atomic_value("uint16", &Value::from("0x100")).unwrap()),
+            format!("{}0100", "00".repeat(30))
+        );
+        assert_eq!(
+            hex::encode(encode_atomic_value("bytes2", &Value::from("0x1234")).unwrap()),
+            format!("1234{}", "00".repeat(30))
+        );
+        assert_eq!(
+            encode_atomic_value("uint8", &Value::from(256)).err(),
+            Some(Error::InvalidTypedData)
+        );
+        assert_eq!(
+            encode_atomic_value("int8", &Value::from(-129)).err(),
+            Some(Error::InvalidTypedData)
+        );
+        assert_eq!(
+            encode_atomic_value("address", &Value::from("0x1234")).err(),
+            Some(Error::InvalidTypedData)
+        );
+        assert_eq!(
+            TypedData::from_str("{}").err(),
+            Some(Error::InvalidTypedData)
+        );
+    }
+}
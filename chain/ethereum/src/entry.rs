@@ -1,4 +1,7 @@
 use super::address::EthereumAddress;
+use super::decoder::Decoder;
+use super::eip712::TypedData;
+use super::message;
 use super::signer::Signer;
 use bytes::BytesMut;
 use chain_common::coin::Coin;
@@ -8,6 +11,7 @@ use chain_common::private_key::PrivateKey;
 use chain_common::public_key::PublicKey;
 use crypto::Error;
 use prost::Message;
+use std::str::FromStr;
 
 pub struct EthereumEntry;
 
@@ -17,6 +21,7 @@ impl Entry for EthereumEntry {
             ChainImportType::PrivateKey,
             ChainImportType::Mnemonic,
             ChainImportType::KeyStoreJson,
+            ChainImportType::WatchOnly,
         ]
     }
 
@@ -58,4 +63,48 @@ impl Entry for EthereumEntry {
             .expect("Fail to encode the SignOutput");
         Ok(buf.to_vec())
     }
+
+    fn sign_message(
+        &self,
+        _coin: &Coin,
+        private_key: &PrivateKey,
+        message: &[u8],
+        raw_hash: bool,
+    ) -> Result<Vec<u8>, Error> {
+        message::sign_message(private_key, message, raw_hash)
+    }
+
+    fn recover_message_signer(
+        &self,
+        coin: &Coin,
+        message: &[u8],
+        signature: &[u8],
+        raw_hash: bool,
+    ) -> Result<String, Error> {
+        message::recover_signer(message, signature, raw_hash, &coin.id)
+    }
+
+    fn sign_typed_data(
+        &self,
+        _coin: &Coin,
+        private_key: &PrivateKey,
+        typed_data: &str,
+    ) -> Result<Vec<u8>, Error> {
+        let hash = TypedData::from_str(typed_data)?.hash()?;
+        message::sign_message(private_key, &hash, true)
+    }
+
+    fn decode_transaction(
+        &self,
+        _coin: &Coin,
+        data: &[u8],
+        chain_id: u64,
+    ) -> Result<Vec<u8>, Error> {
+        let output = Decoder::decode(data, chain_id)?;
+        let mut buf = BytesMut::with_capacity(output.encoded_len());
+        output
+            .encode(&mut buf)
+            .expect("Fail to encode the DecodeOutput");
+        Ok(buf.to_vec())
+    }
 }
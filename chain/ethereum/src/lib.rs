@@ -1,6 +1,9 @@
 pub mod address;
 mod address_checksum;
+mod decoder;
 mod eip2930;
+pub mod eip712;
 pub mod entry;
+pub mod message;
 mod signer;
 mod transaction;
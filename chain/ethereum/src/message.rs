@@ -0,0 +1,131 @@
+use super::address::EthereumAddress;
+use chain_common::private_key::PrivateKey;
+use chain_common::public_key::PublicKey;
+use crypto::hash::{Hasher, Keccak256};
+use crypto::public_key::PublicKeyType;
+use crypto::Error;
+use secp256k1::recovery::{RecoverableSignature, RecoveryId};
+use secp256k1::{Message, Secp256k1, SecretKey};
+
+// Prefix of EIP-191 `personal_sign` messages, followed by the decimal length of the message
+const PERSONAL_MESSAGE_PREFIX: &str = "\x19Ethereum Signed Message:\n";
+const HASH_SIZE: usize = 32;
+const SIGNATURE_SIZE: usize = 65;
+// `v` of a signature is the recovery id plus 27
+const V_OFFSET: u8 = 27;
+
+/// The hash of `message` signed by `personal_sign`, or the message itself if it's a raw hash
+/// signed by `eth_sign`
+pub fn hash_message(message: &[u8], raw_hash: bool) -> Result<Vec<u8>, Error> {
+    if raw_hash {
+        if message.len() != HASH_SIZE {
+            return Err(Error::InvalidMessageHash);
+        }
+        return Ok(message.to_vec());
+    }
+    let mut data = format!("{}{}", PERSONAL_MESSAGE_PREFIX, message.len()).into_bytes();
+    data.extend_from_slice(message);
+    Hasher::hash(Keccak256, &data)
+}
+
+/// The 65-byte `r || s || v` signature of `message`, `v` is 27 or 28
+pub fn sign_message(
+    private_key: &PrivateKey,
+    message: &[u8],
+    raw_hash: bool,
+) -> Result<Vec<u8>, Error> {
+    let hash = hash_message(message, raw_hash)?;
+    let secret_key =
+        SecretKey::from_slice(&private_key.data).map_err(|_| Error::InvalidPrivateKey)?;
+    let message = Message::from_slice(&hash).map_err(|_| Error::InvalidMessageHash)?;
+    let (recovery_id, signature) = Secp256k1::signing_only()
+        .sign_recoverable(&message, &secret_key)
+        .serialize_compact();
+    let mut output = signature.to_vec();
+    output.push(recovery_id.to_i32() as u8 + V_OFFSET);
+    Ok(output)
+}
+
+/// The checksummed address of the key signing `message`, `v` of the signature may be either the
+/// recovery id or the recovery id plus 27
+pub fn recover_signer(
+    message: &[u8],
+    signature: &[u8],
+    raw_hash: bool,
+    coin_id: &str,
+) -> Result<String, Error> {
+    if signature.len() != SIGNATURE_SIZE {
+        return Err(Error::InvalidSignature);
+    }
+    let hash = hash_message(message, raw_hash)?;
+    let v = signature[SIGNATURE_SIZE - 1];
+    let recovery_id = RecoveryId::from_i32(i32::from(if v >= V_OFFSET { v - V_OFFSET } else { v }))
+        .map_err(|_| Error::InvalidSignature)?;
+    let signature = RecoverableSignature::from_compact(&signature[..64], recovery_id)
+        .map_err(|_| Error::InvalidSignature)?;
+    let message = Message::from_slice(&hash).map_err(|_| Error::InvalidMessageHash)?;
+    let public_key = Secp256k1::verification_only()
+        .recover(&message, &signature)
+        .map_err(|_| Error::InvalidSignature)?;
+    let public_key = PublicKey::new(
+        PublicKeyType::Secp256k1Extended,
+        &public_key.serialize_uncompressed(),
+    )?;
+    Ok(EthereumAddress::new(&public_key, coin_id)?.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_personal_sign() {
+        // Signature of the web3.js documentation
+        let private_key = PrivateKey::from_str(
+            "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+        )
+        .unwrap();
+        let signature = sign_message(&private_key, b"Some data", false).unwrap();
+        assert_eq!(hex::encode(&signature), "b91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a0291c");
+        assert_eq!(
+            recover_signer(b"Some data", &signature, false, "ethereum").unwrap(),
+            "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23"
+        );
+
+        // `v` may be the recovery id
+        let mut signature_with_recovery_id = signature.clone();
+        signature_with_recovery_id[64] -= V_OFFSET;
+        assert_eq!(
+            recover_signer(b"Some data", &signature_with_recovery_id, false, "ethereum").unwrap(),
+            "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23"
+        );
+        assert_ne!(
+            recover_signer(b"Other data", &signature, false, "ethereum").unwrap(),
+            "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23"
+        );
+        assert_eq!(
+            recover_signer(b"Some data", &signature[..64], false, "ethereum").err(),
+            Some(Error::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn test_sign_raw_hash() {
+        let private_key = PrivateKey::from_str(
+            "4646464646464646464646464646464646464646464646464646464646464646",
+        )
+        .unwrap();
+        let hash = Hasher::hash(Keccak256, b"MaskWallet").unwrap();
+        let signature = sign_message(&private_key, &hash, true).unwrap();
+        assert_eq!(hex::encode(&signature), "354640bfdf01a3b6b8923c9acb6590d2b36cd51334e1cbda63acff4719738c637b46c2bc7679e48bb427c7e56bfc4cf3143a87b0ad382fb61bfa3dc262a052581b");
+        assert_eq!(
+            recover_signer(&hash, &signature, true, "ethereum").unwrap(),
+            "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"
+        );
+        assert_eq!(
+            sign_message(&private_key, b"MaskWallet", true).err(),
+            Some(Error::InvalidMessageHash)
+        );
+    }
+}
@@ -1,103 +1,121 @@
 use super::address::EthereumAddress;
-use super::transaction::{Eip1559Transaction, LegacyTransaction, Transaction};
-use chain_common::ethereum::{SignInput, SignOutput};
+use super::transaction::{Eip1559Transaction, Eip2930Transaction, LegacyTransaction, Transaction};
+use chain_common::ethereum::{SignInput, SignOutput, TransactionType};
 use chain_common::private_key::PrivateKey;
 use chain_common::Error;
 use ethereum_types::U256;
-use secp256k1::Secp256k1;
+use secp256k1::{Secp256k1, SecretKey};
 use std::convert::TryFrom;
 
 pub struct Signer;
 
 impl Signer {
     pub fn sign(private_key: &PrivateKey, sign_input: &SignInput) -> Result<SignOutput, Error> {
-        if !EthereumAddress::is_valid(&sign_input.to_address) {
+        // An empty recipient creates a contract, which must have the init code as the payload
+        let is_contract_creation = sign_input.to_address.is_empty();
+        if is_contract_creation && sign_input.payload.is_empty() {
             return Err(Error::InvalidSignInput);
         }
-        let chain_id = sign_input.chain_id;
-        let secp = Secp256k1::signing_only();
-        let secrect_key = &secp256k1::SecretKey::from_slice(&private_key.data)
-            .map_err(|_| Error::InvalidPrivateKey)?;
+        if !is_contract_creation && !EthereumAddress::is_valid(&sign_input.to_address) {
+            return Err(Error::InvalidSignInput);
+        }
+        let secrect_key =
+            SecretKey::from_slice(&private_key.data).map_err(|_| Error::InvalidPrivateKey)?;
         let gas_price =
             U256::from_str_radix(&sign_input.gas_price, 16).map_err(|_| Error::InvalidSignInput)?;
-        match gas_price == U256::from(0) {
-            false => {
-                let transaction = LegacyTransaction::try_from(sign_input)?;
-                let hash = transaction
-                    .hash(chain_id)
-                    .map_err(|_| Error::InvalidSignInput)?;
-                let (v_id, signature) = secp
-                    .sign_recoverable(
-                        &secp256k1::Message::from_slice(&hash)
-                            .map_err(|_| Error::InvalidSignInput)?,
-                        &secrect_key,
-                    )
-                    .serialize_compact();
-                let mut r = signature[0..32].to_vec();
-                let mut s = signature[32..64].to_vec();
-                while r[0] == 0 {
-                    r.remove(0);
-                }
-                while s[0] == 0 {
-                    s.remove(0);
-                }
-                let mut v = v_id.to_i32();
-                let chain_id_i32 = chain_id as i32;
-                if transaction.uses_replay_protection() {
-                    // Embed chainID in V param, for replay protection, legacy (EIP155)
-                    if chain_id_i32 != 0 {
-                        v += 35 + chain_id_i32 * 2;
-                    } else {
-                        v += 27;
-                    }
-                }
-                let encoded = transaction.encode_transaction(v as u64, &r, &s, chain_id);
-                Ok(SignOutput {
-                    data: transaction.base.payload,
-                    encoded,
-                    r,
-                    v: v as u32,
-                    s,
-                })
-            }
-            true => {
-                let transaction = Eip1559Transaction::try_from(sign_input)?;
-                let hash = transaction
-                    .hash(chain_id)
-                    .map_err(|_| Error::InvalidSignInput)?;
-                let (v_id, signature) = secp
-                    .sign_recoverable(
-                        &secp256k1::Message::from_slice(&hash)
-                            .map_err(|_| Error::InvalidSignInput)?,
-                        &secrect_key,
-                    )
-                    .serialize_compact();
-                let mut r = signature[0..32].to_vec();
-                let mut s = signature[32..64].to_vec();
-                while r[0] == 0 {
-                    r.remove(0);
-                }
-                while s[0] == 0 {
-                    s.remove(0);
-                }
-                let v = v_id.to_i32();
-                let encoded = transaction.encode_transaction(v as u64, &r, &s, chain_id);
-                Ok(SignOutput {
-                    data: transaction.base.payload,
-                    encoded,
-                    r,
-                    v: v as u32,
-                    s,
-                })
+        let tx_type =
+            TransactionType::from_i32(sign_input.tx_type).ok_or(Error::InvalidSignInput)?;
+        let tx_type = match tx_type {
+            TransactionType::Auto if gas_price == U256::from(0) => TransactionType::Eip1559,
+            TransactionType::Auto if sign_input.access_list.is_empty() => TransactionType::Legacy,
+            TransactionType::Auto => TransactionType::AccessList,
+            tx_type => tx_type,
+        };
+        let mut output = match tx_type {
+            TransactionType::Legacy => Self::sign_transaction(
+                &LegacyTransaction::try_from(sign_input)?,
+                &secrect_key,
+                sign_input,
+            ),
+            TransactionType::AccessList => Self::sign_transaction(
+                &Eip2930Transaction::try_from(sign_input)?,
+                &secrect_key,
+                sign_input,
+            ),
+            TransactionType::Eip1559 | TransactionType::Auto => Self::sign_transaction(
+                &Eip1559Transaction::try_from(sign_input)?,
+                &secrect_key,
+                sign_input,
+            ),
+        }?;
+        if is_contract_creation {
+            output.contract_address = Self::contract_address(private_key, sign_input)?;
+        }
+        Ok(output)
+    }
+
+    fn contract_address(private_key: &PrivateKey, sign_input: &SignInput) -> Result<String, Error> {
+        let nonce =
+            U256::from_str_radix(&sign_input.nonce, 16).map_err(|_| Error::InvalidSignInput)?;
+        let public_key = private_key
+            .get_public_key("secp256k1Extended")
+            .map_err(|_| Error::InvalidPrivateKey)?;
+        let sender =
+            EthereumAddress::new(&public_key, "ethereum").map_err(|_| Error::InvalidPrivateKey)?;
+        let contract =
+            EthereumAddress::new_contract(&sender, &nonce).map_err(|_| Error::InvalidSignInput)?;
+        Ok(contract.to_string())
+    }
+
+    fn sign_transaction<T: Transaction>(
+        transaction: &T,
+        secrect_key: &SecretKey,
+        sign_input: &SignInput,
+    ) -> Result<SignOutput, Error> {
+        let chain_id = sign_input.chain_id;
+        let hash = transaction
+            .hash(chain_id)
+            .map_err(|_| Error::InvalidSignInput)?;
+        let (v_id, signature) = Secp256k1::signing_only()
+            .sign_recoverable(
+                &secp256k1::Message::from_slice(&hash).map_err(|_| Error::InvalidSignInput)?,
+                secrect_key,
+            )
+            .serialize_compact();
+        let mut r = signature[0..32].to_vec();
+        let mut s = signature[32..64].to_vec();
+        while r[0] == 0 {
+            r.remove(0);
+        }
+        while s[0] == 0 {
+            s.remove(0);
+        }
+        let mut v = v_id.to_i32();
+        let chain_id_i32 = chain_id as i32;
+        if transaction.uses_replay_protection() {
+            // Embed chainID in V param, for replay protection, legacy (EIP155)
+            if chain_id_i32 != 0 {
+                v += 35 + chain_id_i32 * 2;
+            } else {
+                v += 27;
             }
         }
+        let encoded = transaction.encode_transaction(v as u64, &r, &s, chain_id);
+        Ok(SignOutput {
+            data: sign_input.payload.clone(),
+            encoded,
+            r,
+            v: v as u32,
+            s,
+            contract_address: "".to_owned(),
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chain_common::ethereum::SignInput;
+    use chain_common::ethereum::{AccessListItem, SignInput};
     use chain_common::private_key::PrivateKey;
     use hex;
     use std::convert::TryFrom;
@@ -114,6 +132,7 @@ mod tests {
             amount: "0xde0b6b3a7640000".to_owned(),
             payload: "".as_bytes().to_vec(),
             to_address: "0x3535353535353535353535353535353535353535".to_owned(),
+            ..Default::default()
         };
         let transaction = LegacyTransaction::try_from(&input).unwrap();
         assert_eq!(transaction.base.amount.to_string(), "1000000000000000000");
@@ -161,6 +180,7 @@ mod tests {
             amount: "0x1bc16d674ec80000".to_owned(),
             payload: payload_bytes.to_vec(),
             to_address: "0x5322b34c88ed0691971bf52a7047448f0f4efc84".to_owned(),
+            ..Default::default()
         };
         let transaction = LegacyTransaction::try_from(&input).unwrap();
         assert_eq!(transaction.base.amount.to_string(), "2000000000000000000");
@@ -188,6 +208,7 @@ mod tests {
             amount: "0xde0b6b3a7640000".to_owned(),
             payload: "".as_bytes().to_vec(),
             to_address: "0x146aed09cd9dea7a64de689c5d3ef73d2ee5ca".to_owned(), // short addr
+            ..Default::default()
         };
         let private_key = PrivateKey::from_str(
             "4646464646464646464646464646464646464646464646464646464646464646",
@@ -210,6 +231,7 @@ mod tests {
             amount: "0x1EE0C29F50CB1".to_owned(),
             payload: "".as_bytes().to_vec(),
             to_address: "0xB9F5771C27664bF2282D98E09D7F50cEc7cB01a7".to_owned(),
+            ..Default::default()
         };
         let private_key = PrivateKey::from_str(
             "4f96ed80e9a7555a6f74b3d658afdd9c756b0a40d4ca30c42c2039eb449bb904",
@@ -233,6 +255,7 @@ mod tests {
             amount: "0x38d7ea4c68000".to_owned(),
             payload: payload_bytes.to_vec(),
             to_address: "0xabbe1101fd8fa5847c452a6d70c8655532b03c33".to_owned(),
+            ..Default::default()
         };
         let private_key = PrivateKey::from_str(
             "76b01c1392fb4d2a0e2103162cf4fd1636c838ebcb3adfeceb82dd552253e287",
@@ -241,4 +264,145 @@ mod tests {
         let output = Signer::sign(&private_key, &input).unwrap();
         assert_eq!(output.v, 37);
     }
+
+    fn access_list() -> Vec<AccessListItem> {
+        vec![AccessListItem {
+            address: "0xdac17f958d2ee523a2206206994597c13d831ec7".to_owned(),
+            storage_keys: vec![
+                "0x0000000000000000000000000000000000000000000000000000000000000000".to_owned(),
+                "0x0000000000000000000000000000000000000000000000000000000000000001".to_owned(),
+            ],
+        }]
+    }
+
+    #[test]
+    fn test_sign_eip2930() {
+        let payload = hex::decode("a9059cbb0000000000000000000000003535353535353535353535353535353535353535000000000000000000000000000000000000000000000000000000000000000a").unwrap();
+        let input = SignInput {
+            chain_id: 1,
+            nonce: "0x3".to_owned(),
+            gas_limit: "0x186a0".to_owned(),
+            gas_price: "0x3b9aca00".to_owned(),
+            amount: "0x0".to_owned(),
+            payload,
+            to_address: "0xdac17f958d2ee523a2206206994597c13d831ec7".to_owned(),
+            access_list: access_list(),
+            ..Default::default()
+        };
+        let private_key = PrivateKey::from_str(
+            "4646464646464646464646464646464646464646464646464646464646464646",
+        )
+        .unwrap();
+        let output = Signer::sign(&private_key, &input).unwrap();
+        assert_eq!(output.v, 1);
+        assert_eq!(hex::encode(&output.encoded), "01f901070103843b9aca00830186a094dac17f958d2ee523a2206206994597c13d831ec780b844a9059cbb0000000000000000000000003535353535353535353535353535353535353535000000000000000000000000000000000000000000000000000000000000000af85bf85994dac17f958d2ee523a2206206994597c13d831ec7f842a00000000000000000000000000000000000000000000000000000000000000000a0000000000000000000000000000000000000000000000000000000000000000101a06119b7d42c3e29398b09542520b16276b9aa328a78bc528c0c07db88fb826661a075eb72fadfd8270c2c2f47250b6e4f5360e3a6d451caa027a45ac69a40f4ed1a");
+
+        // The type is chosen by the access list, or selected explicitly
+        let explicit = SignInput {
+            tx_type: TransactionType::AccessList as i32,
+            ..input.clone()
+        };
+        assert_eq!(
+            Signer::sign(&private_key, &explicit).unwrap().encoded,
+            output.encoded
+        );
+        let legacy = SignInput {
+            tx_type: TransactionType::Legacy as i32,
+            ..input.clone()
+        };
+        assert_eq!(
+            Signer::sign(&private_key, &legacy).err(),
+            Some(Error::InvalidSignInput)
+        );
+        let mut invalid_key = input;
+        invalid_key.access_list[0].storage_keys[0] = "0x01".to_owned();
+        assert_eq!(
+            Signer::sign(&private_key, &invalid_key).err(),
+            Some(Error::InvalidSignInput)
+        );
+    }
+
+    #[test]
+    fn test_sign_eip1559_access_list() {
+        let input = SignInput {
+            chain_id: 3,
+            nonce: "0x6".to_owned(),
+            gas_limit: "0x526C".to_owned(),
+            gas_price: "0x0".to_owned(),
+            max_inclusion_fee_per_gas: "0x77359400".to_owned(),
+            max_fee_per_gas: "0xB2D05E00".to_owned(),
+            amount: "0x1EE0C29F50CB1".to_owned(),
+            payload: "".as_bytes().to_vec(),
+            to_address: "0xB9F5771C27664bF2282D98E09D7F50cEc7cB01a7".to_owned(),
+            access_list: access_list(),
+            tx_type: TransactionType::Eip1559 as i32,
+        };
+        let private_key = PrivateKey::from_str(
+            "4f96ed80e9a7555a6f74b3d658afdd9c756b0a40d4ca30c42c2039eb449bb904",
+        )
+        .unwrap();
+        let output = Signer::sign(&private_key, &input).unwrap();
+        assert_eq!(output.v, 1);
+        assert_eq!(hex::encode(output.encoded), "02f8cd0306847735940084b2d05e0082526c94b9f5771c27664bf2282d98e09d7f50cec7cb01a78701ee0c29f50cb180f85bf85994dac17f958d2ee523a2206206994597c13d831ec7f842a00000000000000000000000000000000000000000000000000000000000000000a0000000000000000000000000000000000000000000000000000000000000000101a0dce440207f3156722a8ea30d8870cf4d5570f4ccc4fb0d9bc174dd01321a466ba00d658faab245f454f80f93c310cdece134ab414c2bdbc9020f2df6ced23aad80");
+    }
+
+    #[test]
+    fn test_sign_contract_creation() {
+        let init_code = hex::decode("6080604052348015600f57600080fd5b50603f80601d6000396000f3fe6080604052600080fdfea164736f6c6343000806000a").unwrap();
+        let input = SignInput {
+            chain_id: 1,
+            nonce: "0x9".to_owned(),
+            gas_limit: "0x186a0".to_owned(),
+            gas_price: "0x4a817c800".to_owned(),
+            amount: "0x0".to_owned(),
+            payload: init_code,
+            to_address: "".to_owned(),
+            ..Default::default()
+        };
+        let private_key = PrivateKey::from_str(
+            "4646464646464646464646464646464646464646464646464646464646464646",
+        )
+        .unwrap();
+        let output = Signer::sign(&private_key, &input).unwrap();
+        assert_eq!(output.v, 38);
+        assert_eq!(hex::encode(&output.encoded), "f884098504a817c800830186a08080b36080604052348015600f57600080fd5b50603f80601d6000396000f3fe6080604052600080fdfea164736f6c6343000806000a26a01325dc40127d392617fc2fcff0a7198918116accece7356554a0f9a2c8007199a04da8e8ffce5c291a03f45fe9dd6c58e8e704c97da231fa1249b99479b4a0710a");
+        assert_eq!(
+            output.contract_address,
+            "0x1fD43573682A8E3CBA6368836BaF6f9DBEadEfCa"
+        );
+
+        let eip1559 = SignInput {
+            gas_price: "0x0".to_owned(),
+            max_inclusion_fee_per_gas: "0x77359400".to_owned(),
+            max_fee_per_gas: "0xB2D05E00".to_owned(),
+            ..input.clone()
+        };
+        let output = Signer::sign(&private_key, &eip1559).unwrap();
+        assert_eq!(output.v, 1);
+        assert_eq!(hex::encode(&output.encoded), "02f88a0109847735940084b2d05e00830186a08080b36080604052348015600f57600080fd5b50603f80601d6000396000f3fe6080604052600080fdfea164736f6c6343000806000ac001a0e7642c011da95a02fca0152c49ad4bb49b2105c565097dfb419744c8f0e95985a00d49d50eb8988247aa17e68072404a7ded5c4392178b4316fce696455a6c0ec8");
+        assert_eq!(
+            output.contract_address,
+            "0x1fD43573682A8E3CBA6368836BaF6f9DBEadEfCa"
+        );
+
+        // A transfer has no contract address, and a contract creation needs the init code
+        let transfer = SignInput {
+            to_address: "0x3535353535353535353535353535353535353535".to_owned(),
+            ..input.clone()
+        };
+        assert_eq!(
+            Signer::sign(&private_key, &transfer)
+                .unwrap()
+                .contract_address,
+            ""
+        );
+        let no_init_code = SignInput {
+            payload: vec![],
+            ..input
+        };
+        assert_eq!(
+            Signer::sign(&private_key, &no_init_code).err(),
+            Some(Error::InvalidSignInput)
+        );
+    }
 }
@@ -35,15 +35,27 @@ pub struct LegacyTransaction {
     pub gas_price: U256,
 }
 
+/// EIP-2930 (type 1) transaction, a legacy transaction with an access list
+pub struct Eip2930Transaction {
+    pub base: TransactionBase,
+
+    /// The transaction gas price in wei
+    pub gas_price: U256,
+
+    pub access_list: AccessList,
+}
+
 pub struct Eip1559Transaction {
     pub base: TransactionBase,
 
     pub max_inclusion_fee_per_gas: U256,
 
     pub max_fee_per_gas: U256,
+
+    pub access_list: AccessList,
 }
 
-impl TryFrom<&SignInput> for LegacyTransaction {
+impl TryFrom<&SignInput> for TransactionBase {
     type Error = Error;
     fn try_from(input: &SignInput) -> Result<Self, Self::Error> {
         // Trim the '0x' prefix
@@ -55,60 +67,63 @@ impl TryFrom<&SignInput> for LegacyTransaction {
             U256::from_str_radix(&input.amount, 16).map_err(|_| Error::InvalidSignInput)?;
         let gas_limit =
             U256::from_str_radix(&input.gas_limit, 16).map_err(|_| Error::InvalidSignInput)?;
-        let gas_price =
-            U256::from_str_radix(&input.gas_price, 16).map_err(|_| Error::InvalidSignInput)?;
         let nonce = U256::from_str_radix(&input.nonce, 16).map_err(|_| Error::InvalidSignInput)?;
         let receiver: Option<H160> = match trimmed_address.is_empty() {
             true => None,
             false => Some(H160::from_str(&trimmed_address).map_err(|_| Error::InvalidSignInput)?),
         };
 
-        let base = TransactionBase {
+        Ok(TransactionBase {
             nonce,
             payload: input.payload.to_vec(),
             gas_limit,
             receiver,
             amount,
-        };
+        })
+    }
+}
 
-        Ok(LegacyTransaction { base, gas_price })
+impl TryFrom<&SignInput> for LegacyTransaction {
+    type Error = Error;
+    fn try_from(input: &SignInput) -> Result<Self, Self::Error> {
+        // Legacy transactions can't carry an access list
+        if !input.access_list.is_empty() {
+            return Err(Error::InvalidSignInput);
+        }
+        let gas_price =
+            U256::from_str_radix(&input.gas_price, 16).map_err(|_| Error::InvalidSignInput)?;
+        Ok(LegacyTransaction {
+            base: TransactionBase::try_from(input)?,
+            gas_price,
+        })
+    }
+}
+
+impl TryFrom<&SignInput> for Eip2930Transaction {
+    type Error = Error;
+    fn try_from(input: &SignInput) -> Result<Self, Self::Error> {
+        let gas_price =
+            U256::from_str_radix(&input.gas_price, 16).map_err(|_| Error::InvalidSignInput)?;
+        Ok(Eip2930Transaction {
+            base: TransactionBase::try_from(input)?,
+            gas_price,
+            access_list: AccessList::try_from(input.access_list.as_slice())?,
+        })
     }
 }
 
 impl TryFrom<&SignInput> for Eip1559Transaction {
     type Error = Error;
     fn try_from(input: &SignInput) -> Result<Self, Self::Error> {
-        // Trim the '0x' prefix
-        let trimmed_address = match input.to_address.starts_with("0x") {
-            true => input.to_address[2..].to_string(),
-            false => input.to_address.to_string(),
-        };
-        let amount =
-            U256::from_str_radix(&input.amount, 16).map_err(|_| Error::InvalidSignInput)?;
-        let gas_limit =
-            U256::from_str_radix(&input.gas_limit, 16).map_err(|_| Error::InvalidSignInput)?;
         let max_fee_per_gas = U256::from_str_radix(&input.max_fee_per_gas, 16)
             .map_err(|_| Error::InvalidSignInput)?;
         let max_inclusion_fee_per_gas = U256::from_str_radix(&input.max_inclusion_fee_per_gas, 16)
             .map_err(|_| Error::InvalidSignInput)?;
-        let nonce = U256::from_str_radix(&input.nonce, 16).map_err(|_| Error::InvalidSignInput)?;
-        let receiver: Option<H160> = match trimmed_address.is_empty() {
-            true => None,
-            false => Some(H160::from_str(&trimmed_address).map_err(|_| Error::InvalidSignInput)?),
-        };
-
-        let base = TransactionBase {
-            nonce,
-            payload: input.payload.to_vec(),
-            gas_limit,
-            receiver,
-            amount,
-        };
-
         Ok(Eip1559Transaction {
-            base,
+            base: TransactionBase::try_from(input)?,
             max_inclusion_fee_per_gas,
             max_fee_per_gas,
+            access_list: AccessList::try_from(input.access_list.as_slice())?,
         })
     }
 }
@@ -156,6 +171,59 @@ impl Transaction for LegacyTransaction {
     }
 }
 
+impl Transaction for Eip2930Transaction {
+    fn uses_replay_protection(&self) -> bool {
+        false
+    }
+
+    fn hash(&self, chain_id: u64) -> Result<Vec<u8>, CryptoError> {
+        let mut rlp_stream = RlpStream::new_list(8);
+        rlp_stream.append(&chain_id);
+        rlp_stream.append(&self.base.nonce);
+        rlp_stream.append(&self.gas_price);
+        rlp_stream.append(&self.base.gas_limit);
+        if let Some(ref t) = self.base.receiver {
+            rlp_stream.append(t);
+        } else {
+            rlp_stream.append(&vec![]);
+        }
+        rlp_stream.append(&self.base.amount);
+        rlp_stream.append(&self.base.payload);
+        rlp_stream.append(&self.access_list);
+        let rlp_encoded = rlp_stream.out();
+
+        let mut encoded = vec![];
+        encoded.extend_from_slice(&[0x1]);
+        encoded.extend_from_slice(&rlp_encoded);
+        Hasher::hash(Keccak256, &encoded)
+    }
+
+    fn encode_transaction(&self, v: u64, r: &[u8], s: &[u8], chain_id: u64) -> Vec<u8> {
+        let mut rlp_stream = RlpStream::new_list(11);
+        rlp_stream.append(&chain_id);
+        rlp_stream.append(&self.base.nonce);
+        rlp_stream.append(&self.gas_price);
+        rlp_stream.append(&self.base.gas_limit);
+        if let Some(ref t) = self.base.receiver {
+            rlp_stream.append(t);
+        } else {
+            rlp_stream.append(&vec![]);
+        }
+        rlp_stream.append(&self.base.amount);
+        rlp_stream.append(&self.base.payload);
+        rlp_stream.append(&self.access_list);
+        rlp_stream.append(&v);
+        rlp_stream.append(&r);
+        rlp_stream.append(&s);
+        let rlp_encoded = rlp_stream.out();
+
+        let mut encoded = vec![];
+        encoded.extend_from_slice(&[0x1]);
+        encoded.extend_from_slice(&rlp_encoded);
+        encoded
+    }
+}
+
 impl Transaction for Eip1559Transaction {
     fn uses_replay_protection(&self) -> bool {
         false
@@ -175,8 +243,7 @@ impl Transaction for Eip1559Transaction {
         }
         rlp_stream.append(&self.base.amount);
         rlp_stream.append(&self.base.payload);
-        let access_list = AccessList::from(vec![]);
-        rlp_stream.append(&access_list);
+        rlp_stream.append(&self.access_list);
         let rlp_encoded = rlp_stream.out();
 
         let mut encoded = vec![];
@@ -200,8 +267,7 @@ impl Transaction for Eip1559Transaction {
         }
         rlp_stream.append(&self.base.amount);
         rlp_stream.append(&self.base.payload);
-        let access_list = AccessList::from(vec![]);
-        rlp_stream.append(&access_list);
+        rlp_stream.append(&self.access_list);
         rlp_stream.append(&v);
         rlp_stream.append(&r);
         rlp_stream.append(&s);
@@ -0,0 +1,152 @@
+use bitcoin::util::base58;
+use chain_common::public_key::PublicKey;
+use crypto::hash::{Blake2b512, Hasher};
+use crypto::public_key::PublicKeyType;
+use crypto::Error;
+
+// SS58 network prefix of the Polkadot relay chain
+pub const POLKADOT_NETWORK: u16 = 0;
+
+const ACCOUNT_ID_SIZE: usize = 32;
+const CHECKSUM_SIZE: usize = 2;
+const CHECKSUM_PREFIX: &[u8] = b"SS58PRE";
+// Network prefixes above this value can't be encoded in SS58
+const MAX_NETWORK: u16 = 0x3fff;
+
+pub struct PolkadotAddress {
+    pub network: u16,
+    pub account_id: Vec<u8>,
+}
+
+impl PolkadotAddress {
+    pub fn is_valid(address: &str, network: u16) -> bool {
+        Self::from_ss58(address, network).is_some()
+    }
+
+    pub fn new(public_key: &PublicKey, network: u16) -> Result<Self, Error> {
+        if public_key.r#type != PublicKeyType::Ed25519
+            && public_key.r#type != PublicKeyType::Sr25519
+        {
+            return Err(Error::NotSupportedPublicKeyType);
+        }
+        if public_key.data.len() != ACCOUNT_ID_SIZE || network > MAX_NETWORK {
+            return Err(Error::InvalidPublicKey);
+        }
+        Ok(PolkadotAddress {
+            network,
+            account_id: public_key.data.to_vec(),
+        })
+    }
+
+    /// Decode a SS58 address, which must belong to `network`
+    pub fn from_ss58(address: &str, network: u16) -> Option<Self> {
+        let data = base58::from(address).ok()?;
+        let (decoded_network, prefix_len) = match data.first()? {
+            0..=63 => (data[0] as u16, 1),
+            64..=127 => {
+                let second = *data.get(1)?;
+                let lower = (data[0] << 2) | (second >> 6);
+                let upper = second & 0b0011_1111;
+                (lower as u16 | (upper as u16) << 8, 2)
+            }
+            _ => return None,
+        };
+        if decoded_network != network || data.len() != prefix_len + ACCOUNT_ID_SIZE + CHECKSUM_SIZE
+        {
+            return None;
+        }
+        let (body, checksum) = data.split_at(prefix_len + ACCOUNT_ID_SIZE);
+        if Self::checksum(body) != checksum {
+            return None;
+        }
+        Some(PolkadotAddress {
+            network,
+            account_id: body[prefix_len..].to_vec(),
+        })
+    }
+
+    fn checksum(body: &[u8]) -> Vec<u8> {
+        let hash = Hasher::hash(Blake2b512, &[CHECKSUM_PREFIX, body].concat())
+            .expect("blake2b never fails");
+        hash[..CHECKSUM_SIZE].to_vec()
+    }
+}
+
+impl ToString for PolkadotAddress {
+    fn to_string(&self) -> String {
+        let mut data = match self.network {
+            0..=63 => vec![self.network as u8],
+            _ => vec![
+                ((self.network & 0b1111_1100) >> 2) as u8 | 0b0100_0000,
+                (self.network >> 8) as u8 | ((self.network & 0b11) << 6) as u8,
+            ],
+        };
+        data.extend_from_slice(&self.account_id);
+        let checksum = Self::checksum(&data);
+        data.extend_from_slice(&checksum);
+        base58::encode_slice(&data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE_PUBLIC_KEY: &str =
+        "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d";
+
+    fn alice_address(network: u16) -> String {
+        let public_key = PublicKey {
+            r#type: PublicKeyType::Ed25519,
+            data: hex::decode(ALICE_PUBLIC_KEY).unwrap(),
+        };
+        PolkadotAddress::new(&public_key, network)
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn test_derive_from_pub_key() {
+        assert_eq!(
+            alice_address(0),
+            "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5"
+        );
+        assert_eq!(
+            alice_address(2),
+            "HNZata7iMYWmk5RvZRTiAsSDhV8366zq2YGb3tLH5Upf74F"
+        );
+        assert_eq!(
+            alice_address(42),
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+        );
+        assert_eq!(
+            alice_address(255),
+            "yGHXkYLYqxijLKKfd9Q2CB9shRVu8rPNBS53wvwGTutYg4zTg"
+        );
+    }
+
+    #[test]
+    fn test_validate_address() {
+        for network in [0u16, 2, 42, 255].iter() {
+            let address = alice_address(*network);
+            assert!(PolkadotAddress::is_valid(&address, *network));
+            let decoded = PolkadotAddress::from_ss58(&address, *network).unwrap();
+            assert_eq!(hex::encode(decoded.account_id), ALICE_PUBLIC_KEY);
+        }
+        // Valid address of another network
+        assert!(!PolkadotAddress::is_valid(
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+            0
+        ));
+        // Broken checksum
+        assert!(!PolkadotAddress::is_valid(
+            "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp6",
+            0
+        ));
+        assert!(!PolkadotAddress::is_valid("abc", 0));
+        assert!(!PolkadotAddress::is_valid(
+            "0x5bb497e8d9fe26e92dd1be01e32076c8e024d167",
+            0
+        ));
+    }
+}
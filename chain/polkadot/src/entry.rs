@@ -0,0 +1,75 @@
+use super::address::{PolkadotAddress, POLKADOT_NETWORK};
+use super::signer::Signer;
+use bytes::BytesMut;
+use chain_common::coin::Coin;
+use chain_common::entry::{ChainExportType, ChainImportType, Entry};
+use chain_common::polkadot::SignInput;
+use chain_common::private_key::PrivateKey;
+use chain_common::public_key::PublicKey;
+use crypto::public_key::PublicKeyType;
+use crypto::Error;
+use prost::Message;
+use std::str::FromStr;
+
+pub struct PolkadotEntry {
+    // SS58 network prefix of the addresses
+    pub network: u16,
+}
+
+impl PolkadotEntry {
+    /// Entry of the network configured by the `ss58_prefix` of `coin`, Polkadot if absent
+    pub fn new(coin: &Coin) -> Self {
+        let network = coin
+            .get_value("ss58_prefix")
+            .and_then(|prefix| prefix.parse().ok())
+            .unwrap_or(POLKADOT_NETWORK);
+        PolkadotEntry { network }
+    }
+}
+
+impl Entry for PolkadotEntry {
+    fn get_supported_import_types(&self) -> Vec<ChainImportType> {
+        vec![ChainImportType::PrivateKey, ChainImportType::Mnemonic]
+    }
+
+    fn get_supported_export_types(&self) -> Vec<ChainExportType> {
+        vec![ChainExportType::PrivateKey]
+    }
+
+    fn validate_address(&self, address: &str) -> bool {
+        PolkadotAddress::is_valid(address, self.network)
+    }
+
+    fn derive_address(
+        &self,
+        _coin: &Coin,
+        public_key: &PublicKey,
+        _p2pkh: &[u8],
+        _hrp: &[u8],
+    ) -> Result<String, Error> {
+        let address = PolkadotAddress::new(public_key, self.network)?;
+        Ok(address.to_string())
+    }
+
+    fn sign(
+        &self,
+        coin: &Coin,
+        private_key: &PrivateKey,
+        payload: &[u8],
+    ) -> Result<Vec<u8>, Error> {
+        let sign_input: SignInput = match SignInput::decode(payload) {
+            Ok(request) => request,
+            Err(_) => return Err(Error::InvalidPrivateKey),
+        };
+        let public_key_type = PublicKeyType::from_str(&coin.public_key_type)
+            .map_err(|_| Error::NotSupportedPublicKeyType)?;
+        let output = Signer::sign(private_key, &public_key_type, self.network, &sign_input)
+            .map_err(|_| Error::InvalidPrivateKey)?;
+
+        let mut buf = BytesMut::with_capacity(output.encoded_len());
+        output
+            .encode(&mut buf)
+            .expect("Fail to encode the SignOutput");
+        Ok(buf.to_vec())
+    }
+}
@@ -0,0 +1,153 @@
+use super::address::PolkadotAddress;
+use super::scale::{encode_compact, Era};
+use chain_common::polkadot::{sign_input::MessageOneof, BalanceTransfer, SignInput};
+use chain_common::Error;
+use crypto::hash::{Blake2b256, Hasher};
+
+const HASH_SIZE: usize = 32;
+// Polkadot's Balances.transfer
+const DEFAULT_TRANSFER_CALL_INDICES: [u8; 2] = [0x05, 0x00];
+// Signed extrinsic of format version 4
+const SIGNED_EXTRINSIC_VERSION: u8 = 0x84;
+// MultiAddress::Id
+const MULTI_ADDRESS_ID: u8 = 0x00;
+// Signing payloads longer than this are hashed before signing
+const MAX_PAYLOAD_SIZE: usize = 256;
+
+/// The variant of a MultiSignature
+pub enum SignatureType {
+    Ed25519 = 0x00,
+    Sr25519 = 0x01,
+}
+
+fn parse_u128(hex_str: &str) -> Result<u128, Error> {
+    match hex_str.is_empty() {
+        true => Ok(0),
+        false => u128::from_str_radix(hex_str, 16).map_err(|_| Error::InvalidSignInput),
+    }
+}
+
+fn encode_transfer(transfer: &BalanceTransfer, network: u16) -> Result<Vec<u8>, Error> {
+    let to_address =
+        PolkadotAddress::from_ss58(&transfer.to_address, network).ok_or(Error::InvalidSignInput)?;
+    let mut call = match transfer.call_indices.len() {
+        0 => DEFAULT_TRANSFER_CALL_INDICES.to_vec(),
+        2 => transfer.call_indices.to_vec(),
+        _ => return Err(Error::InvalidSignInput),
+    };
+    call.push(MULTI_ADDRESS_ID);
+    call.extend_from_slice(&to_address.account_id);
+    encode_compact(&mut call, parse_u128(&transfer.value)?);
+    Ok(call)
+}
+
+pub struct Extrinsic {
+    pub call: Vec<u8>,
+    pub era: Era,
+    pub nonce: u64,
+    pub tip: u128,
+    pub spec_version: u32,
+    pub transaction_version: u32,
+    pub genesis_hash: Vec<u8>,
+    pub block_hash: Vec<u8>,
+}
+
+impl Extrinsic {
+    pub fn new(input: &SignInput, network: u16) -> Result<Self, Error> {
+        let call = match &input.message_oneof {
+            Some(MessageOneof::BalanceTransfer(transfer)) => encode_transfer(transfer, network)?,
+            None => return Err(Error::InvalidSignInput),
+        };
+        if input.genesis_hash.len() != HASH_SIZE {
+            return Err(Error::InvalidSignInput);
+        }
+        let (era, block_hash) = match &input.era {
+            Some(era) => (
+                Era::mortal(era.period, era.block_number),
+                input.block_hash.to_vec(),
+            ),
+            None if input.block_hash.is_empty() => (Era::Immortal, input.genesis_hash.to_vec()),
+            None => (Era::Immortal, input.block_hash.to_vec()),
+        };
+        if block_hash.len() != HASH_SIZE {
+            return Err(Error::InvalidSignInput);
+        }
+        Ok(Extrinsic {
+            call,
+            era,
+            nonce: input.nonce,
+            tip: parse_u128(&input.tip)?,
+            spec_version: input.spec_version,
+            transaction_version: input.transaction_version,
+            genesis_hash: input.genesis_hash.to_vec(),
+            block_hash,
+        })
+    }
+
+    // The signed extensions included in the extrinsic: era, nonce and tip
+    fn encode_extra(&self, buf: &mut Vec<u8>) {
+        self.era.encode(buf);
+        encode_compact(buf, self.nonce as u128);
+        encode_compact(buf, self.tip);
+    }
+
+    /// The payload to be signed by the sender
+    pub fn signing_payload(&self) -> Vec<u8> {
+        let mut payload = self.call.to_vec();
+        self.encode_extra(&mut payload);
+        payload.extend_from_slice(&self.spec_version.to_le_bytes());
+        payload.extend_from_slice(&self.transaction_version.to_le_bytes());
+        payload.extend_from_slice(&self.genesis_hash);
+        payload.extend_from_slice(&self.block_hash);
+        match payload.len() > MAX_PAYLOAD_SIZE {
+            true => Hasher::hash(Blake2b256, &payload).expect("blake2b never fails"),
+            false => payload,
+        }
+    }
+
+    /// The length-prefixed encoding of the signed extrinsic
+    pub fn encode_signed(
+        &self,
+        signer: &[u8],
+        signature_type: SignatureType,
+        signature: &[u8],
+    ) -> Vec<u8> {
+        let mut extrinsic = vec![SIGNED_EXTRINSIC_VERSION, MULTI_ADDRESS_ID];
+        extrinsic.extend_from_slice(signer);
+        extrinsic.push(signature_type as u8);
+        extrinsic.extend_from_slice(signature);
+        self.encode_extra(&mut extrinsic);
+        extrinsic.extend_from_slice(&self.call);
+
+        let mut encoded = vec![];
+        encode_compact(&mut encoded, extrinsic.len() as u128);
+        encoded.extend_from_slice(&extrinsic);
+        encoded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_long_signing_payload_is_hashed() {
+        let mut extrinsic = Extrinsic {
+            call: vec![0u8; 100],
+            era: Era::Immortal,
+            nonce: 0,
+            tip: 0,
+            spec_version: 0,
+            transaction_version: 0,
+            genesis_hash: vec![0u8; HASH_SIZE],
+            block_hash: vec![0u8; HASH_SIZE],
+        };
+        assert_eq!(extrinsic.signing_payload(), vec![0u8; 175]);
+
+        extrinsic.call = vec![0u8; 200];
+        assert_eq!(
+            hex::encode(extrinsic.signing_payload()),
+            "f580f51c39e5f64ddfda06ea925557936b50616bb5e55c93f956d8b2e41d01f2"
+        );
+    }
+}
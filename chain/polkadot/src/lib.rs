@@ -0,0 +1,5 @@
+pub mod address;
+pub mod entry;
+mod extrinsic;
+mod scale;
+mod signer;
@@ -0,0 +1,124 @@
+/// Append the SCALE compact encoding of `value`
+pub fn encode_compact(buf: &mut Vec<u8>, value: u128) {
+    match value {
+        0..=0x3f => buf.push((value as u8) << 2),
+        0x40..=0x3fff => buf.extend_from_slice(&(((value as u16) << 2) | 0b01).to_le_bytes()),
+        0x4000..=0x3fff_ffff => {
+            buf.extend_from_slice(&(((value as u32) << 2) | 0b10).to_le_bytes())
+        }
+        _ => {
+            let bytes = value.to_le_bytes();
+            let len = 16 - value.leading_zeros() as usize / 8;
+            buf.push((((len - 4) as u8) << 2) | 0b11);
+            buf.extend_from_slice(&bytes[..len]);
+        }
+    }
+}
+
+// Bounds of the period of a mortal era
+const MIN_PERIOD: u64 = 4;
+const MAX_PERIOD: u64 = 1 << 16;
+
+/// The range of blocks in which a transaction is valid
+#[derive(Debug, PartialEq)]
+pub enum Era {
+    Immortal,
+    Mortal { period: u64, phase: u64 },
+}
+
+impl Era {
+    /// A mortal era starting from `current` block, `period` is rounded up to a power of two
+    pub fn mortal(period: u64, current: u64) -> Self {
+        let period = period
+            .checked_next_power_of_two()
+            .unwrap_or(MAX_PERIOD)
+            .clamp(MIN_PERIOD, MAX_PERIOD);
+        let phase = current % period;
+        let quantize_factor = (period >> 12).max(1);
+        Era::Mortal {
+            period,
+            phase: phase / quantize_factor * quantize_factor,
+        }
+    }
+
+    pub fn encode(&self, buf: &mut Vec<u8>) {
+        match self {
+            Era::Immortal => buf.push(0),
+            Era::Mortal { period, phase } => {
+                let quantize_factor = (period >> 12).max(1);
+                let encoded = (period.trailing_zeros() - 1).clamp(1, 15) as u16
+                    | ((phase / quantize_factor) << 4) as u16;
+                buf.extend_from_slice(&encoded.to_le_bytes());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_compact() {
+        let cases: [(u128, &str); 9] = [
+            (0, "00"),
+            (1, "04"),
+            (63, "fc"),
+            (64, "0101"),
+            (16383, "fdff"),
+            (16384, "02000100"),
+            (1073741823, "feffffff"),
+            (1073741824, "0300000040"),
+            (u128::MAX, "33ffffffffffffffffffffffffffffffff"),
+        ];
+        for (value, expected) in cases.iter() {
+            let mut buf = vec![];
+            encode_compact(&mut buf, *value);
+            assert_eq!(hex::encode(buf), *expected);
+        }
+    }
+
+    #[test]
+    fn test_era() {
+        assert_eq!(
+            Era::mortal(64, 42),
+            Era::Mortal {
+                period: 64,
+                phase: 42
+            }
+        );
+        assert_eq!(
+            Era::mortal(200, 513),
+            Era::Mortal {
+                period: 256,
+                phase: 1
+            }
+        );
+        assert_eq!(
+            Era::mortal(2, 1),
+            Era::Mortal {
+                period: 4,
+                phase: 1
+            }
+        );
+        assert_eq!(
+            Era::mortal(1_000_000, 1_000_001),
+            Era::Mortal {
+                period: 65536,
+                phase: 1_000_001 % 65536 / 4 * 4
+            }
+        );
+
+        let mut buf = vec![];
+        Era::Immortal.encode(&mut buf);
+        assert_eq!(buf, vec![0]);
+
+        let mut buf = vec![];
+        Era::mortal(64, 42).encode(&mut buf);
+        assert_eq!(buf, vec![5 + 42 % 16 * 16, 42 / 16]);
+
+        let mut buf = vec![];
+        Era::mortal(32768, 20_000).encode(&mut buf);
+        assert_eq!(buf, vec![(14 + 2500 % 16 * 16) as u8, (2500 / 16) as u8]);
+    }
+}
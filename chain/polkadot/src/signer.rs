@@ -0,0 +1,233 @@
+use super::extrinsic::{Extrinsic, SignatureType};
+use chain_common::polkadot::{SignInput, SignOutput};
+use chain_common::private_key::PrivateKey;
+use chain_common::Error;
+use crypto::public_key::PublicKeyType;
+use crypto::sr25519;
+use ed25519_dalek::{Keypair, PublicKey, SecretKey, Signer as _};
+
+pub struct Signer;
+
+impl Signer {
+    pub fn sign(
+        private_key: &PrivateKey,
+        public_key_type: &PublicKeyType,
+        network: u16,
+        sign_input: &SignInput,
+    ) -> Result<SignOutput, Error> {
+        let extrinsic = Extrinsic::new(sign_input, network)?;
+        let payload = extrinsic.signing_payload();
+        let (public_key, signature_type, signature) = match public_key_type {
+            PublicKeyType::Ed25519 => {
+                let secret = SecretKey::from_bytes(&private_key.data)
+                    .map_err(|_| Error::InvalidPrivateKey)?;
+                let public = PublicKey::from(&secret);
+                let keypair = Keypair { secret, public };
+                let signature = keypair.sign(&payload).to_bytes().to_vec();
+                (
+                    public.to_bytes().to_vec(),
+                    SignatureType::Ed25519,
+                    signature,
+                )
+            }
+            PublicKeyType::Sr25519 => {
+                let public = sr25519::get_public_key(&private_key.data, &private_key.extends_data)
+                    .map_err(|_| Error::InvalidPrivateKey)?;
+                let signature =
+                    sr25519::sign(&private_key.data, &private_key.extends_data, &payload)
+                        .map_err(|_| Error::InvalidPrivateKey)?;
+                (public, SignatureType::Sr25519, signature)
+            }
+            _ => return Err(Error::InvalidPrivateKey),
+        };
+        Ok(SignOutput {
+            encoded: extrinsic.encode_signed(&public_key, signature_type, &signature),
+            signature,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::address::POLKADOT_NETWORK;
+    use chain_common::polkadot::{sign_input::MessageOneof, BalanceTransfer, Era};
+
+    const GENESIS_HASH: &str = "91b171bb158e2d3848fa23a9f1c25182fb8e20313b2c1eb49219da7a70ce90c3";
+
+    fn private_key() -> PrivateKey {
+        let data = hex::decode("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60")
+            .unwrap();
+        PrivateKey::new(&data).unwrap()
+    }
+
+    fn transfer(to_address: &str, value: &str) -> Option<MessageOneof> {
+        Some(MessageOneof::BalanceTransfer(BalanceTransfer {
+            to_address: to_address.to_owned(),
+            value: value.to_owned(),
+            call_indices: vec![],
+        }))
+    }
+
+    #[test]
+    fn test_sign_mortal_transfer() {
+        let input = SignInput {
+            block_hash: hex::decode(
+                "343a3f4258fd92f5ca6ca5abdf473d86a78b0bcd0dc09c568ca594245cc8c642",
+            )
+            .unwrap(),
+            genesis_hash: hex::decode(GENESIS_HASH).unwrap(),
+            nonce: 3,
+            spec_version: 9150,
+            transaction_version: 9,
+            tip: "".to_owned(),
+            era: Some(Era {
+                block_number: 7391123,
+                period: 64,
+            }),
+            message_oneof: transfer(
+                "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5",
+                "2540be400",
+            ),
+        };
+        let output = Signer::sign(
+            &private_key(),
+            &PublicKeyType::Ed25519,
+            POLKADOT_NETWORK,
+            &input,
+        )
+        .unwrap();
+        assert_eq!(hex::encode(&output.signature), "183af4a5bf31135b668027cfda27ad6bb8c89bb6fbb63b96438953d933ebf55759ce15bfc66602a1f8bfbcded1f21238e9ff69f432e69f169b0949399d887501");
+        assert_eq!(hex::encode(&output.encoded), "41028400d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a00183af4a5bf31135b668027cfda27ad6bb8c89bb6fbb63b96438953d933ebf55759ce15bfc66602a1f8bfbcded1f21238e9ff69f432e69f169b0949399d88750135010c00050000d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d0700e40b5402");
+    }
+
+    #[test]
+    fn test_sign_immortal_transfer() {
+        // The genesis hash is used as the block hash of an immortal transaction
+        let input = SignInput {
+            block_hash: vec![],
+            genesis_hash: hex::decode(GENESIS_HASH).unwrap(),
+            nonce: 0,
+            spec_version: 9150,
+            transaction_version: 9,
+            tip: "10".to_owned(),
+            era: None,
+            message_oneof: transfer("15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5", "3039"),
+        };
+        let output = Signer::sign(
+            &private_key(),
+            &PublicKeyType::Ed25519,
+            POLKADOT_NETWORK,
+            &input,
+        )
+        .unwrap();
+        assert_eq!(hex::encode(&output.encoded), "2d028400d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a00c875f0a15c0205614e6a5f50649cdbeb2141ad47903a5676df6f9208b3f692b054645f8571982d06318476c2eff4a7cdbe017158133d7c1c50f65eb8482d8601000040050000d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27de5c0");
+    }
+
+    #[test]
+    fn test_sign_sr25519() {
+        // Seed of `//Alice`
+        let seed = hex::decode("e5be9a5092b81bca64be81d212e7f2f9eba183bb7a90954f7b76361f6edb5c0a")
+            .unwrap();
+        let private_key = PrivateKey::new(&seed).unwrap();
+        let input = SignInput {
+            block_hash: hex::decode(
+                "343a3f4258fd92f5ca6ca5abdf473d86a78b0bcd0dc09c568ca594245cc8c642",
+            )
+            .unwrap(),
+            genesis_hash: hex::decode(GENESIS_HASH).unwrap(),
+            nonce: 3,
+            spec_version: 9150,
+            transaction_version: 9,
+            tip: "".to_owned(),
+            era: Some(Era {
+                block_number: 7391123,
+                period: 64,
+            }),
+            message_oneof: transfer(
+                "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5",
+                "2540be400",
+            ),
+        };
+        let output = Signer::sign(
+            &private_key,
+            &PublicKeyType::Sr25519,
+            POLKADOT_NETWORK,
+            &input,
+        )
+        .unwrap();
+
+        // sr25519 signatures are randomized, so only the signature is checked
+        let payload = Extrinsic::new(&input, POLKADOT_NETWORK)
+            .unwrap()
+            .signing_payload();
+        let public_key =
+            hex::decode("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d")
+                .unwrap();
+        assert!(sr25519::verify(&public_key, &payload, &output.signature));
+        let expected = format!("41028400d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d01{}35010c00050000d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d0700e40b5402", hex::encode(&output.signature));
+        assert_eq!(hex::encode(&output.encoded), expected);
+    }
+
+    #[test]
+    fn test_sign_invalid_input() {
+        let valid_input = SignInput {
+            block_hash: vec![],
+            genesis_hash: hex::decode(GENESIS_HASH).unwrap(),
+            nonce: 0,
+            spec_version: 9150,
+            transaction_version: 9,
+            tip: "".to_owned(),
+            era: None,
+            message_oneof: transfer("15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5", "1"),
+        };
+        assert!(Signer::sign(
+            &private_key(),
+            &PublicKeyType::Ed25519,
+            POLKADOT_NETWORK,
+            &valid_input
+        )
+        .is_ok());
+
+        // Address of another network
+        let input = SignInput {
+            message_oneof: transfer("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY", "1"),
+            ..valid_input.clone()
+        };
+        let sign_error = Signer::sign(
+            &private_key(),
+            &PublicKeyType::Ed25519,
+            POLKADOT_NETWORK,
+            &input,
+        );
+        assert_eq!(sign_error.err().unwrap(), Error::InvalidSignInput);
+
+        // Mortal era without the hash of its starting block
+        let input = SignInput {
+            era: Some(Era {
+                block_number: 7391123,
+                period: 64,
+            }),
+            ..valid_input.clone()
+        };
+        let sign_error = Signer::sign(
+            &private_key(),
+            &PublicKeyType::Ed25519,
+            POLKADOT_NETWORK,
+            &input,
+        );
+        assert_eq!(sign_error.err().unwrap(), Error::InvalidSignInput);
+
+        let input = SignInput {
+            message_oneof: None,
+            ..valid_input
+        };
+        let sign_error = Signer::sign(
+            &private_key(),
+            &PublicKeyType::Ed25519,
+            POLKADOT_NETWORK,
+            &input,
+        );
+        assert_eq!(sign_error.err().unwrap(), Error::InvalidSignInput);
+    }
+}
@@ -1,9 +1,13 @@
 use super::address::SolanaAddress;
+use super::signer::Signer;
+use bytes::BytesMut;
 use chain_common::coin::Coin;
 use chain_common::entry::{ChainExportType, ChainImportType, Entry};
 use chain_common::private_key::PrivateKey;
 use chain_common::public_key::PublicKey;
+use chain_common::solana::SignInput;
 use crypto::Error;
+use prost::Message;
 
 pub struct SolanaEntry;
 
@@ -37,18 +41,17 @@ impl Entry for SolanaEntry {
         private_key: &PrivateKey,
         payload: &[u8],
     ) -> Result<Vec<u8>, Error> {
-        Ok(vec![])
-        // let sign_input: SignInput = match SignInput::decode(payload) {
-        //     Ok(request) => request,
-        //     Err(_) => return Err(Error::InvalidPrivateKey),
-        // };
-        // let output =
-        //     Signer::sign(&private_key, &sign_input).map_err(|_| Error::InvalidPrivateKey)?;
+        let sign_input: SignInput = match SignInput::decode(payload) {
+            Ok(request) => request,
+            Err(_) => return Err(Error::InvalidPrivateKey),
+        };
+        let output =
+            Signer::sign(private_key, &sign_input).map_err(|_| Error::InvalidPrivateKey)?;
 
-        // let mut buf = BytesMut::with_capacity(output.encoded_len());
-        // output
-        //     .encode(&mut buf)
-        //     .expect("Fail to encode the SignOutput");
-        // Ok(buf.to_vec())
+        let mut buf = BytesMut::with_capacity(output.encoded_len());
+        output
+            .encode(&mut buf)
+            .expect("Fail to encode the SignOutput");
+        Ok(buf.to_vec())
     }
 }
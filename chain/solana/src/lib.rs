@@ -1,2 +1,4 @@
 pub mod address;
 pub mod entry;
+mod signer;
+mod transaction;
@@ -0,0 +1,133 @@
+use super::transaction::{decode_pubkey, Message, Transaction};
+use bitcoin::util::base58;
+use chain_common::private_key::PrivateKey;
+use chain_common::solana::{SignInput, SignOutput};
+use chain_common::Error;
+use ed25519_dalek::{Keypair, PublicKey, SecretKey, Signer as _};
+
+pub struct Signer;
+
+impl Signer {
+    pub fn sign(private_key: &PrivateKey, sign_input: &SignInput) -> Result<SignOutput, Error> {
+        let secret =
+            SecretKey::from_bytes(&private_key.data).map_err(|_| Error::InvalidPrivateKey)?;
+        let public = PublicKey::from(&secret);
+        let keypair = Keypair { secret, public };
+
+        let signer = public.to_bytes();
+        let fee_payer = match sign_input.fee_payer.is_empty() {
+            true => signer,
+            false => decode_pubkey(&sign_input.fee_payer)?,
+        };
+        let message = Message::new(sign_input, &fee_payer)?;
+        let signer_index = message
+            .signer_index(&signer)
+            .ok_or(Error::InvalidSignInput)?;
+        let signature = keypair.sign(&message.serialize()).to_bytes();
+
+        let mut transaction = Transaction::new_unsigned(message);
+        transaction.signatures[signer_index] = signature;
+        Ok(SignOutput {
+            encoded: base58::encode_slice(&transaction.serialize()),
+            signature: base58::encode_slice(&signature),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chain_common::solana::{AccountMeta, Instruction};
+
+    const SYSTEM_PROGRAM_ID: &str = "11111111111111111111111111111111";
+
+    fn transfer_instruction(from: &str, to: &str, lamports: u64) -> Instruction {
+        let mut data = 2u32.to_le_bytes().to_vec();
+        data.extend_from_slice(&lamports.to_le_bytes());
+        Instruction {
+            program_id: SYSTEM_PROGRAM_ID.to_owned(),
+            accounts: vec![
+                AccountMeta {
+                    pubkey: from.to_owned(),
+                    is_signer: true,
+                    is_writable: true,
+                },
+                AccountMeta {
+                    pubkey: to.to_owned(),
+                    is_signer: false,
+                    is_writable: true,
+                },
+            ],
+            data,
+        }
+    }
+
+    fn private_key() -> PrivateKey {
+        let data = base58::from("A7psj2GW7ZMdY4E5hJq14KMeYg7HFjULSsWSrTXZLvYr").unwrap();
+        PrivateKey::new(&data).unwrap()
+    }
+
+    #[test]
+    fn test_sign_transfer() {
+        let from = "7v91N7iZ9mNicL8WfG6cgSCKyRXydQjLh6UYBWwm6y1Q";
+        let input = SignInput {
+            recent_blockhash: "9ipJh5xfyoyDaiq8trtrdqQeAhQbQkWy2eANizKvx75K".to_owned(),
+            fee_payer: "".to_owned(),
+            instructions: vec![transfer_instruction(
+                from,
+                "EN2sCsJ1WDV8UFqsiTXHcUPUxQ4juE71eCknHYYMifkd",
+                42,
+            )],
+        };
+        let output = Signer::sign(&private_key(), &input).unwrap();
+        assert_eq!(
+            output.signature,
+            "51BWu63u4WqRSBWTvn1fvZwVwB6dLjVo8Jtn9rJW9s22onoDJFtrVwnZqrsDx3LPZKQV4sBumhpjnLC7NrrRuY8T"
+        );
+        assert_eq!(output.encoded, "5wFUmDXidiB7sibk2PsiNtKbB1aXWHb2nzDkKhuiGzMCwQgzAMqistTuTsQxmDrkMWXabDgeLgMpTGVteAafkqwm3ztqy2XnDAnjLDEAffJkCm2n2WrmSN919qZnsozkKqpx5wir7ZS5KxUEWJkqLQo3esu7QedpHeaFRP1bSYH2k9LxDtwo9fDFiNyAdmJRhunnvf4bjCQTGWTAFiabCcoF3Wc4VsqeS9yEmAfuEn4VfC1n3Xmx9fKxN27tdZhsz6NzBsXTZr9cUXEx41usJijsVfBsHzgGLQWVm");
+    }
+
+    #[test]
+    fn test_sign_with_other_fee_payer() {
+        let from = "7v91N7iZ9mNicL8WfG6cgSCKyRXydQjLh6UYBWwm6y1Q";
+        let memo = Instruction {
+            program_id: "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr".to_owned(),
+            accounts: vec![AccountMeta {
+                pubkey: from.to_owned(),
+                is_signer: true,
+                is_writable: false,
+            }],
+            data: "mask".as_bytes().to_vec(),
+        };
+        let input = SignInput {
+            recent_blockhash: "9ipJh5xfyoyDaiq8trtrdqQeAhQbQkWy2eANizKvx75K".to_owned(),
+            fee_payer: "Bxp8yhH9zNwxyE4UqxP7a7hgJ5xTZfxNNft7YJJ2VRjT".to_owned(),
+            instructions: vec![
+                transfer_instruction(from, "EN2sCsJ1WDV8UFqsiTXHcUPUxQ4juE71eCknHYYMifkd", 1000),
+                memo,
+            ],
+        };
+        // The fee payer signature is left empty for the fee payer to fill in
+        let output = Signer::sign(&private_key(), &input).unwrap();
+        assert_eq!(
+            output.signature,
+            "2CqafEqstcwqocXEg4Z8seDrywh6ABCq5LZ95o3afHHqYYdL67efP9tLW1tYZQt5k6nBQ6tT5RxCtHcrcrBMGmUW"
+        );
+        assert_eq!(output.encoded, "2r7t15ahSQsV5agVfAciNXYSLDa2K36E5Lq9mcqNR4UXc5YtSV93oDcg6VMT4fyW72EdSiADHvnhHUaaVVHgRbQExXq2bHzgxCo2ZdHpnoVKvn8UdYDgh8kBoZBLTuc5jAYXc9XXSobG19SNUJCWYRAk9odcHoHDPQXpXLtFbNEZ21q1iaDYveAdHgZG2Uq9oZT3EvyuX7uqrx4e62AaEfUMWGnhLa2CvQ8rBuSPiBhiHcicXexTXh3A7wNSm3PD5vAw4TPXkmL5dxG4ZMaEqBH6zoDNZWV4gLuWN83D6TT62My8SYhd83ArJQuyru1s9ubusfDWorAn4wYm4Fhv5EGgUBymhed3iq7enssiCv2g4Hrn5Y9Rg8se9Qx3itYhacM59yhVdQVjBTaXNjwQFPNsudGyUG2WKdeBJT3r1xAx2HMCLtUZ4oe3cJZrZcoRi6gcWCaCSuabwqfqQnUyGNi7ZiET4RG");
+    }
+
+    #[test]
+    fn test_sign_without_being_signer() {
+        let input = SignInput {
+            recent_blockhash: "9ipJh5xfyoyDaiq8trtrdqQeAhQbQkWy2eANizKvx75K".to_owned(),
+            fee_payer: "Bxp8yhH9zNwxyE4UqxP7a7hgJ5xTZfxNNft7YJJ2VRjT".to_owned(),
+            instructions: vec![transfer_instruction(
+                "Bxp8yhH9zNwxyE4UqxP7a7hgJ5xTZfxNNft7YJJ2VRjT",
+                "EN2sCsJ1WDV8UFqsiTXHcUPUxQ4juE71eCknHYYMifkd",
+                42,
+            )],
+        };
+        let sign_error = Signer::sign(&private_key(), &input);
+        assert_eq!(sign_error.err().unwrap(), Error::InvalidSignInput);
+    }
+}
@@ -0,0 +1,244 @@
+use bitcoin::util::base58;
+use chain_common::solana::SignInput;
+use chain_common::Error;
+use std::convert::TryInto;
+
+const PUBKEY_SIZE: usize = 32;
+const SIGNATURE_SIZE: usize = 64;
+// Account indices are encoded as a single byte in compiled instructions
+const MAX_ACCOUNT_KEYS: usize = 256;
+
+pub type Pubkey = [u8; PUBKEY_SIZE];
+
+pub fn decode_pubkey(address: &str) -> Result<Pubkey, Error> {
+    let data = base58::from(address).map_err(|_| Error::InvalidSignInput)?;
+    data.try_into().map_err(|_| Error::InvalidSignInput)
+}
+
+/// Append the compact-u16 ("shortvec") encoding of `len` used by Solana for array lengths
+fn encode_length(buf: &mut Vec<u8>, len: usize) {
+    let mut rem = len as u16;
+    loop {
+        let mut elem = (rem & 0x7f) as u8;
+        rem >>= 7;
+        if rem == 0 {
+            buf.push(elem);
+            break;
+        }
+        elem |= 0x80;
+        buf.push(elem);
+    }
+}
+
+#[derive(Clone)]
+struct AccountMeta {
+    pubkey: Pubkey,
+    is_signer: bool,
+    is_writable: bool,
+}
+
+pub struct MessageHeader {
+    /// The number of signatures required for the transaction to be valid
+    pub num_required_signatures: u8,
+
+    /// The number of signed accounts which are read-only
+    pub num_readonly_signed_accounts: u8,
+
+    /// The number of unsigned accounts which are read-only
+    pub num_readonly_unsigned_accounts: u8,
+}
+
+pub struct CompiledInstruction {
+    pub program_id_index: u8,
+    pub accounts: Vec<u8>,
+    pub data: Vec<u8>,
+}
+
+/// A legacy Solana transaction message
+pub struct Message {
+    pub header: MessageHeader,
+    pub account_keys: Vec<Pubkey>,
+    pub recent_blockhash: [u8; 32],
+    pub instructions: Vec<CompiledInstruction>,
+}
+
+impl Message {
+    pub fn new(input: &SignInput, fee_payer: &Pubkey) -> Result<Self, Error> {
+        if input.instructions.is_empty() {
+            return Err(Error::InvalidSignInput);
+        }
+        let recent_blockhash = decode_pubkey(&input.recent_blockhash)?;
+
+        // Collect every referenced account, the fee payer always comes first
+        let mut metas = vec![AccountMeta {
+            pubkey: *fee_payer,
+            is_signer: true,
+            is_writable: true,
+        }];
+        for instruction in &input.instructions {
+            for account in &instruction.accounts {
+                metas.push(AccountMeta {
+                    pubkey: decode_pubkey(&account.pubkey)?,
+                    is_signer: account.is_signer,
+                    is_writable: account.is_writable,
+                });
+            }
+        }
+        for instruction in &input.instructions {
+            metas.push(AccountMeta {
+                pubkey: decode_pubkey(&instruction.program_id)?,
+                is_signer: false,
+                is_writable: false,
+            });
+        }
+
+        // Merge duplicated accounts, keeping the most privileged flags
+        let mut unique_metas: Vec<AccountMeta> = vec![];
+        for meta in metas {
+            match unique_metas.iter_mut().find(|x| x.pubkey == meta.pubkey) {
+                Some(existing) => {
+                    existing.is_signer |= meta.is_signer;
+                    existing.is_writable |= meta.is_writable;
+                }
+                None => unique_metas.push(meta),
+            }
+        }
+        if unique_metas.len() > MAX_ACCOUNT_KEYS {
+            return Err(Error::InvalidSignInput);
+        }
+
+        // Order the accounts as writable signers, read-only signers, writable non-signers
+        // and read-only non-signers, which is what the message header describes
+        let (signed, unsigned): (Vec<AccountMeta>, Vec<AccountMeta>) =
+            unique_metas.into_iter().partition(|x| x.is_signer);
+        let (writable_signed, readonly_signed): (Vec<AccountMeta>, Vec<AccountMeta>) =
+            signed.into_iter().partition(|x| x.is_writable);
+        let (writable_unsigned, readonly_unsigned): (Vec<AccountMeta>, Vec<AccountMeta>) =
+            unsigned.into_iter().partition(|x| x.is_writable);
+
+        let header = MessageHeader {
+            num_required_signatures: (writable_signed.len() + readonly_signed.len()) as u8,
+            num_readonly_signed_accounts: readonly_signed.len() as u8,
+            num_readonly_unsigned_accounts: readonly_unsigned.len() as u8,
+        };
+        let account_keys: Vec<Pubkey> = writable_signed
+            .iter()
+            .chain(readonly_signed.iter())
+            .chain(writable_unsigned.iter())
+            .chain(readonly_unsigned.iter())
+            .map(|x| x.pubkey)
+            .collect();
+
+        let position = |pubkey: &Pubkey| -> Result<u8, Error> {
+            account_keys
+                .iter()
+                .position(|x| x == pubkey)
+                .map(|x| x as u8)
+                .ok_or(Error::InvalidSignInput)
+        };
+        let mut instructions = vec![];
+        for instruction in &input.instructions {
+            let mut accounts = vec![];
+            for account in &instruction.accounts {
+                accounts.push(position(&decode_pubkey(&account.pubkey)?)?);
+            }
+            instructions.push(CompiledInstruction {
+                program_id_index: position(&decode_pubkey(&instruction.program_id)?)?,
+                accounts,
+                data: instruction.data.to_vec(),
+            });
+        }
+
+        Ok(Message {
+            header,
+            account_keys,
+            recent_blockhash,
+            instructions,
+        })
+    }
+
+    /// The position of `pubkey` in the signature list, if it is a required signer
+    pub fn signer_index(&self, pubkey: &Pubkey) -> Option<usize> {
+        self.account_keys
+            .iter()
+            .take(self.header.num_required_signatures as usize)
+            .position(|x| x == pubkey)
+    }
+
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut buf = vec![
+            self.header.num_required_signatures,
+            self.header.num_readonly_signed_accounts,
+            self.header.num_readonly_unsigned_accounts,
+        ];
+        encode_length(&mut buf, self.account_keys.len());
+        for key in &self.account_keys {
+            buf.extend_from_slice(key);
+        }
+        buf.extend_from_slice(&self.recent_blockhash);
+        encode_length(&mut buf, self.instructions.len());
+        for instruction in &self.instructions {
+            buf.push(instruction.program_id_index);
+            encode_length(&mut buf, instruction.accounts.len());
+            buf.extend_from_slice(&instruction.accounts);
+            encode_length(&mut buf, instruction.data.len());
+            buf.extend_from_slice(&instruction.data);
+        }
+        buf
+    }
+}
+
+pub struct Transaction {
+    pub signatures: Vec<[u8; SIGNATURE_SIZE]>,
+    pub message: Message,
+}
+
+impl Transaction {
+    pub fn new_unsigned(message: Message) -> Self {
+        let signatures =
+            vec![[0u8; SIGNATURE_SIZE]; message.header.num_required_signatures as usize];
+        Transaction {
+            signatures,
+            message,
+        }
+    }
+
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut buf = vec![];
+        encode_length(&mut buf, self.signatures.len());
+        for signature in &self.signatures {
+            buf.extend_from_slice(signature);
+        }
+        buf.extend_from_slice(&self.message.serialize());
+        buf
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_length() {
+        let cases: [(usize, Vec<u8>); 6] = [
+            (0x0, vec![0x0]),
+            (0x7f, vec![0x7f]),
+            (0x80, vec![0x80, 0x01]),
+            (0xff, vec![0xff, 0x01]),
+            (0x3fff, vec![0xff, 0x7f]),
+            (0x4000, vec![0x80, 0x80, 0x01]),
+        ];
+        for (len, expected) in cases.iter() {
+            let mut buf = vec![];
+            encode_length(&mut buf, *len);
+            assert_eq!(&buf, expected);
+        }
+    }
+
+    #[test]
+    fn test_decode_pubkey() {
+        assert!(decode_pubkey("11111111111111111111111111111111").is_ok());
+        assert!(decode_pubkey("2gVkYWexTHR5Hb2aLeQN3tnngvWzisFKXDUPrgMHpd").is_err());
+        assert!(decode_pubkey("0OIl").is_err());
+    }
+}
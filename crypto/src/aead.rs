@@ -0,0 +1,185 @@
+use crate::Error;
+use aes_gcm::aead::{Aead, NewAead, Payload};
+use aes_gcm::Aes256Gcm;
+use chacha20poly1305::XChaCha20Poly1305;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::string::ToString;
+
+pub const KEY_LEN: usize = 32;
+
+/// Authenticated ciphers with 256-bit keys, the encrypted data is followed by a 16-byte tag
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy, Debug)]
+pub enum AeadType {
+    Aes256Gcm,
+    XChaCha20Poly1305,
+}
+
+impl AeadType {
+    pub fn nonce_len(&self) -> usize {
+        match self {
+            Self::Aes256Gcm => 12,
+            Self::XChaCha20Poly1305 => 24,
+        }
+    }
+}
+
+impl FromStr for AeadType {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Error> {
+        match s.to_lowercase().as_str() {
+            "aes-256-gcm" => Ok(Self::Aes256Gcm),
+            "xchacha20-poly1305" => Ok(Self::XChaCha20Poly1305),
+            _ => Err(Error::NotSupportedCipher),
+        }
+    }
+}
+
+impl ToString for AeadType {
+    fn to_string(&self) -> String {
+        match self {
+            Self::Aes256Gcm => "aes-256-gcm".to_owned(),
+            Self::XChaCha20Poly1305 => "xchacha20-poly1305".to_owned(),
+        }
+    }
+}
+
+fn check_key_nonce(aead_type: AeadType, key: &[u8], nonce: &[u8]) -> Result<(), Error> {
+    if key.len() != KEY_LEN || nonce.len() != aead_type.nonce_len() {
+        return Err(Error::InvalidKeyIvLength);
+    }
+    Ok(())
+}
+
+/// Encrypt `data` and authenticate it together with `associated_data`
+pub fn encrypt(
+    aead_type: AeadType,
+    key: &[u8],
+    nonce: &[u8],
+    data: &[u8],
+    associated_data: &[u8],
+) -> Result<Vec<u8>, Error> {
+    check_key_nonce(aead_type, key, nonce)?;
+    let payload = Payload {
+        msg: data,
+        aad: associated_data,
+    };
+    let encrypted = match aead_type {
+        AeadType::Aes256Gcm => Aes256Gcm::new(key.into()).encrypt(nonce.into(), payload),
+        AeadType::XChaCha20Poly1305 => {
+            XChaCha20Poly1305::new(key.into()).encrypt(nonce.into(), payload)
+        }
+    };
+    encrypted.map_err(|_| Error::InvalidCiphertext)
+}
+
+/// Decrypt `data`, fails with `PasswordIncorrect` if the key is wrong or either `data` or
+/// `associated_data` has been tampered with
+pub fn decrypt(
+    aead_type: AeadType,
+    key: &[u8],
+    nonce: &[u8],
+    data: &[u8],
+    associated_data: &[u8],
+) -> Result<Vec<u8>, Error> {
+    check_key_nonce(aead_type, key, nonce)?;
+    let payload = Payload {
+        msg: data,
+        aad: associated_data,
+    };
+    let decrypted = match aead_type {
+        AeadType::Aes256Gcm => Aes256Gcm::new(key.into()).decrypt(nonce.into(), payload),
+        AeadType::XChaCha20Poly1305 => {
+            XChaCha20Poly1305::new(key.into()).decrypt(nonce.into(), payload)
+        }
+    };
+    decrypted.map_err(|_| Error::PasswordIncorrect)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aes_256_gcm() {
+        let key = [1u8; KEY_LEN];
+        let nonce = [2u8; 12];
+        let encrypted = encrypt(
+            AeadType::Aes256Gcm,
+            &key,
+            &nonce,
+            b"MaskWallet",
+            b"metadata",
+        )
+        .unwrap();
+        assert_eq!(
+            hex::encode(&encrypted),
+            "4ab7ba221d36ad91b6b88d58d724d2a9735f1145bd1a263f1a41"
+        );
+        assert_eq!(
+            decrypt(AeadType::Aes256Gcm, &key, &nonce, &encrypted, b"metadata").unwrap(),
+            b"MaskWallet"
+        );
+        assert_eq!(
+            decrypt(AeadType::Aes256Gcm, &key, &nonce, &encrypted, b"tampered").err(),
+            Some(Error::PasswordIncorrect)
+        );
+        assert_eq!(
+            decrypt(
+                AeadType::Aes256Gcm,
+                &key,
+                &[2u8; 24],
+                &encrypted,
+                b"metadata"
+            )
+            .err(),
+            Some(Error::InvalidKeyIvLength)
+        );
+    }
+
+    #[test]
+    fn test_xchacha20_poly1305() {
+        let key = [1u8; KEY_LEN];
+        let nonce = [2u8; 24];
+        let encrypted = encrypt(
+            AeadType::XChaCha20Poly1305,
+            &key,
+            &nonce,
+            b"MaskWallet",
+            b"metadata",
+        )
+        .unwrap();
+        assert_eq!(
+            hex::encode(&encrypted),
+            "81074584fd169021c5159839162f3703a80ee704dbe16bb750a4"
+        );
+        assert_eq!(
+            decrypt(
+                AeadType::XChaCha20Poly1305,
+                &key,
+                &nonce,
+                &encrypted,
+                b"metadata"
+            )
+            .unwrap(),
+            b"MaskWallet"
+        );
+        let mut tampered = encrypted.clone();
+        tampered[0] ^= 1;
+        assert_eq!(
+            decrypt(
+                AeadType::XChaCha20Poly1305,
+                &key,
+                &nonce,
+                &tampered,
+                b"metadata"
+            )
+            .err(),
+            Some(Error::PasswordIncorrect)
+        );
+        assert_eq!(
+            AeadType::from_str(&AeadType::XChaCha20Poly1305.to_string()).unwrap(),
+            AeadType::XChaCha20Poly1305
+        );
+    }
+}
@@ -3,12 +3,21 @@ use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 use std::string::ToString;
 
-#[derive(Serialize, Deserialize, PartialEq)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy)]
 pub enum AesType {
     Ctr(u32),
     Cbc(u32),
 }
 
+impl AesType {
+    /// The number of bits of the key
+    pub fn bits(&self) -> u32 {
+        match self {
+            Self::Ctr(bits) | Self::Cbc(bits) => *bits,
+        }
+    }
+}
+
 impl FromStr for AesType {
     type Err = Error;
     fn from_str(s: &str) -> Result<Self, Error> {
@@ -38,74 +47,82 @@ impl ToString for AesType {
     }
 }
 
+/// Key length in bytes of AES with `bits` bits keys
+pub fn key_len(bits: u32) -> Result<usize, Error> {
+    match bits {
+        128 | 192 | 256 => Ok(bits as usize / 8),
+        _ => Err(Error::NotSupportedCipher),
+    }
+}
+
 pub mod ctr {
     use aes::cipher::generic_array::GenericArray;
     use aes::cipher::{NewCipher, StreamCipher};
     use aes::{Aes128Ctr, Aes192Ctr, Aes256Ctr};
 
+    use super::key_len;
     use crate::Error;
 
     type CryptoResult<T> = Result<T, Error>;
 
     pub fn encrypt(data: &[u8], key: &[u8], iv: &[u8], bits: u32) -> CryptoResult<Vec<u8>> {
-        if key.len() != 16 || iv.len() != 16 {
+        apply_keystream(data, key, iv, bits)
+    }
+
+    pub fn decrypt(data: &[u8], key: &[u8], iv: &[u8], bits: u32) -> CryptoResult<Vec<u8>> {
+        apply_keystream(data, key, iv, bits)
+    }
+
+    fn apply_keystream(data: &[u8], key: &[u8], iv: &[u8], bits: u32) -> CryptoResult<Vec<u8>> {
+        if key.len() != key_len(bits)? || iv.len() != 16 {
             return Err(Error::InvalidKeyIvLength);
         }
-        if ![128, 192, 256].contains(&bits) {
-            return Err(Error::NotSupportedCipher);
-        }
-        let mut data_copy = vec![0; data.len()];
-        data_copy.copy_from_slice(data);
-
+        let mut data_copy = data.to_vec();
+        let iv = GenericArray::from_slice(iv);
         match bits {
             128 => {
-                let key = GenericArray::from_slice(key);
-                let iv = GenericArray::from_slice(iv);
-                Aes128Ctr::new(&key, &iv).apply_keystream(&mut data_copy)
+                Aes128Ctr::new(GenericArray::from_slice(key), iv).apply_keystream(&mut data_copy)
             }
             192 => {
-                let key = GenericArray::from_slice(key);
-                let iv = GenericArray::from_slice(iv);
-                Aes192Ctr::new(&key, &iv).apply_keystream(&mut data_copy)
+                Aes192Ctr::new(GenericArray::from_slice(key), iv).apply_keystream(&mut data_copy)
             }
             256 => {
-                let key = GenericArray::from_slice(key);
-                let iv = GenericArray::from_slice(iv);
-                Aes256Ctr::new(&key, &iv).apply_keystream(&mut data_copy)
+                Aes256Ctr::new(GenericArray::from_slice(key), iv).apply_keystream(&mut data_copy)
             }
             _ => return Err(Error::NotSupportedCipher),
         };
         Ok(data_copy)
     }
+}
+
+pub mod cbc {
+    use aes::{Aes128, Aes192, Aes256};
+    use block_modes::block_padding::Pkcs7;
+    use block_modes::{BlockMode, Cbc};
+
+    use super::key_len;
+    use crate::Error;
+
+    type CryptoResult<T> = Result<T, Error>;
 
+    /// Decrypt `data` and remove its PKCS#7 padding
     pub fn decrypt(data: &[u8], key: &[u8], iv: &[u8], bits: u32) -> CryptoResult<Vec<u8>> {
-        if key.len() != 16 || iv.len() != 16 {
+        if key.len() != key_len(bits)? || iv.len() != 16 {
             return Err(Error::InvalidKeyIvLength);
         }
-        if ![128, 192, 256].contains(&bits) {
-            return Err(Error::NotSupportedCipher);
-        }
-        let mut data_copy = vec![0; data.len()];
-        data_copy.copy_from_slice(data);
-        match bits {
-            128 => {
-                let key = GenericArray::from_slice(key);
-                let iv = GenericArray::from_slice(iv);
-                Aes128Ctr::new(&key, &iv).apply_keystream(&mut data_copy)
-            }
-            192 => {
-                let key = GenericArray::from_slice(key);
-                let iv = GenericArray::from_slice(iv);
-                Aes192Ctr::new(&key, &iv).apply_keystream(&mut data_copy)
-            }
-            256 => {
-                let key = GenericArray::from_slice(key);
-                let iv = GenericArray::from_slice(iv);
-                Aes256Ctr::new(&key, &iv).apply_keystream(&mut data_copy)
-            }
+        let decrypted = match bits {
+            128 => Cbc::<Aes128, Pkcs7>::new_from_slices(key, iv)
+                .map_err(|_| Error::InvalidKeyIvLength)?
+                .decrypt_vec(data),
+            192 => Cbc::<Aes192, Pkcs7>::new_from_slices(key, iv)
+                .map_err(|_| Error::InvalidKeyIvLength)?
+                .decrypt_vec(data),
+            256 => Cbc::<Aes256, Pkcs7>::new_from_slices(key, iv)
+                .map_err(|_| Error::InvalidKeyIvLength)?
+                .decrypt_vec(data),
             _ => return Err(Error::NotSupportedCipher),
         };
-        Ok(data_copy)
+        decrypted.map_err(|_| Error::InvalidCiphertext)
     }
 }
 
@@ -158,4 +175,66 @@ mod tests {
         let ret = decrypt(&data, &key, &wrong_len_iv, 128);
         assert!(ret.is_err());
     }
+
+    #[test]
+    fn ctr_key_length_test() {
+        use crate::aes::ctr::{decrypt, encrypt};
+
+        let data = "MaskWallet".as_bytes();
+        let iv = hex::decode("01020304010203040102030401020304").unwrap();
+        let key192 = hex::decode("0102030405060708090a0b0c0d0e0f101112131415161718").unwrap();
+        let ret = encrypt(data, &key192, &iv, 192).unwrap();
+        assert_eq!(hex::encode(&ret), "5143d910a32f25363a4a");
+        assert_eq!(decrypt(&ret, &key192, &iv, 192).unwrap(), data);
+
+        let key256 =
+            hex::decode("0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20")
+                .unwrap();
+        let ret = encrypt(data, &key256, &iv, 256).unwrap();
+        assert_eq!(hex::encode(&ret), "fc2e73df17e1df8874d8");
+        assert_eq!(decrypt(&ret, &key256, &iv, 256).unwrap(), data);
+
+        // The key length must match the cipher
+        assert!(encrypt(data, &key256, &iv, 128).is_err());
+        assert!(encrypt(data, &key192[..16], &iv, 192).is_err());
+        assert!(encrypt(data, &key256[..16], &iv, 512).is_err());
+    }
+
+    #[test]
+    fn cbc_decrypt_test() {
+        use crate::aes::cbc::decrypt;
+        use crate::Error;
+
+        let iv = hex::decode("01020304010203040102030401020304").unwrap();
+        let key128 = hex::decode("01020304010203040102030401020304").unwrap();
+        let encrypted = hex::decode("6f39f9cd2a72717c6ae03e10d505231f").unwrap();
+        assert_eq!(
+            decrypt(&encrypted, &key128, &iv, 128).unwrap(),
+            b"MaskWallet"
+        );
+
+        let key256 =
+            hex::decode("0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20")
+                .unwrap();
+        let encrypted =
+            hex::decode("885b759aa3d392d02aeb0b12c23564926aa61a051304db381bda96f8a217fd3e")
+                .unwrap();
+        assert_eq!(
+            decrypt(&encrypted, &key256, &iv, 256).unwrap(),
+            b"MaskWallet MaskWallet"
+        );
+
+        assert_eq!(
+            decrypt(&encrypted, &key128, &iv, 128).err(),
+            Some(Error::InvalidCiphertext)
+        );
+        assert_eq!(
+            decrypt(&encrypted[..20], &key256, &iv, 256).err(),
+            Some(Error::InvalidCiphertext)
+        );
+        assert_eq!(
+            decrypt(&encrypted, &key256, &iv, 128).err(),
+            Some(Error::InvalidKeyIvLength)
+        );
+    }
 }
@@ -0,0 +1,96 @@
+use argon2::{Algorithm, Argon2, Params, Version};
+use serde::{Deserialize, Serialize};
+
+use super::kdf_params::KdfParamsType;
+use super::number_util::random_iv;
+use crate::Error;
+
+// Recommended Argon2id costs of RFC 9106 for memory-constrained environments
+pub const DEFAULT_M_COST: u32 = 65536;
+pub const DEFAULT_T_COST: u32 = 3;
+pub const DEFAULT_P_COST: u32 = 4;
+// Derived keys are split into the encryption key and the MAC key, 16 bytes each
+const MIN_DKLEN: usize = 32;
+const MAX_DKLEN: usize = 64;
+
+/// Argon2id (version 0x13) parameters, `m_cost` is the memory size in KiB
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Argon2Params {
+    pub m_cost: u32,
+    pub t_cost: u32,
+    pub p_cost: u32,
+    pub dklen: usize,
+    pub salt: String,
+}
+
+impl Argon2Params {
+    pub fn new(m_cost: u32, t_cost: u32, p_cost: u32) -> Argon2Params {
+        Argon2Params {
+            m_cost,
+            t_cost,
+            p_cost,
+            dklen: 32,
+            salt: hex::encode(random_iv(32)),
+        }
+    }
+}
+
+impl Default for Argon2Params {
+    fn default() -> Argon2Params {
+        Self::new(DEFAULT_M_COST, DEFAULT_T_COST, DEFAULT_P_COST)
+    }
+}
+
+impl KdfParamsType for Argon2Params {
+    fn generate_derived_key(&self, password: &[u8]) -> Result<Vec<u8>, Error> {
+        if self.dklen < MIN_DKLEN || self.dklen > MAX_DKLEN {
+            return Err(Error::KdfParamsInvalid);
+        }
+        let params = Params::new(self.m_cost, self.t_cost, self.p_cost, Some(self.dklen))
+            .or(Err(Error::KdfParamsInvalid))?;
+        let salt_bytes = hex::decode(&self.salt).or(Err(Error::KdfParamsInvalid))?;
+        let mut output = vec![0u8; self.dklen];
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(password, &salt_bytes, &mut output)
+            .or(Err(Error::KdfParamsInvalid))?;
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_argon2id() {
+        // Argon2id test vector of the reference implementation
+        let params = Argon2Params {
+            m_cost: 65536,
+            t_cost: 2,
+            p_cost: 1,
+            dklen: 32,
+            salt: hex::encode("somesalt"),
+        };
+        assert_eq!(
+            hex::encode(params.generate_derived_key(b"password").unwrap()),
+            "09316115d5cf24ed5a15a31a3ba326e5cf32edc24702987c02b6566f61913cf7"
+        );
+
+        let invalid_cost = Argon2Params {
+            t_cost: 0,
+            ..params.clone()
+        };
+        assert_eq!(
+            invalid_cost.generate_derived_key(b"password").err(),
+            Some(Error::KdfParamsInvalid)
+        );
+        let short_salt = Argon2Params {
+            salt: hex::encode("salt"),
+            ..params
+        };
+        assert_eq!(
+            short_salt.generate_derived_key(b"password").err(),
+            Some(Error::KdfParamsInvalid)
+        );
+    }
+}
@@ -1,12 +1,74 @@
 use super::curve::Curve;
 use crate::Error;
+use bitcoin::util::base58;
 use bitcoin::util::bip32::{ChildNumber, DerivationPath, ExtendedPrivKey, ExtendedPubKey};
 use bitcoin::Network;
 use ed25519_dalek_bip32;
 use secp256k1::Secp256k1;
 use std::convert::TryInto;
 use std::str::FromStr;
+use zeroize::Zeroize;
 
+const EXTENDED_KEY_SIZE: usize = 78;
+
+/// SLIP-132 versions of serialized extended public keys, which select the network and the type
+/// of addresses
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ExtendedPublicKeyVersion {
+    Xpub,
+    Ypub,
+    Zpub,
+    Tpub,
+    Upub,
+    Vpub,
+}
+
+impl ExtendedPublicKeyVersion {
+    const ALL: [ExtendedPublicKeyVersion; 6] = [
+        Self::Xpub,
+        Self::Ypub,
+        Self::Zpub,
+        Self::Tpub,
+        Self::Upub,
+        Self::Vpub,
+    ];
+
+    /// The mainnet version of the addresses of the BIP `purpose`, xpub for any other purpose
+    pub fn from_purpose(purpose: u32) -> Self {
+        match purpose {
+            49 => Self::Ypub,
+            84 => Self::Zpub,
+            _ => Self::Xpub,
+        }
+    }
+
+    pub fn bytes(&self) -> [u8; 4] {
+        match self {
+            Self::Xpub => [0x04, 0x88, 0xb2, 0x1e],
+            Self::Ypub => [0x04, 0x9d, 0x7c, 0xb2],
+            Self::Zpub => [0x04, 0xb2, 0x47, 0x46],
+            Self::Tpub => [0x04, 0x35, 0x87, 0xcf],
+            Self::Upub => [0x04, 0x4a, 0x52, 0x62],
+            Self::Vpub => [0x04, 0x5f, 0x1c, 0xf6],
+        }
+    }
+
+    /// The BIP purpose of the addresses, 44 for P2PKH, 49 for P2WPKH nested in P2SH and 84 for
+    /// native P2WPKH
+    pub fn purpose(&self) -> u32 {
+        match self {
+            Self::Xpub | Self::Tpub => 44,
+            Self::Ypub | Self::Upub => 49,
+            Self::Zpub | Self::Vpub => 84,
+        }
+    }
+
+    pub fn is_testnet(&self) -> bool {
+        matches!(self, Self::Tpub | Self::Upub | Self::Vpub)
+    }
+}
+
+/// A derived key, the chain code and private key are wiped when it's dropped
 pub struct HdNode {
     pub depth: u8,
     pub child_num: u32,
@@ -17,6 +79,20 @@ pub struct HdNode {
     pub curve: Curve,
 }
 
+impl Zeroize for HdNode {
+    fn zeroize(&mut self) {
+        self.chain_code.zeroize();
+        self.private_key_bytes.zeroize();
+        self.private_key_extension.zeroize();
+    }
+}
+
+impl Drop for HdNode {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
 impl HdNode {
     pub fn new_from_extended_private_key(extended_priv_key: ExtendedPrivKey, curve: Curve) -> Self {
         let child_num = match extended_priv_key.child_number {
@@ -62,6 +138,8 @@ impl HdNode {
                     curve,
                 });
             }
+            // sr25519 keys are derived from the entropy, see `sr25519::get_node`
+            Curve::Sr25519 => Err(Error::NotSupportedCurve),
             _ => {
                 let extended_private_key = extended_master_key
                     .derive_priv(&Secp256k1::new(), &derivation_path)
@@ -71,34 +149,125 @@ impl HdNode {
                     curve,
                 ));
             }
-        };
+        }
+    }
+}
+
+/// A serialized secp256k1 extended public key of any SLIP-132 version, children derived from it
+/// keep the version
+pub struct ExtendedPublicKey {
+    key: ExtendedPubKey,
+    pub version: ExtendedPublicKeyVersion,
+}
+
+impl FromStr for ExtendedPublicKey {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut data = base58::from_check(s).map_err(|_| Error::InvalidPublicKey)?;
+        if data.len() != EXTENDED_KEY_SIZE {
+            return Err(Error::InvalidPublicKey);
+        }
+        let version = *ExtendedPublicKeyVersion::ALL
+            .iter()
+            .find(|version| data[..4] == version.bytes())
+            .ok_or(Error::InvalidPublicKey)?;
+        // The version only selects the network and type of addresses, the rest is the same as an
+        // xpub
+        data[..4].copy_from_slice(&ExtendedPublicKeyVersion::Xpub.bytes());
+        let key = ExtendedPubKey::decode(&data).map_err(|_| Error::InvalidPublicKey)?;
+        Ok(ExtendedPublicKey { key, version })
+    }
+}
+
+impl ToString for ExtendedPublicKey {
+    fn to_string(&self) -> String {
+        let mut data = self.key.encode();
+        data[..4].copy_from_slice(&self.version.bytes());
+        base58::check_encode_slice(&data)
+    }
+}
+
+impl ExtendedPublicKey {
+    /// The extended public key at `path` of the BIP32 master key of `seed`
+    pub fn new_from_seed(
+        seed: &[u8],
+        path: &str,
+        version: ExtendedPublicKeyVersion,
+    ) -> Result<ExtendedPublicKey, Error> {
+        let extended_master_key =
+            ExtendedPrivKey::new_master(Network::Bitcoin, seed).map_err(|_| Error::InvalidSeed)?;
+        let derivation_path =
+            DerivationPath::from_str(path).map_err(|_| Error::InvalidDerivationpath)?;
+        let secp = Secp256k1::new();
+        let extended_private_key = extended_master_key
+            .derive_priv(&secp, &derivation_path)
+            .map_err(|_| Error::InvalidSeed)?;
+        Ok(ExtendedPublicKey {
+            key: ExtendedPubKey::from_private(&secp, &extended_private_key),
+            version,
+        })
+    }
+
+    /// The BIP purpose implied by the version, e.g. 84 for a zpub
+    pub fn purpose(&self) -> u32 {
+        self.version.purpose()
+    }
+
+    /// The account index of a key at the hardened account level `m/purpose'/coin'/account'`
+    pub fn account(&self) -> Option<u32> {
+        match self.key.child_number {
+            ChildNumber::Hardened { index } if self.key.depth == 3 => Some(index),
+            _ => None,
+        }
+    }
+
+    pub fn depth(&self) -> u8 {
+        self.key.depth
+    }
+
+    /// The compressed public key
+    pub fn public_key(&self) -> Vec<u8> {
+        self.key.public_key.key.serialize().to_vec()
+    }
+
+    /// The extended public key at the non-hardened `path` relative to this key, e.g. `m/0/1`
+    pub fn derive(&self, path: &str) -> Result<ExtendedPublicKey, Error> {
+        let path = DerivationPath::from_str(path).map_err(|_| Error::InvalidDerivationpath)?;
+        let key = self
+            .key
+            .derive_pub(&Secp256k1::verification_only(), &path)
+            .map_err(|_| Error::InvalidDerivationpath)?;
+        Ok(ExtendedPublicKey { key, ..*self })
+    }
+
+    /// The compressed public key at the non-hardened `path` relative to this key
+    pub fn derive_public_key(&self, path: &str) -> Result<Vec<u8>, Error> {
+        Ok(self.derive(path)?.public_key())
     }
 }
 
-pub fn get_extended_public_key(seed: &[u8], path: &str) -> Result<String, Error> {
-    let extended_master_key =
-        ExtendedPrivKey::new_master(Network::Bitcoin, &seed).map_err(|_| Error::InvalidSeed)?;
-    let derivation_path =
-        DerivationPath::from_str(path).map_err(|_| Error::InvalidDerivationpath)?;
-    let extended_private_key = extended_master_key
-        .derive_priv(&Secp256k1::new(), &derivation_path)
-        .map_err(|_| Error::InvalidSeed)?;
-    let extended_public_key =
-        ExtendedPubKey::from_private(&Secp256k1::new(), &extended_private_key);
-    std::str::from_utf8(&extended_public_key.encode()[..])
-        .map(|x| x.to_owned())
-        .map_err(|_| Error::InvalidSeed)
+/// The serialized extended public key at `path` of the BIP32 master key of `seed`
+pub fn get_extended_public_key(
+    seed: &[u8],
+    path: &str,
+    version: ExtendedPublicKeyVersion,
+) -> Result<String, Error> {
+    Ok(ExtendedPublicKey::new_from_seed(seed, path, version)?.to_string())
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::bip32::HdNode;
+    use crate::bip32::{
+        get_extended_public_key, ExtendedPublicKey, ExtendedPublicKeyVersion, HdNode,
+    };
     use crate::curve::Curve;
+    use crate::Error;
     use bitcoin::util::bip32::{DerivationPath, ExtendedPrivKey};
     use bitcoin::Network;
     use hex;
     use secp256k1::Secp256k1;
     use std::str::FromStr;
+    use zeroize::Zeroize;
     #[test]
     fn test_derive_from_seed() {
         let seed = "000102030405060708090a0b0c0d0e0f";
@@ -126,4 +295,114 @@ mod tests {
             extended_private_key.private_key.to_bytes()
         );
     }
+
+    #[test]
+    fn test_zeroize() {
+        let seed_bytes = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        let mut node = HdNode::get_node(&seed_bytes, "m/0'/1", Curve::Secp256k1).unwrap();
+        assert_ne!(node.private_key_bytes, [0u8; 32]);
+        node.zeroize();
+        assert_eq!(node.chain_code, [0u8; 32]);
+        assert_eq!(node.private_key_bytes, [0u8; 32]);
+        assert_eq!(node.private_key_extension, [0u8; 32]);
+    }
+
+    #[test]
+    fn test_extended_public_key() {
+        // Account keys of the mnemonic "abandon abandon ... about", the test vectors of BIP49 and BIP84
+        let zpub = ExtendedPublicKey::from_str("zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs").unwrap();
+        assert_eq!(zpub.purpose(), 84);
+        assert_eq!(zpub.account(), Some(0));
+        let cases = [
+            (
+                "m/0/0",
+                "0330d54fd0dd420a6e5f8d3624f5f3482cae350f79d5f0753bf5beef9c2d91af3c",
+            ),
+            (
+                "m/0/1",
+                "03e775fd51f0dfb8cd865d9ff1cca2a158cf651fe997fdc9fee9c1d3b5e995ea77",
+            ),
+            (
+                "m/1/0",
+                "03025324888e429ab8e3dbaf1f7802648b9cd01e9b418485c5fa4c1b9b5700e1a6",
+            ),
+        ];
+        for (path, public_key) in cases.iter() {
+            assert_eq!(
+                hex::encode(zpub.derive_public_key(path).unwrap()),
+                *public_key
+            );
+        }
+        let ypub = ExtendedPublicKey::from_str("ypub6Ww3ibxVfGzLrAH1PNcjyAWenMTbbAosGNB6VvmSEgytSER9azLDWCxoJwW7Ke7icmizBMXrzBx9979FfaHxHcrArf3zbeJJJUZPf663zsP").unwrap();
+        assert_eq!(ypub.purpose(), 49);
+        assert_eq!(
+            hex::encode(ypub.derive_public_key("m/0/0").unwrap()),
+            "039b3b694b8fc5b5e07fb069c783cac754f5d38c3e08bed1960e31fdb1dda35c24"
+        );
+        let xpub = ExtendedPublicKey::from_str("xpub6BosfCnifzxcFwrSzQiqu2DBVTshkCXacvNsWGYJVVhhawA7d4R5WSWGFNbi8Aw6ZRc1brxMyWMzG3DSSSSoekkudhUd9yLb6qx39T9nMdj").unwrap();
+        assert_eq!(xpub.purpose(), 44);
+        assert!(!xpub.version.is_testnet());
+
+        // Hardened children can't be derived from a public key
+        assert_eq!(
+            zpub.derive_public_key("m/0'/0").err(),
+            Some(Error::InvalidDerivationpath)
+        );
+        // Derived children keep the version of the parent
+        let change = zpub.derive("m/0").unwrap();
+        assert_eq!(change.depth(), 4);
+        assert_eq!(change.account(), None);
+        assert_eq!(change.to_string(), "zpub6u4KbU8TSgNuZSxzv7HaGq5Tk361gMHdZxnM4UYuwzg5CMLcNytzhobitV4Zq6vWtWHpG9QijsigkxAzXvQWyLRfLq1L7VxPP1tky1hPfD4");
+        assert_eq!(
+            hex::encode(change.derive_public_key("m/0").unwrap()),
+            "0330d54fd0dd420a6e5f8d3624f5f3482cae350f79d5f0753bf5beef9c2d91af3c"
+        );
+
+        // Testnet account key of m/44'/1'/0'
+        let tpub = ExtendedPublicKey::from_str("tpubDC5FSnBiZDMmhiuCmWAYsLwgLYrrT9rAqvTySfuCCrgsWz8wxMXUS9Tb9iVMvcRbvFcAHGkMD5Kx8koh4GquNGNTfohfk7pgjhaPCdXpoba").unwrap();
+        assert_eq!(tpub.purpose(), 44);
+        assert!(tpub.version.is_testnet());
+        let receive = tpub.derive("m/0/0").unwrap();
+        assert_eq!(receive.to_string(), "tpubDGPeJf8tZASQrf4U8Pi4qUwNxBfKSzmh6GcNXBFLTgmR2jqqFBFWj9cw8p15gMMjJ12ekozkJQLkgeUzePYGrb8gAa6UAakjCum4ytn2aP3");
+        assert_eq!(
+            hex::encode(receive.public_key()),
+            "02a7451395735369f2ecdfc829c0f774e88ef1303dfe5b2f04dbaab30a535dfdd6"
+        );
+
+        // Extended private keys and corrupted keys are rejected
+        assert_eq!(
+            ExtendedPublicKey::from_str("xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi").err(),
+            Some(Error::InvalidPublicKey)
+        );
+        assert_eq!(
+            ExtendedPublicKey::from_str("zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYt").err(),
+            Some(Error::InvalidPublicKey)
+        );
+    }
+
+    #[test]
+    fn test_get_extended_public_key() {
+        let mnemonic = crate::bip39::Mnemonic::new("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about", "").unwrap();
+        assert_eq!(
+            get_extended_public_key(&mnemonic.seed, "m/84'/0'/0'", ExtendedPublicKeyVersion::Zpub).unwrap(),
+            "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs"
+        );
+        assert_eq!(
+            get_extended_public_key(&mnemonic.seed, "m/44'/0'/0'", ExtendedPublicKeyVersion::Xpub).unwrap(),
+            "xpub6BosfCnifzxcFwrSzQiqu2DBVTshkCXacvNsWGYJVVhhawA7d4R5WSWGFNbi8Aw6ZRc1brxMyWMzG3DSSSSoekkudhUd9yLb6qx39T9nMdj"
+        );
+        assert_eq!(
+            get_extended_public_key(&mnemonic.seed, "m/44'/1'/0'", ExtendedPublicKeyVersion::Tpub).unwrap(),
+            "tpubDC5FSnBiZDMmhiuCmWAYsLwgLYrrT9rAqvTySfuCCrgsWz8wxMXUS9Tb9iVMvcRbvFcAHGkMD5Kx8koh4GquNGNTfohfk7pgjhaPCdXpoba"
+        );
+        assert_eq!(
+            ExtendedPublicKeyVersion::from_purpose(49),
+            ExtendedPublicKeyVersion::Ypub
+        );
+        assert_eq!(
+            get_extended_public_key(&mnemonic.seed, "m/44'/x", ExtendedPublicKeyVersion::Xpub)
+                .err(),
+            Some(Error::InvalidDerivationpath)
+        );
+    }
 }
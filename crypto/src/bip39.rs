@@ -1,63 +1,131 @@
 use super::number_util::random_iv;
 use crate::Error;
-use bip39::Language;
-use bip39::Mnemonic as CryptoMnemonic;
-use std::str::FromStr;
+pub use bip39::Language;
+use bip39::{Error as Bip39Error, Mnemonic as CryptoMnemonic};
+use zeroize::Zeroize;
 
-const SUPPORT_MNEMONIC_WORDS_COUNT: [u32; 3] = [12, 18, 24];
+const SUPPORT_MNEMONIC_WORDS_COUNT: [u32; 5] = [12, 15, 18, 21, 24];
+// Japanese mnemonics are written with ideographic spaces between the words
+const JAPANESE_WORD_SEPARATOR: &str = "\u{3000}";
 
+/// A parsed mnemonic, the words, seed and entropy are wiped when it's dropped
 pub struct Mnemonic {
     pub words: String,
     pub seed: Vec<u8>,
     pub entropy: Vec<u8>,
 }
 
+impl Zeroize for Mnemonic {
+    fn zeroize(&mut self) {
+        self.words.zeroize();
+        self.seed.zeroize();
+        self.entropy.zeroize();
+    }
+}
+
+impl Drop for Mnemonic {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
 impl Mnemonic {
     pub fn generate_mnemonic_string(word_count: u32) -> Result<String, Error> {
-        if !SUPPORT_MNEMONIC_WORDS_COUNT.contains(&word_count) {
-            return Err(Error::InvalidMnemonic);
-        }
-        let entropy_bytes = (word_count / 3) * 4;
-        let entropy = random_iv(entropy_bytes as usize);
-        let mnemonic = CryptoMnemonic::from_entropy_in(Language::English, &entropy)
-            .map_err(|_| Error::InvalidMnemonic)?;
-        Ok(mnemonic.to_string())
+        Self::generate_mnemonic_string_in(Language::English, word_count)
+    }
+
+    pub fn generate_mnemonic_string_in(
+        language: Language,
+        word_count: u32,
+    ) -> Result<String, Error> {
+        let mnemonic = Self::generate_crypto_mnemonic(language, word_count)?;
+        Ok(Self::join_words(&mnemonic))
     }
 
     pub fn generate(word_count: u32, password: &str) -> Result<Mnemonic, Error> {
+        Self::generate_in(Language::English, word_count, password)
+    }
+
+    pub fn generate_in(
+        language: Language,
+        word_count: u32,
+        password: &str,
+    ) -> Result<Mnemonic, Error> {
+        let mnemonic = Self::generate_crypto_mnemonic(language, word_count)?;
+        Ok(Self::from_crypto_mnemonic(&mnemonic, password))
+    }
+
+    /// Parse `mnemonic` in any supported language, the language is detected from its words
+    pub fn new(mnemonic: &str, password: &str) -> Result<Mnemonic, Error> {
+        let mnemonic = Self::parse(mnemonic, None)?;
+        Ok(Self::from_crypto_mnemonic(&mnemonic, password))
+    }
+
+    pub fn new_in(language: Language, mnemonic: &str, password: &str) -> Result<Mnemonic, Error> {
+        let mnemonic = Self::parse(mnemonic, Some(language))?;
+        Ok(Self::from_crypto_mnemonic(&mnemonic, password))
+    }
+
+    pub fn is_valid(mnemonic: &str) -> bool {
+        Self::parse(mnemonic, None).is_ok()
+    }
+
+    pub fn is_valid_in(language: Language, mnemonic: &str) -> bool {
+        Self::parse(mnemonic, Some(language)).is_ok()
+    }
+
+    fn generate_crypto_mnemonic(
+        language: Language,
+        word_count: u32,
+    ) -> Result<CryptoMnemonic, Error> {
         if !SUPPORT_MNEMONIC_WORDS_COUNT.contains(&word_count) {
             return Err(Error::InvalidMnemonic);
         }
         let entropy_bytes = (word_count / 3) * 4;
         let entropy = random_iv(entropy_bytes as usize);
-        let mnemonic = CryptoMnemonic::from_entropy_in(Language::English, &entropy)
-            .map_err(|_| Error::InvalidMnemonic)?;
+        CryptoMnemonic::from_entropy_in(language, &entropy).map_err(|_| Error::InvalidMnemonic)
+    }
 
-        let seed = mnemonic.to_seed_normalized(password).to_vec();
-        let (arr, len) = mnemonic.to_entropy_array();
-        let entropy = arr[0..len].to_vec();
-        Ok(Mnemonic {
-            words: mnemonic.to_string(),
-            seed,
-            entropy,
-        })
+    // The mnemonic is NFKD normalized before looking up its words, so that composed
+    // characters and ideographic spaces of Japanese mnemonics are accepted
+    fn parse(mnemonic: &str, language: Option<Language>) -> Result<CryptoMnemonic, Error> {
+        let mnemonic = mnemonic.to_lowercase();
+        let parsed = match language {
+            Some(language) => CryptoMnemonic::parse_in(language, mnemonic.as_str()),
+            None => CryptoMnemonic::parse(mnemonic.as_str()),
+        };
+        match parsed {
+            Ok(parsed) => Ok(parsed),
+            // The words of some wordlists are shared, any language with a valid checksum
+            // gives the same seed
+            Err(Bip39Error::AmbiguousLanguages(languages)) => languages
+                .iter()
+                .find_map(|language| CryptoMnemonic::parse_in(language, mnemonic.as_str()).ok())
+                .ok_or(Error::InvalidMnemonic),
+            Err(_) => Err(Error::InvalidMnemonic),
+        }
     }
 
-    pub fn new(mnemonic: &str, password: &str) -> Result<Mnemonic, Error> {
-        let mnemonic = CryptoMnemonic::from_str(&mnemonic.to_lowercase())
-            .map_err(|_| Error::InvalidMnemonic)?;
-        let seed = mnemonic.to_seed_normalized(password).to_vec();
-        let (arr, len) = mnemonic.to_entropy_array();
+    fn from_crypto_mnemonic(mnemonic: &CryptoMnemonic, password: &str) -> Mnemonic {
+        let mut seed_array = mnemonic.to_seed(password);
+        let seed = seed_array.to_vec();
+        seed_array.zeroize();
+        let (mut arr, len) = mnemonic.to_entropy_array();
         let entropy = arr[0..len].to_vec();
-        Ok(Mnemonic {
-            words: mnemonic.to_string(),
+        arr.zeroize();
+        Mnemonic {
+            words: Self::join_words(mnemonic),
             seed,
             entropy,
-        })
+        }
     }
 
-    pub fn is_valid(mnemonic: &str) -> bool {
-        CryptoMnemonic::parse_normalized(&mnemonic.to_lowercase()).is_ok()
+    fn join_words(mnemonic: &CryptoMnemonic) -> String {
+        let separator = match mnemonic.language() {
+            Language::Japanese => JAPANESE_WORD_SEPARATOR,
+            _ => " ",
+        };
+        mnemonic.word_iter().collect::<Vec<_>>().join(separator)
     }
 }
 
@@ -75,7 +143,16 @@ mod tests {
         let mnemonic = Mnemonic::generate(24, "").unwrap();
         assert_eq!(Mnemonic::is_valid(&mnemonic.words), true);
 
+        // 15 and 21 words encode 160 and 224 bits of entropy
+        for (word_count, entropy_len) in [(15, 20), (21, 28)] {
+            let mnemonic = Mnemonic::generate(word_count, "").unwrap();
+            assert!(Mnemonic::is_valid(&mnemonic.words));
+            assert_eq!(mnemonic.words.split(' ').count(), word_count as usize);
+            assert_eq!(mnemonic.entropy.len(), entropy_len);
+        }
+
         assert_eq!(Mnemonic::generate(25, "").is_err(), true);
+        assert!(Mnemonic::generate(16, "").is_err());
         assert_eq!(Mnemonic::generate(11, "").is_err(), true);
     }
 
@@ -95,4 +172,82 @@ mod tests {
         let mnemonic_eng = Mnemonic::new(&test_mnemonic_eng, "password1").unwrap();
         assert_eq!(mnemonic_eng.words, test_mnemonic_eng.to_lowercase());
     }
+
+    #[test]
+    fn test_word_count() {
+        // Mnemonics of zero entropy, the seeds are derived with the passphrase "TREZOR"
+        let mnemonic_15 = format!("{} address", ["abandon"; 14].join(" "));
+        let mnemonic = Mnemonic::new(&mnemonic_15, "TREZOR").unwrap();
+        assert_eq!(mnemonic.entropy, [0u8; 20]);
+        assert_eq!(hex::encode(&mnemonic.seed), "fa08713f46bf5cb48728ceb70e3aae1bc53c5cb7b4e29c5610261d1cbb7be3bed4d805256fec515754d2be35974fc5da678168e9d9bb0cb70948026923b0def3");
+
+        let mnemonic_21 = format!("{} admit", ["abandon"; 20].join(" "));
+        let mnemonic = Mnemonic::new(&mnemonic_21, "TREZOR").unwrap();
+        assert_eq!(mnemonic.entropy, [0u8; 28]);
+        assert_eq!(hex::encode(&mnemonic.seed), "e7dadc189d2e8d07ac278d9ec98a1d2d327e4a6b7df494c00cbf2cbf2d3543dac7000fc72d4ada8d9997dc8db388ff22c6d79f604a7455f2df5534a28eee04c6");
+    }
+
+    #[test]
+    fn test_language() {
+        let english = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        assert!(Mnemonic::is_valid_in(Language::English, english));
+        assert!(!Mnemonic::is_valid_in(Language::French, english));
+        assert!(Mnemonic::new_in(Language::Spanish, english, "").is_err());
+
+        for language in [Language::English, Language::Japanese, Language::Korean].iter() {
+            let mnemonic = Mnemonic::generate_in(*language, 24, "").unwrap();
+            assert!(Mnemonic::is_valid_in(*language, &mnemonic.words));
+            assert_eq!(
+                Mnemonic::new(&mnemonic.words, "").unwrap().seed,
+                mnemonic.seed
+            );
+        }
+        let chinese =
+            Mnemonic::generate_mnemonic_string_in(Language::SimplifiedChinese, 18).unwrap();
+        assert_eq!(chinese.split(' ').count(), 18);
+        assert!(Mnemonic::is_valid_in(Language::SimplifiedChinese, &chinese));
+    }
+
+    #[test]
+    fn test_japanese_normalization() {
+        // Japanese test vector of BIP39, written in NFC with ideographic spaces
+        let mnemonic = "あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あおぞら";
+        let passphrase = "㍍ガバヴァぱばぐゞちぢ十人十色";
+        let seed = "a262d6fb6122ecf45be09c50492b31f92e9beb7d9a845987a02cefda57a15f9c467a17872029a9e92299b5cbdf306e3a0ee620245cbd508959b6cb7ca637bd55";
+        assert!(Mnemonic::is_valid(mnemonic));
+        let parsed = Mnemonic::new(mnemonic, passphrase).unwrap();
+        assert_eq!(hex::encode(&parsed.seed), seed);
+        assert_eq!(parsed.entropy, vec![0u8; 16]);
+        assert_eq!(parsed.words.split('\u{3000}').count(), 12);
+        let parsed = Mnemonic::new_in(Language::Japanese, mnemonic, passphrase).unwrap();
+        assert_eq!(hex::encode(&parsed.seed), seed);
+
+        // Words separated by ASCII spaces are accepted as well
+        let spaced = mnemonic.replace('\u{3000}', " ");
+        let parsed = Mnemonic::new(&spaced, passphrase).unwrap();
+        assert_eq!(hex::encode(&parsed.seed), seed);
+    }
+
+    #[test]
+    fn test_zeroize() {
+        let mut mnemonic = Mnemonic::new(
+            "suffer artefact burst review network fantasy easy century mom unique pupil boy",
+            "TREZOR",
+        )
+        .unwrap();
+        let (words, words_len) = (mnemonic.words.as_ptr(), mnemonic.words.len());
+        let seed = mnemonic.seed.as_ptr();
+        mnemonic.zeroize();
+        assert!(mnemonic.words.is_empty());
+        assert!(mnemonic.seed.is_empty());
+        assert!(mnemonic.entropy.is_empty());
+        // The wiped buffers keep their capacity, and are still owned by the mnemonic
+        let (words, seed) = unsafe {
+            (
+                std::slice::from_raw_parts(words, words_len),
+                std::slice::from_raw_parts(seed, 64),
+            )
+        };
+        assert!(words.iter().chain(seed).all(|&byte| byte == 0));
+    }
 }
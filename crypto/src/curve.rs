@@ -9,6 +9,7 @@ pub enum Curve {
     Curve25519,
     Nist256p1,
     Ed25519Extended,
+    Sr25519,
 }
 
 impl FromStr for Curve {
@@ -23,6 +24,7 @@ impl FromStr for Curve {
             "curve25519" => Ok(Self::Curve25519),
             "nist256p1" => Ok(Self::Nist256p1),
             "ed25519-cardano-seed" => Ok(Self::Ed25519Extended),
+            "sr25519" => Ok(Self::Sr25519),
             _ => Err(Error::NotSupportedCurve),
         }
     }
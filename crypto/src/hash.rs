@@ -24,19 +24,43 @@ impl Hashable for Keccak256 {
     }
 }
 
+pub struct Blake2b256;
+impl Hashable for Blake2b256 {
+    fn hash(&self, input: &[u8]) -> Result<Vec<u8>, Error> {
+        use blake2::digest::{Update, VariableOutput};
+        let mut hasher = blake2::VarBlake2b::new(32).expect("valid blake2b output size");
+        hasher.update(input);
+        Ok(hasher.finalize_boxed().to_vec())
+    }
+}
+
+pub struct Blake2b512;
+impl Hashable for Blake2b512 {
+    fn hash(&self, input: &[u8]) -> Result<Vec<u8>, Error> {
+        use blake2::{Blake2b, Digest};
+        Ok(Blake2b::digest(input).to_vec())
+    }
+}
+
 /* Helper hash functions */
 pub fn compute_mac(derived_key: &[u8], encrypted_text: &[u8]) -> Vec<u8> {
     use tiny_keccak::{Hasher as KeccakHasher, Keccak};
-    let result = [&derived_key, encrypted_text].concat();
     let mut hasher = Keccak::v256();
-    hasher.update(&result);
+    hasher.update(derived_key);
+    hasher.update(encrypted_text);
     let mut output = [0u8; 32];
     hasher.finalize(&mut output);
     output.to_vec()
 }
 
+/// Whether `mac` is the MAC of `encrypted_text`, compared in constant time
+pub fn verify_mac(derived_key: &[u8], encrypted_text: &[u8], mac: &[u8]) -> bool {
+    use subtle::ConstantTimeEq;
+    compute_mac(derived_key, encrypted_text).ct_eq(mac).into()
+}
+
 pub fn dsha256(input: &[u8]) -> Vec<u8> {
-    use sha2::{ Digest, Sha256 };
+    use sha2::{Digest, Sha256};
     Sha256::digest(&Sha256::digest(&input)).to_vec()
 }
 
@@ -88,4 +112,27 @@ mod tests {
         let wrong_hex_mac = hex::encode(wrong_mac);
         assert_ne!(wrong_hex_mac, expected_mac);
     }
+
+    use crate::hash::verify_mac;
+    #[test]
+    fn test_verify_mac() {
+        let mac = hex::decode("57caa176af1ac0433c5df30e8dabcd2ec1af1e92a26eced5f719b88458777cd6")
+            .unwrap();
+        assert!(verify_mac(b"hello world", b"!", &mac));
+        assert!(!verify_mac(b"!", b"hello world", &mac));
+        assert!(!verify_mac(b"hello world", b"!", &mac[..31]));
+        assert!(!verify_mac(b"hello world", b"!", &[]));
+    }
+
+    use crate::hash::{Blake2b256, Blake2b512, Hasher as CryptoHasher};
+    #[test]
+    fn test_blake2b() {
+        let hash256 = CryptoHasher::hash(Blake2b256, b"abc").unwrap();
+        assert_eq!(
+            hex::encode(hash256),
+            "bddd813c634239723171ef3fee98579b94964e3bb1cb3e427262c8c068d52319"
+        );
+        let hash512 = CryptoHasher::hash(Blake2b512, b"abc").unwrap();
+        assert_eq!(hex::encode(hash512), "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d17d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923");
+    }
 }
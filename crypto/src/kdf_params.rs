@@ -1,3 +1,5 @@
+use super::argon2_params::{self, Argon2Params};
+use super::pbkdf2_params::Pbkdf2Params;
 use super::scrypt_params::ScryptParams;
 use crate::Error;
 use serde::{Deserialize, Serialize};
@@ -10,12 +12,98 @@ pub trait KdfParamsType {
 #[serde(untagged)]
 pub enum KdfParams {
     ScryptParam(ScryptParams),
+    Pbkdf2Param(Pbkdf2Params),
+    Argon2Param(Argon2Params),
+}
+
+/// Scrypt, the default kdf of V3 KeyStoreJSONs
+impl Default for KdfParams {
+    fn default() -> KdfParams {
+        Self::ScryptParam(ScryptParams::default())
+    }
+}
+
+impl KdfParams {
+    /// The `kdf` name of the params in a V3 KeyStoreJSON
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::ScryptParam(_) => "scrypt",
+            Self::Pbkdf2Param(_) => "pbkdf2",
+            Self::Argon2Param(_) => "argon2id",
+        }
+    }
 }
 
 impl KdfParamsType for KdfParams {
     fn generate_derived_key(&self, password: &[u8]) -> Result<Vec<u8>, Error> {
         match self {
             Self::ScryptParam(algo) => algo.generate_derived_key(password),
+            Self::Pbkdf2Param(algo) => algo.generate_derived_key(password),
+            Self::Argon2Param(algo) => algo.generate_derived_key(password),
+        }
+    }
+}
+
+// Minimum costs of a `KdfPolicy`, the recommendations of OWASP, below which passwords are cheap
+// to brute-force
+const MIN_ARGON2ID_M_COST: u32 = 19456;
+const MIN_ARGON2ID_T_COST: u32 = 2;
+const MIN_SCRYPT_N: u32 = 1 << 15;
+const MIN_SCRYPT_R: u32 = 8;
+
+/// The kdf and its cost used to encrypt new data, every encryption gets its own random salt
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum KdfPolicy {
+    Argon2id {
+        m_cost: u32,
+        t_cost: u32,
+        p_cost: u32,
+    },
+    Scrypt {
+        n: u32,
+        r: u32,
+        p: u32,
+    },
+}
+
+impl Default for KdfPolicy {
+    fn default() -> KdfPolicy {
+        KdfPolicy::Argon2id {
+            m_cost: argon2_params::DEFAULT_M_COST,
+            t_cost: argon2_params::DEFAULT_T_COST,
+            p_cost: argon2_params::DEFAULT_P_COST,
+        }
+    }
+}
+
+impl KdfPolicy {
+    /// New params of the policy with a random salt, fails with `KdfParamsTooWeak` if the cost
+    /// of the policy is below the minimum
+    pub fn new_kdf_params(&self) -> Result<KdfParams, Error> {
+        match *self {
+            KdfPolicy::Argon2id {
+                m_cost,
+                t_cost,
+                p_cost,
+            } => {
+                if m_cost < MIN_ARGON2ID_M_COST || t_cost < MIN_ARGON2ID_T_COST {
+                    return Err(Error::KdfParamsTooWeak);
+                }
+                Ok(KdfParams::Argon2Param(Argon2Params::new(
+                    m_cost, t_cost, p_cost,
+                )))
+            }
+            KdfPolicy::Scrypt { n, r, p } => {
+                if n < MIN_SCRYPT_N || r < MIN_SCRYPT_R {
+                    return Err(Error::KdfParamsTooWeak);
+                }
+                Ok(KdfParams::ScryptParam(ScryptParams {
+                    n,
+                    r,
+                    p,
+                    ..ScryptParams::default()
+                }))
+            }
         }
     }
 }
@@ -1,13 +1,17 @@
 use super::aes_params::AesParams;
 use super::kdf_params::KdfParams;
 use crate::Error;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::str::FromStr;
 
+/// A V3 KeyStoreJSON, or a V1 KeyStoreJSON of geth whose `Crypto` is capitalized and whose
+/// version is a string
 #[derive(Serialize, Deserialize)]
 pub struct KeyStoreJson {
+    #[serde(alias = "Crypto")]
     pub crypto: Crypto,
     pub id: String,
+    #[serde(deserialize_with = "deserialize_version")]
     pub version: u32,
 }
 
@@ -21,6 +25,19 @@ pub struct Crypto {
     pub mac: String,
 }
 
+fn deserialize_version<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Version {
+        Number(u32),
+        String(String),
+    }
+    match Version::deserialize(deserializer)? {
+        Version::Number(version) => Ok(version),
+        Version::String(version) => version.parse().map_err(serde::de::Error::custom),
+    }
+}
+
 impl FromStr for KeyStoreJson {
     type Err = Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -69,6 +86,7 @@ mod tests {
         );
         let scrypt_param = match key_store_json_struct.crypto.kdfparams {
             KdfParams::ScryptParam(param) => param,
+            _ => panic!("scrypt params expected"),
         };
         assert_eq!(scrypt_param.n, 262144);
         assert_eq!(scrypt_param.dklen, 32);
@@ -88,4 +106,43 @@ mod tests {
             "2103ac29920d71da29f15d75b4a16dbe95cfd7ff8faea1056c33131d846e3097"
         );
     }
+
+    #[test]
+    fn test_import_pbkdf2_keystore_json() {
+        let data = r#"
+        {
+            "crypto" : {
+                "cipher" : "aes-128-ctr",
+                "cipherparams" : {
+                    "iv" : "6087dab2f9fdbbfaddc31a909735c1e6"
+                },
+                "ciphertext" : "5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46",
+                "kdf" : "pbkdf2",
+                "kdfparams" : {
+                    "c" : 262144,
+                    "dklen" : 32,
+                    "prf" : "hmac-sha256",
+                    "salt" : "ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd"
+                },
+                "mac" : "517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2"
+            },
+            "id" : "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+            "version" : 3
+        }
+        "#;
+        let key_store_json_struct = KeyStoreJson::from_str(data).unwrap();
+        assert_eq!(key_store_json_struct.crypto.kdf, "pbkdf2");
+        assert_eq!(key_store_json_struct.crypto.kdfparams.name(), "pbkdf2");
+        let pbkdf2_param = match key_store_json_struct.crypto.kdfparams {
+            KdfParams::Pbkdf2Param(param) => param,
+            _ => panic!("pbkdf2 params expected"),
+        };
+        assert_eq!(pbkdf2_param.c, 262144);
+        assert_eq!(pbkdf2_param.dklen, 32);
+        assert_eq!(pbkdf2_param.prf, "hmac-sha256");
+        assert_eq!(
+            pbkdf2_param.salt,
+            "ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd"
+        );
+    }
 }
@@ -1,11 +1,16 @@
+pub mod aead;
 pub mod aes;
 pub mod aes_params;
+pub mod argon2_params;
 pub mod curve;
 pub mod hash;
 pub mod kdf_params;
 pub mod key_store_json;
+pub mod pbkdf2_params;
 pub mod public_key;
+pub mod rsa;
 pub mod scrypt_params;
+pub mod sr25519;
 
 pub mod bip32;
 pub mod bip39;
@@ -39,6 +44,22 @@ pub enum Error {
     NotSupportedCurve,
 
     NotSupportedCipher,
+
+    InvalidJwk,
+
+    NotSupportedMessageSigning,
+
+    InvalidSignature,
+
+    InvalidMessageHash,
+
+    InvalidTypedData,
+
+    NotSupportedTransactionDecoding,
+
+    InvalidEncodedTransaction,
+
+    KdfParamsTooWeak,
 }
 
 impl Error {
@@ -57,6 +78,14 @@ impl Error {
             Error::NotSupportedPublicKeyType => "-3011".to_owned(),
             Error::NotSupportedCurve => "-3012".to_owned(),
             Error::NotSupportedCipher => "-3013".to_owned(),
+            Error::InvalidJwk => "-3014".to_owned(),
+            Error::NotSupportedMessageSigning => "-3015".to_owned(),
+            Error::InvalidSignature => "-3016".to_owned(),
+            Error::InvalidMessageHash => "-3017".to_owned(),
+            Error::InvalidTypedData => "-3018".to_owned(),
+            Error::NotSupportedTransactionDecoding => "-3019".to_owned(),
+            Error::InvalidEncodedTransaction => "-3020".to_owned(),
+            Error::KdfParamsTooWeak => "-3021".to_owned(),
         }
     }
 
@@ -75,6 +104,18 @@ impl Error {
             Error::NotSupportedPublicKeyType => "Not supported public key type".to_owned(),
             Error::NotSupportedCurve => "Not supported curve".to_owned(),
             Error::NotSupportedCipher => "Not supported cipher type".to_owned(),
+            Error::InvalidJwk => "Invalid JWK".to_owned(),
+            Error::NotSupportedMessageSigning => {
+                "Signing messages is not supported by the coin".to_owned()
+            }
+            Error::InvalidSignature => "Invalid signature".to_owned(),
+            Error::InvalidMessageHash => "The raw hash of a message must be 32 bytes".to_owned(),
+            Error::InvalidTypedData => "Invalid EIP-712 typed data".to_owned(),
+            Error::NotSupportedTransactionDecoding => {
+                "Decoding transactions is not supported by the coin".to_owned()
+            }
+            Error::InvalidEncodedTransaction => "Invalid encoded transaction".to_owned(),
+            Error::KdfParamsTooWeak => "The cost of the kdf is below the minimum".to_owned(),
         }
     }
 }
@@ -0,0 +1,85 @@
+use hmac::Hmac;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use super::kdf_params::KdfParamsType;
+use super::number_util::random_iv;
+use crate::Error;
+
+// The only pseudorandom function defined by the Web3 Secret Storage Definition
+pub const PRF_HMAC_SHA256: &str = "hmac-sha256";
+// Derived keys are split into the encryption key and the MAC key, 16 bytes each
+const MIN_DKLEN: usize = 32;
+const MAX_DKLEN: usize = 64;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Pbkdf2Params {
+    pub c: u32,
+    pub prf: String,
+    pub dklen: usize,
+    pub salt: String,
+}
+
+impl Default for Pbkdf2Params {
+    fn default() -> Pbkdf2Params {
+        Pbkdf2Params {
+            c: 262144,
+            prf: PRF_HMAC_SHA256.to_owned(),
+            dklen: 32,
+            salt: hex::encode(random_iv(32)),
+        }
+    }
+}
+
+impl KdfParamsType for Pbkdf2Params {
+    fn generate_derived_key(&self, password: &[u8]) -> Result<Vec<u8>, Error> {
+        if self.prf != PRF_HMAC_SHA256
+            || self.c == 0
+            || self.dklen < MIN_DKLEN
+            || self.dklen > MAX_DKLEN
+        {
+            return Err(Error::KdfParamsInvalid);
+        }
+        let salt_bytes = hex::decode(&self.salt).or(Err(Error::KdfParamsInvalid))?;
+        let mut output = vec![0u8; self.dklen];
+        pbkdf2::pbkdf2::<Hmac<Sha256>>(password, &salt_bytes, self.c, &mut output);
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pbkdf2() {
+        // PBKDF2-HMAC-SHA256 test vector of RFC 7914
+        let params = Pbkdf2Params {
+            c: 1,
+            prf: PRF_HMAC_SHA256.to_owned(),
+            dklen: 64,
+            salt: hex::encode("salt"),
+        };
+        assert_eq!(
+            hex::encode(params.generate_derived_key(b"passwd").unwrap()),
+            "55ac046e56e3089fec1691c22544b605f94185216dde0465e68b9d57c20dacbc49ca9cccf179b645991664b39d77ef317c71b845b1e30bd509112041d3a19783"
+        );
+
+        let invalid_prf = Pbkdf2Params {
+            prf: "hmac-sha512".to_owned(),
+            ..params.clone()
+        };
+        assert_eq!(
+            invalid_prf.generate_derived_key(b"passwd").err(),
+            Some(Error::KdfParamsInvalid)
+        );
+        let invalid_dklen = Pbkdf2Params {
+            dklen: 16,
+            ..params
+        };
+        assert_eq!(
+            invalid_dklen.generate_derived_key(b"passwd").err(),
+            Some(Error::KdfParamsInvalid)
+        );
+    }
+}
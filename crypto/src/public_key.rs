@@ -1,3 +1,5 @@
+use crate::rsa;
+use crate::sr25519;
 use crate::Error;
 use ed25519_dalek;
 use secp256k1;
@@ -8,6 +10,8 @@ pub enum PublicKeyType {
     Secp256k1,
     Secp256k1Extended,
     Ed25519,
+    Sr25519,
+    Rsa,
 }
 
 impl FromStr for PublicKeyType {
@@ -18,6 +22,8 @@ impl FromStr for PublicKeyType {
             "secp256k1" => Ok(Self::Secp256k1),
             "secp256k1extended" => Ok(Self::Secp256k1Extended),
             "ed25519" => Ok(Self::Ed25519),
+            "sr25519" => Ok(Self::Sr25519),
+            "rsa" => Ok(Self::Rsa),
             _ => Err(()),
         }
     }
@@ -73,7 +79,7 @@ impl PublickKeyConvertter {
 pub fn get_public_key(
     pub_key_type: &str,
     private_key: &[u8],
-    _extend_bytes: &[u8],
+    extend_bytes: &[u8],
     _chain_code_bytes: &[u8],
 ) -> Result<Vec<u8>, Error> {
     let public_key_type =
@@ -85,5 +91,21 @@ pub fn get_public_key(
             PublickKeyConvertter::convert(Secp256k1ExtendConverter, private_key)
         }
         PublicKeyType::Ed25519 => PublickKeyConvertter::convert(Ed25519Converter, private_key),
+        PublicKeyType::Sr25519 => sr25519::get_public_key(private_key, extend_bytes),
+        PublicKeyType::Rsa => rsa::get_public_key(private_key),
+    }
+}
+
+/// Encode `public_key` in the format of `pub_key_type`, secp256k1 keys may be either compressed
+/// or uncompressed, other keys are returned as they are
+pub fn convert_public_key(pub_key_type: &str, public_key: &[u8]) -> Result<Vec<u8>, Error> {
+    let public_key_type =
+        PublicKeyType::from_str(pub_key_type).map_err(|_| Error::NotSupportedPublicKeyType)?;
+    let secp256k1_key =
+        || secp256k1::PublicKey::from_slice(public_key).map_err(|_| Error::InvalidPublicKey);
+    match public_key_type {
+        PublicKeyType::Secp256k1 => Ok(secp256k1_key()?.serialize().to_vec()),
+        PublicKeyType::Secp256k1Extended => Ok(secp256k1_key()?.serialize_uncompressed().to_vec()),
+        _ => Ok(public_key.to_vec()),
     }
 }
@@ -0,0 +1,151 @@
+use crate::Error;
+use ::rsa::pkcs1::{FromRsaPrivateKey, ToRsaPrivateKey};
+use ::rsa::{BigUint, PaddingScheme, PublicKey, PublicKeyParts, RsaPrivateKey, RsaPublicKey};
+use rand_core::OsRng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::str::FromStr;
+
+// The only key size used by Arweave
+const KEY_BITS: usize = 4096;
+pub const MODULUS_SIZE: usize = KEY_BITS / 8;
+// Salt length of the RSA-PSS signatures, the size of a SHA-256 digest
+const PSS_SALT_LENGTH: usize = 32;
+
+/// An RSA private key in the JSON Web Key format, every number is base64url encoded
+#[derive(Serialize, Deserialize)]
+pub struct Jwk {
+    pub kty: String,
+    pub n: String,
+    pub e: String,
+    pub d: String,
+    pub p: String,
+    pub q: String,
+    pub dp: String,
+    pub dq: String,
+    pub qi: String,
+}
+
+impl FromStr for Jwk {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s).map_err(|_| Error::InvalidJwk)
+    }
+}
+
+impl Jwk {
+    /// PKCS#1 DER encoding of the private key
+    pub fn to_der(&self) -> Result<Vec<u8>, Error> {
+        if self.kty != "RSA" {
+            return Err(Error::InvalidJwk);
+        }
+        let key = RsaPrivateKey::from_components(
+            decode_number(&self.n)?,
+            decode_number(&self.e)?,
+            decode_number(&self.d)?,
+            vec![decode_number(&self.p)?, decode_number(&self.q)?],
+        );
+        check_private_key(&key).map_err(|_| Error::InvalidJwk)?;
+        let der = key.to_pkcs1_der().map_err(|_| Error::InvalidJwk)?;
+        Ok(der.as_der().to_vec())
+    }
+}
+
+fn decode_number(encoded: &str) -> Result<BigUint, Error> {
+    let bytes =
+        base64::decode_config(encoded, base64::URL_SAFE_NO_PAD).map_err(|_| Error::InvalidJwk)?;
+    Ok(BigUint::from_bytes_be(&bytes))
+}
+
+fn check_private_key(key: &RsaPrivateKey) -> Result<(), Error> {
+    if key.n().bits() != KEY_BITS || key.validate().is_err() {
+        return Err(Error::InvalidPrivateKey);
+    }
+    Ok(())
+}
+
+fn parse_private_key(der: &[u8]) -> Result<RsaPrivateKey, Error> {
+    let key = RsaPrivateKey::from_pkcs1_der(der).map_err(|_| Error::InvalidPrivateKey)?;
+    check_private_key(&key)?;
+    Ok(key)
+}
+
+fn parse_public_key(modulus: &[u8]) -> Result<RsaPublicKey, Error> {
+    if modulus.len() != MODULUS_SIZE {
+        return Err(Error::InvalidPublicKey);
+    }
+    // Arweave keys always use the public exponent 65537
+    RsaPublicKey::new(BigUint::from_bytes_be(modulus), BigUint::from(65537u32))
+        .map_err(|_| Error::InvalidPublicKey)
+}
+
+/// Whether `der` is a PKCS#1 DER encoded RSA-4096 private key
+pub fn is_valid_private_key(der: &[u8]) -> bool {
+    parse_private_key(der).is_ok()
+}
+
+/// The big-endian modulus of the PKCS#1 DER encoded private key, which is used as its public key
+pub fn get_public_key(der: &[u8]) -> Result<Vec<u8>, Error> {
+    let key = parse_private_key(der)?;
+    let modulus = key.n().to_bytes_be();
+    let mut public_key = vec![0u8; MODULUS_SIZE - modulus.len()];
+    public_key.extend(modulus);
+    Ok(public_key)
+}
+
+/// RSA-PSS signature of `message` with SHA-256 and MGF1
+pub fn sign(der: &[u8], message: &[u8]) -> Result<Vec<u8>, Error> {
+    let key = parse_private_key(der)?;
+    let padding = PaddingScheme::new_pss_with_salt::<Sha256, _>(OsRng, PSS_SALT_LENGTH);
+    key.sign_blinded(&mut OsRng, padding, &Sha256::digest(message))
+        .map_err(|_| Error::InvalidPrivateKey)
+}
+
+/// Verify the RSA-PSS `signature` of `message` by the key of `modulus`
+pub fn verify(modulus: &[u8], message: &[u8], signature: &[u8]) -> bool {
+    let key = match parse_public_key(modulus) {
+        Ok(key) => key,
+        Err(_) => return false,
+    };
+    let padding = PaddingScheme::new_pss::<Sha256, _>(OsRng);
+    key.verify(padding, &Sha256::digest(message), signature)
+        .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JWK: &str = include_str!("../resource/test_rsa_key.json");
+
+    #[test]
+    fn test_jwk_to_der() {
+        let der = Jwk::from_str(JWK).unwrap().to_der().unwrap();
+        assert!(is_valid_private_key(&der));
+        let public_key = get_public_key(&der).unwrap();
+        assert_eq!(public_key.len(), MODULUS_SIZE);
+        let jwk: Jwk = serde_json::from_str(JWK).unwrap();
+        assert_eq!(
+            base64::encode_config(&public_key, base64::URL_SAFE_NO_PAD),
+            jwk.n
+        );
+
+        assert_eq!(Jwk::from_str("{}").err(), Some(Error::InvalidJwk));
+        let mut tampered = Jwk::from_str(JWK).unwrap();
+        tampered.d = tampered.e.clone();
+        assert_eq!(tampered.to_der().err(), Some(Error::InvalidJwk));
+        assert!(!is_valid_private_key(&[1u8; 32]));
+        assert!(!is_valid_private_key(&der[..der.len() - 1]));
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let der = Jwk::from_str(JWK).unwrap().to_der().unwrap();
+        let public_key = get_public_key(&der).unwrap();
+        let signature = sign(&der, b"message").unwrap();
+        assert_eq!(signature.len(), MODULUS_SIZE);
+        assert!(verify(&public_key, b"message", &signature));
+        assert!(!verify(&public_key, b"another message", &signature));
+        assert!(!verify(&public_key[1..], b"message", &signature));
+    }
+}
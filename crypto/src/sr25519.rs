@@ -0,0 +1,356 @@
+use super::bip32::HdNode;
+use super::curve::Curve;
+use super::hash::{Blake2b256, Hasher};
+use crate::Error;
+use hmac::Hmac;
+use schnorrkel::derive::{ChainCode, Derivation};
+use schnorrkel::{ExpansionMode, MiniSecretKey, PublicKey, SecretKey, Signature};
+use sha2::Sha512;
+use std::convert::TryInto;
+use std::str::FromStr;
+use zeroize::Zeroizing;
+
+const JUNCTION_ID_SIZE: usize = 32;
+// Signing context of Substrate's sr25519 signatures
+const SIGNING_CONTEXT: &[u8] = b"substrate";
+const PASSWORD_SEPARATOR: &str = "///";
+
+// SCALE compact encoding of a length
+fn encode_length(len: usize) -> Vec<u8> {
+    match len {
+        0..=0x3f => vec![(len as u8) << 2],
+        0x40..=0x3fff => (((len as u16) << 2) | 0b01).to_le_bytes().to_vec(),
+        _ => (((len as u32) << 2) | 0b10).to_le_bytes().to_vec(),
+    }
+}
+
+/// A single step of a Substrate derivation path, `//code` if hard and `/code` if soft
+#[derive(Debug, PartialEq)]
+pub struct Junction {
+    pub code: String,
+    pub hard: bool,
+}
+
+impl Junction {
+    pub fn chain_code(&self) -> [u8; JUNCTION_ID_SIZE] {
+        // Numeric junctions are encoded as u64, any other as a SCALE encoded string
+        let encoded = match self.code.parse::<u64>() {
+            Ok(index) => index.to_le_bytes().to_vec(),
+            Err(_) => [&encode_length(self.code.len())[..], self.code.as_bytes()].concat(),
+        };
+        let mut id = [0u8; JUNCTION_ID_SIZE];
+        if encoded.len() > JUNCTION_ID_SIZE {
+            let hash = Hasher::hash(Blake2b256, &encoded).expect("blake2b never fails");
+            id.copy_from_slice(&hash);
+        } else {
+            id[..encoded.len()].copy_from_slice(&encoded);
+        }
+        id
+    }
+}
+
+impl ToString for Junction {
+    fn to_string(&self) -> String {
+        match self.hard {
+            true => format!("//{}", self.code),
+            false => format!("/{}", self.code),
+        }
+    }
+}
+
+/// A Substrate derivation path like `//hard/soft///password`, the empty path is the root key
+#[derive(Debug, PartialEq)]
+pub struct JunctionPath {
+    pub junctions: Vec<Junction>,
+    pub password: Option<String>,
+}
+
+impl JunctionPath {
+    /// Whether `path` is written in the junction syntax rather than as a BIP32 path
+    pub fn is_junction_path(path: &str) -> bool {
+        path.is_empty() || path.starts_with('/')
+    }
+}
+
+impl FromStr for JunctionPath {
+    type Err = Error;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        let (mut rest, password) = match path.find(PASSWORD_SEPARATOR) {
+            Some(index) => (
+                &path[..index],
+                Some(path[index + PASSWORD_SEPARATOR.len()..].to_owned()),
+            ),
+            None => (path, None),
+        };
+        let mut junctions = vec![];
+        while !rest.is_empty() {
+            rest = rest.strip_prefix('/').ok_or(Error::InvalidDerivationpath)?;
+            let hard = rest.starts_with('/');
+            if hard {
+                rest = &rest[1..];
+            }
+            let end = rest.find('/').unwrap_or(rest.len());
+            if end == 0 {
+                return Err(Error::InvalidDerivationpath);
+            }
+            junctions.push(Junction {
+                code: rest[..end].to_owned(),
+                hard,
+            });
+            rest = &rest[end..];
+        }
+        Ok(JunctionPath {
+            junctions,
+            password,
+        })
+    }
+}
+
+impl ToString for JunctionPath {
+    fn to_string(&self) -> String {
+        let mut path: String = self.junctions.iter().map(|x| x.to_string()).collect();
+        if let Some(password) = &self.password {
+            path.push_str(PASSWORD_SEPARATOR);
+            path.push_str(password);
+        }
+        path
+    }
+}
+
+/// The mini secret key of a BIP39 `entropy`, derived the same way as substrate-bip39
+pub fn mini_secret_from_entropy(entropy: &[u8], password: &str) -> Result<MiniSecretKey, Error> {
+    if entropy.len() < 16 || entropy.len() > 32 || !entropy.len().is_multiple_of(4) {
+        return Err(Error::InvalidMnemonic);
+    }
+    let salt = Zeroizing::new(format!("mnemonic{}", password));
+    let mut seed = Zeroizing::new([0u8; 64]);
+    pbkdf2::pbkdf2::<Hmac<Sha512>>(entropy, salt.as_bytes(), 2048, seed.as_mut());
+    MiniSecretKey::from_bytes(&seed[..32]).map_err(|_| Error::InvalidSeed)
+}
+
+/// Derive the sr25519 key of `path` from a BIP39 `entropy`
+///
+/// The private key and nonce are stored in `private_key_bytes` and `private_key_extension`
+/// in the 64-byte format used by polkadot.js. The password of the path takes precedence
+/// over `password`.
+pub fn get_node(entropy: &[u8], password: &str, path: &str) -> Result<HdNode, Error> {
+    let path = JunctionPath::from_str(path)?;
+    let password = path.password.as_deref().unwrap_or(password);
+    let mut secret = mini_secret_from_entropy(entropy, password)?.expand(ExpansionMode::Ed25519);
+    let mut chain_code = [0u8; JUNCTION_ID_SIZE];
+    for junction in &path.junctions {
+        chain_code = junction.chain_code();
+        secret = match junction.hard {
+            true => secret
+                .hard_derive_mini_secret_key(Some(ChainCode(chain_code)), b"")
+                .0
+                .expand(ExpansionMode::Ed25519),
+            false => secret.derived_key_simple(ChainCode(chain_code), []).0,
+        };
+    }
+    let secret_bytes = secret.to_ed25519_bytes();
+    Ok(HdNode {
+        depth: path.junctions.len() as u8,
+        child_num: 0,
+        chain_code,
+        private_key_bytes: secret_bytes[..32].try_into().unwrap(),
+        private_key_extension: secret_bytes[32..].try_into().unwrap(),
+        public_key_bytes: [0u8; 33],
+        curve: Curve::Sr25519,
+    })
+}
+
+// A private key without nonce is the 32-byte seed of the key, aka the mini secret key
+fn get_secret_key(private_key: &[u8], nonce: &[u8]) -> Result<SecretKey, Error> {
+    match nonce.is_empty() {
+        true => MiniSecretKey::from_bytes(private_key)
+            .map(|x| x.expand(ExpansionMode::Ed25519))
+            .map_err(|_| Error::InvalidPrivateKey),
+        false => SecretKey::from_ed25519_bytes(&[private_key, nonce].concat())
+            .map_err(|_| Error::InvalidPrivateKey),
+    }
+}
+
+pub fn get_public_key(private_key: &[u8], nonce: &[u8]) -> Result<Vec<u8>, Error> {
+    let secret = get_secret_key(private_key, nonce)?;
+    Ok(secret.to_public().to_bytes().to_vec())
+}
+
+/// Sign `message` in the signing context of Substrate
+pub fn sign(private_key: &[u8], nonce: &[u8], message: &[u8]) -> Result<Vec<u8>, Error> {
+    let secret = get_secret_key(private_key, nonce)?;
+    let signature = secret.sign_simple(SIGNING_CONTEXT, message, &secret.to_public());
+    Ok(signature.to_bytes().to_vec())
+}
+
+pub fn verify(public_key: &[u8], message: &[u8], signature: &[u8]) -> bool {
+    let public_key = match PublicKey::from_bytes(public_key) {
+        Ok(public_key) => public_key,
+        Err(_) => return false,
+    };
+    match Signature::from_bytes(signature) {
+        Ok(signature) => public_key
+            .verify_simple(SIGNING_CONTEXT, message, &signature)
+            .is_ok(),
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bip39::Mnemonic;
+
+    const DEV_PHRASE: &str =
+        "bottom drive obey lake curtain smoke basket hold race lonely fit walk";
+
+    fn derive_public_key(path: &str) -> String {
+        let mnemonic = Mnemonic::new(DEV_PHRASE, "").unwrap();
+        let node = get_node(&mnemonic.entropy, "", path).unwrap();
+        let public_key =
+            get_public_key(&node.private_key_bytes, &node.private_key_extension).unwrap();
+        hex::encode(public_key)
+    }
+
+    #[test]
+    fn test_parse_junction_path() {
+        let path = JunctionPath::from_str("//polkadot//0/1///pass/word").unwrap();
+        assert_eq!(
+            path.junctions,
+            vec![
+                Junction {
+                    code: "polkadot".to_owned(),
+                    hard: true
+                },
+                Junction {
+                    code: "0".to_owned(),
+                    hard: true
+                },
+                Junction {
+                    code: "1".to_owned(),
+                    hard: false
+                },
+            ]
+        );
+        assert_eq!(path.password, Some("pass/word".to_owned()));
+        assert_eq!(path.to_string(), "//polkadot//0/1///pass/word");
+
+        let root = JunctionPath::from_str("").unwrap();
+        assert!(root.junctions.is_empty());
+        assert_eq!(root.password, None);
+
+        assert!(JunctionPath::from_str("m/44'/354'").is_err());
+        assert!(JunctionPath::from_str("//polkadot/").is_err());
+        assert!(JunctionPath::from_str("polkadot").is_err());
+    }
+
+    #[test]
+    fn test_junction_chain_code() {
+        let numeric = Junction {
+            code: "1".to_owned(),
+            hard: false,
+        };
+        assert_eq!(
+            hex::encode(numeric.chain_code()),
+            "0100000000000000000000000000000000000000000000000000000000000000"
+        );
+        let name = Junction {
+            code: "Alice".to_owned(),
+            hard: true,
+        };
+        assert_eq!(
+            hex::encode(name.chain_code()),
+            "14416c6963650000000000000000000000000000000000000000000000000000"
+        );
+    }
+
+    #[test]
+    fn test_derive_from_entropy() {
+        let cases = [
+            (
+                "",
+                "46ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a",
+            ),
+            (
+                "//Alice",
+                "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d",
+            ),
+            (
+                "//Bob",
+                "8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48",
+            ),
+            (
+                "//Alice/soft",
+                "02cfd83074aefc9955af4034d19b3780d47a52e158ababec8ec012b2295f1c5b",
+            ),
+            (
+                "/1",
+                "328b984202c3c1c834b9c5973a0e8955c4f60c6e3c100503c77d4ad3b9328639",
+            ),
+            (
+                "//polkadot//0/1",
+                "fa91d646cb0e8d8ca57eb780ee4c7f4e8803b575c0cb1185277f42915745b459",
+            ),
+            (
+                "//a-very-long-junction-name-longer-than-thirty-two-bytes",
+                "d8dadcf48f5767ffd33f494997a5c7514a7f0accd199b55651b7fae8f9aec937",
+            ),
+            (
+                "//Alice///secret",
+                "08a5e583f74f54f3811cb5f7d74e686d473e3a466fd0e95738707a80c3183b15",
+            ),
+        ];
+        for (path, expected) in cases.iter() {
+            assert_eq!(derive_public_key(path), *expected, "path {}", path);
+        }
+    }
+
+    #[test]
+    fn test_password() {
+        let mnemonic = Mnemonic::new(DEV_PHRASE, "").unwrap();
+        let node = get_node(&mnemonic.entropy, "secret", "").unwrap();
+        let public_key =
+            get_public_key(&node.private_key_bytes, &node.private_key_extension).unwrap();
+        assert_eq!(
+            hex::encode(public_key),
+            "e828382f645685df1e292bb59c5cef182ad88be0be56568e24c73a69f8a7094e"
+        );
+        // The password of the path takes precedence
+        assert_eq!(
+            derive_public_key("///secret"),
+            "e828382f645685df1e292bb59c5cef182ad88be0be56568e24c73a69f8a7094e"
+        );
+    }
+
+    #[test]
+    fn test_public_key_of_seed() {
+        // Seed of `//Alice`
+        let seed = hex::decode("e5be9a5092b81bca64be81d212e7f2f9eba183bb7a90954f7b76361f6edb5c0a")
+            .unwrap();
+        let public_key = get_public_key(&seed, &[]).unwrap();
+        assert_eq!(
+            hex::encode(public_key),
+            "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"
+        );
+        assert!(get_public_key(&seed[1..], &[]).is_err());
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let mnemonic = Mnemonic::new(DEV_PHRASE, "").unwrap();
+        let node = get_node(&mnemonic.entropy, "", "//Alice").unwrap();
+        let public_key =
+            get_public_key(&node.private_key_bytes, &node.private_key_extension).unwrap();
+        let message = b"mask wallet";
+        let signature = sign(
+            &node.private_key_bytes,
+            &node.private_key_extension,
+            message,
+        )
+        .unwrap();
+        assert_eq!(signature.len(), 64);
+        assert!(verify(&public_key, message, &signature));
+        assert!(!verify(&public_key, b"another message", &signature));
+    }
+}
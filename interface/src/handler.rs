@@ -14,24 +14,35 @@ pub fn dispatch_request(request: mw_request::Request) -> MwResponse {
         ParamImportPrivateKey(param) => stored_key::create_stored_key_with_private_key(param),
         ParamImportMnemonic(param) => stored_key::create_stored_key_with_mnemonic(param),
         ParamImportJson(param) => stored_key::create_stored_key_with_json(param),
+        ParamImportJwk(param) => stored_key::create_stored_key_with_jwk(param),
+        ParamImportWatchOnly(param) => stored_key::create_watch_only_stored_key(param),
         ParamExportPrivateKey(param) => stored_key::export_private_key(param),
         ParamExportPrivateKeyOfPath(param) => stored_key::export_private_key_of_path(param),
         ParamExportMnemonic(param) => stored_key::export_mnemonic(param),
+        ParamGetExtendedPublicKey(param) => stored_key::export_extended_public_key(param),
         ParamExportKeyStoreJsonOfAddress(param) => {
             stored_key::export_key_store_json_of_address(param)
         }
         ParamExportKeyStoreJsonOfPath(param) => stored_key::export_key_store_json_of_path(param),
         ParamUpdateKeyStorePassword(param) => stored_key::update_key_store_password(param),
+        ParamRekeyStoredKey(param) => stored_key::rekey_stored_key(param),
         ParamGetStoredKeyImportType(param) => stored_key::get_supported_import_types(param),
         ParamGetStoredKeyExportType(param) => stored_key::get_supported_export_types(param),
 
         ParamCreateAccountOfCoinAtPath(param) => {
             account::create_stored_key_account_of_coin_at_path(param)
         }
+        ParamGetStoredKeyAccounts(param) => account::get_stored_key_accounts(param),
+        ParamRemoveAccount(param) => account::remove_stored_key_account(param),
+        ParamRenameAccount(param) => account::rename_stored_key_account(param),
         ParamSignTransaction(param) => sign::sign_transaction(param),
+        ParamSignMessage(param) => sign::sign_message(param),
+        ParamVerifyMessage(param) => sign::verify_message(param),
+        ParamSignTypedData(param) => sign::sign_typed_data(param),
+        ParamDecodeTransaction(param) => sign::decode_transaction(param),
 
         ParamValidation(param) => validate::validate(param),
 
-        ParamGenerateMnemonic(_) => common::generate_mnemonic(),
+        ParamGenerateMnemonic(param) => common::generate_mnemonic(param),
     }
 }
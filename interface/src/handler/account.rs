@@ -19,10 +19,10 @@ pub fn create_stored_key_account_of_coin_at_path(
             };
         }
     };
-    let stored_key: StoredKey = match serde_json::from_slice(&param.stored_key_data) {
+    let mut stored_key = match StoredKey::from_json(&param.stored_key_data) {
         Ok(key) => key,
-        Err(_) => {
-            return get_json_error_response();
+        Err(error) => {
+            return get_load_stored_key_error_response(error);
         }
     };
     let account = match stored_key.add_new_account_of_coin_and_derivation_path_by_password(
@@ -45,3 +45,106 @@ pub fn create_stored_key_account_of_coin_at_path(
         )),
     }
 }
+
+pub fn get_stored_key_accounts(param: GetStoredKeyAccountsParam) -> MwResponse {
+    let coins_info: Option<Vec<_>> = param
+        .coins
+        .iter()
+        .map(|coin| get_coin_info(*coin))
+        .collect();
+    let coins = match coins_info {
+        Some(coins) => coins,
+        None => {
+            return MwResponse {
+                response: Some(Response::Error(MwResponseError {
+                    error_code: "-1".to_owned(),
+                    error_msg: "Invalid Coin Type".to_owned(),
+                })),
+            };
+        }
+    };
+    let stored_key = match StoredKey::from_json(&param.stored_key_data) {
+        Ok(key) => key,
+        Err(error) => {
+            return get_load_stored_key_error_response(error);
+        }
+    };
+    let accounts = match stored_key.get_accounts(&coins, &param.derivation_path, &param.address) {
+        Ok(accounts) => accounts,
+        Err(error) => {
+            return get_error_response_by_error(error);
+        }
+    };
+    MwResponse {
+        response: Some(Response::RespGetStoredKeyAccounts(
+            GetStoredKeyAccountsResp {
+                accounts: accounts
+                    .into_iter()
+                    .map(StoredKeyAccountInfo::from)
+                    .collect(),
+            },
+        )),
+    }
+}
+
+pub fn remove_stored_key_account(param: RemoveStoredKeyAccountParam) -> MwResponse {
+    let coin_info = get_coin_info(param.coin);
+    let coin = match coin_info {
+        Some(coin_info) => coin_info,
+        None => {
+            return MwResponse {
+                response: Some(Response::Error(MwResponseError {
+                    error_code: "-1".to_owned(),
+                    error_msg: "Invalid Coin Type".to_owned(),
+                })),
+            };
+        }
+    };
+    let mut stored_key = match StoredKey::from_json(&param.stored_key_data) {
+        Ok(key) => key,
+        Err(error) => {
+            return get_load_stored_key_error_response(error);
+        }
+    };
+    if let Err(error) = stored_key.remove_account(&param.address, coin) {
+        return get_error_response_by_error(error);
+    }
+    MwResponse {
+        response: Some(Response::RespRemoveAccount(RemoveStoredKeyAccountResp {
+            stored_key: Some(StoredKeyInfo::from(stored_key)),
+        })),
+    }
+}
+
+pub fn rename_stored_key_account(param: RenameStoredKeyAccountParam) -> MwResponse {
+    let coin_info = get_coin_info(param.coin);
+    let coin = match coin_info {
+        Some(coin_info) => coin_info,
+        None => {
+            return MwResponse {
+                response: Some(Response::Error(MwResponseError {
+                    error_code: "-1".to_owned(),
+                    error_msg: "Invalid Coin Type".to_owned(),
+                })),
+            };
+        }
+    };
+    let mut stored_key = match StoredKey::from_json(&param.stored_key_data) {
+        Ok(key) => key,
+        Err(error) => {
+            return get_load_stored_key_error_response(error);
+        }
+    };
+    let account = match stored_key.rename_account(&param.address, coin, &param.name) {
+        Ok(account) => account,
+        Err(error) => {
+            return get_error_response_by_error(error);
+        }
+    };
+    MwResponse {
+        response: Some(Response::RespRenameAccount(RenameStoredKeyAccountResp {
+            account: Some(StoredKeyAccountInfo::from(&account)),
+            stored_key: Some(StoredKeyInfo::from(stored_key)),
+        })),
+    }
+}
@@ -1,7 +1,9 @@
 use crate::response_util::*;
 use chain_common::api::mw_response::Response;
 use chain_common::api::*;
-use crypto::bip39::Mnemonic;
+use crypto::bip32::ExtendedPublicKeyVersion as CryptoExtendedPublicKeyVersion;
+use crypto::bip39::{Language, Mnemonic};
+use crypto::kdf_params::KdfPolicy as CryptoKdfPolicy;
 use std::env;
 
 const VERSION: &str = include_str!(concat!(env!("OUT_DIR"), "/VERSION"));
@@ -14,8 +16,70 @@ pub fn get_lib_version() -> MwResponse {
     }
 }
 
-pub fn generate_mnemonic() -> MwResponse {
-    let mnemonic = match Mnemonic::generate_mnemonic_string(12) {
+// Word count of the generated mnemonics if it's not specified
+const DEFAULT_WORD_COUNT: u32 = 12;
+
+pub fn get_mnemonic_word_count(word_count: u32) -> u32 {
+    match word_count {
+        0 => DEFAULT_WORD_COUNT,
+        count => count,
+    }
+}
+
+/// The wordlist selected by `language`, None for `AutoDetect`
+pub fn get_mnemonic_language(language: MnemonicLanguage) -> Option<Language> {
+    match language {
+        MnemonicLanguage::AutoDetect => None,
+        MnemonicLanguage::English => Some(Language::English),
+        MnemonicLanguage::ChineseSimplified => Some(Language::SimplifiedChinese),
+        MnemonicLanguage::ChineseTraditional => Some(Language::TraditionalChinese),
+        MnemonicLanguage::Czech => Some(Language::Czech),
+        MnemonicLanguage::French => Some(Language::French),
+        MnemonicLanguage::Italian => Some(Language::Italian),
+        MnemonicLanguage::Japanese => Some(Language::Japanese),
+        MnemonicLanguage::Korean => Some(Language::Korean),
+        MnemonicLanguage::Spanish => Some(Language::Spanish),
+    }
+}
+
+pub fn get_extended_public_key_version(
+    version: ExtendedPublicKeyVersion,
+) -> Option<CryptoExtendedPublicKeyVersion> {
+    match version {
+        ExtendedPublicKeyVersion::DefaultVersion => None,
+        ExtendedPublicKeyVersion::Xpub => Some(CryptoExtendedPublicKeyVersion::Xpub),
+        ExtendedPublicKeyVersion::Ypub => Some(CryptoExtendedPublicKeyVersion::Ypub),
+        ExtendedPublicKeyVersion::Zpub => Some(CryptoExtendedPublicKeyVersion::Zpub),
+        ExtendedPublicKeyVersion::Tpub => Some(CryptoExtendedPublicKeyVersion::Tpub),
+        ExtendedPublicKeyVersion::Upub => Some(CryptoExtendedPublicKeyVersion::Upub),
+        ExtendedPublicKeyVersion::Vpub => Some(CryptoExtendedPublicKeyVersion::Vpub),
+    }
+}
+
+/// The kdf policy of a request, the default policy is used if it's not set
+pub fn get_kdf_policy(kdf_policy: Option<KdfPolicy>) -> CryptoKdfPolicy {
+    match kdf_policy.and_then(|kdf_policy| kdf_policy.policy) {
+        Some(kdf_policy::Policy::Argon2id(policy)) => CryptoKdfPolicy::Argon2id {
+            m_cost: policy.memory_cost,
+            t_cost: policy.time_cost,
+            p_cost: policy.parallelism,
+        },
+        Some(kdf_policy::Policy::Scrypt(policy)) => CryptoKdfPolicy::Scrypt {
+            n: policy.n,
+            r: policy.r,
+            p: policy.p,
+        },
+        None => CryptoKdfPolicy::default(),
+    }
+}
+
+pub fn generate_mnemonic(param: GenerateMnemonicParam) -> MwResponse {
+    let language = match MnemonicLanguage::from_i32(param.language) {
+        Some(language) => get_mnemonic_language(language).unwrap_or(Language::English),
+        None => return get_invalid_proto_resposne(),
+    };
+    let word_count = get_mnemonic_word_count(param.word_count);
+    let mnemonic = match Mnemonic::generate_mnemonic_string_in(language, word_count) {
         Ok(mnemonic) => mnemonic,
         Err(_) => {
             return get_json_error_response();
@@ -3,8 +3,9 @@ use crate::encode_message;
 use crate::response_util::*;
 use chain_common::api::mw_response::Response;
 use chain_common::api::*;
-use chain_common::ethereum;
+use chain_common::{arweave, bitcoin, ethereum, polkadot, solana};
 use prost::Message;
+use wallet::coin_dispatcher::CoinDispatcher;
 use wallet::stored_key::StoredKey;
 
 pub fn sign_transaction(param: SignTransactionParam) -> MwResponse {
@@ -20,12 +21,6 @@ pub fn sign_transaction(param: SignTransactionParam) -> MwResponse {
             };
         }
     };
-    let mut stored_key: StoredKey = match serde_json::from_slice(&param.stored_key_data) {
-        Ok(key) => key,
-        Err(_) => {
-            return get_json_error_response();
-        }
-    };
     let input_struct = match param.input {
         Some(input) => input,
         None => {
@@ -37,8 +32,34 @@ pub fn sign_transaction(param: SignTransactionParam) -> MwResponse {
             };
         }
     };
-    let sign_transaction_param::Input::SignInput(chain_input) = input_struct;
-    let encoded_input = match encode_message(&chain_input) {
+    // The input is encoded for, and the output decoded from, the chain of the input,
+    // which must be the chain of the coin
+    let input_coin = match &input_struct {
+        sign_transaction_param::Input::SignInput(_) => Coin::Ethereum,
+        sign_transaction_param::Input::SolanaSignInput(_) => Coin::Solana,
+        sign_transaction_param::Input::PolkadotSignInput(_) => Coin::Polkadot,
+        sign_transaction_param::Input::BitcoinSignInput(_) => Coin::Bitcoin,
+        sign_transaction_param::Input::ArweaveSignInput(_) => Coin::Arweave,
+    };
+    if input_coin as i32 != param.coin {
+        return get_invalid_proto_resposne();
+    }
+    let stored_key = match StoredKey::from_json(&param.stored_key_data) {
+        Ok(key) => key,
+        Err(error) => {
+            return get_load_stored_key_error_response(error);
+        }
+    };
+    let encoded_input_result = match &input_struct {
+        sign_transaction_param::Input::SignInput(chain_input) => encode_message(chain_input),
+        sign_transaction_param::Input::SolanaSignInput(chain_input) => encode_message(chain_input),
+        sign_transaction_param::Input::PolkadotSignInput(chain_input) => {
+            encode_message(chain_input)
+        }
+        sign_transaction_param::Input::BitcoinSignInput(chain_input) => encode_message(chain_input),
+        sign_transaction_param::Input::ArweaveSignInput(chain_input) => encode_message(chain_input),
+    };
+    let encoded_input = match encoded_input_result {
         Ok(encoded) => encoded,
         Err(_) => {
             return MwResponse {
@@ -61,8 +82,28 @@ pub fn sign_transaction(param: SignTransactionParam) -> MwResponse {
         }
     };
 
-    let ethereum::SignInput { .. } = chain_input;
-    let decoded_output_result = ethereum::SignOutput::decode(&sign_output[..]);
+    let decoded_output_result = match input_struct {
+        sign_transaction_param::Input::SignInput(_) => {
+            ethereum::SignOutput::decode(&sign_output[..])
+                .map(sign_transaction_resp::Output::SignOutput)
+        }
+        sign_transaction_param::Input::SolanaSignInput(_) => {
+            solana::SignOutput::decode(&sign_output[..])
+                .map(sign_transaction_resp::Output::SolanaSignOutput)
+        }
+        sign_transaction_param::Input::PolkadotSignInput(_) => {
+            polkadot::SignOutput::decode(&sign_output[..])
+                .map(sign_transaction_resp::Output::PolkadotSignOutput)
+        }
+        sign_transaction_param::Input::BitcoinSignInput(_) => {
+            bitcoin::SignOutput::decode(&sign_output[..])
+                .map(sign_transaction_resp::Output::BitcoinSignOutput)
+        }
+        sign_transaction_param::Input::ArweaveSignInput(_) => {
+            arweave::SignOutput::decode(&sign_output[..])
+                .map(sign_transaction_resp::Output::ArweaveSignOutput)
+        }
+    };
 
     let decoded_output = match decoded_output_result {
         Ok(decoded) => decoded,
@@ -78,7 +119,161 @@ pub fn sign_transaction(param: SignTransactionParam) -> MwResponse {
 
     MwResponse {
         response: Some(Response::RespSignTransaction(SignTransactionResp {
-            output: Some(sign_transaction_resp::Output::SignOutput(decoded_output)),
+            output: Some(decoded_output),
         })),
     }
 }
+
+pub fn sign_message(param: SignMessageParam) -> MwResponse {
+    let coin_info = get_coin_info(param.coin);
+    let coin = match coin_info {
+        Some(coin_info) => coin_info,
+        None => {
+            return MwResponse {
+                response: Some(Response::Error(MwResponseError {
+                    error_code: "-1".to_owned(),
+                    error_msg: "Invalid Coin Type".to_owned(),
+                })),
+            };
+        }
+    };
+    let stored_key = match StoredKey::from_json(&param.stored_key_data) {
+        Ok(key) => key,
+        Err(error) => {
+            return get_load_stored_key_error_response(error);
+        }
+    };
+    match stored_key.sign_message(
+        coin,
+        &param.password,
+        &param.derivation_path,
+        &param.message,
+        param.raw_hash,
+    ) {
+        Ok(signature) => MwResponse {
+            response: Some(Response::RespSignMessage(SignMessageResp { signature })),
+        },
+        Err(error) => get_error_response_by_error(error),
+    }
+}
+
+pub fn verify_message(param: VerifyMessageParam) -> MwResponse {
+    let coin_info = get_coin_info(param.coin);
+    let coin = match coin_info {
+        Some(coin_info) => coin_info,
+        None => {
+            return MwResponse {
+                response: Some(Response::Error(MwResponseError {
+                    error_code: "-1".to_owned(),
+                    error_msg: "Invalid Coin Type".to_owned(),
+                })),
+            };
+        }
+    };
+    let signer = CoinDispatcher::get_entry(coin).and_then(|entry| {
+        Ok(entry.recover_message_signer(coin, &param.message, &param.signature, param.raw_hash)?)
+    });
+    match signer {
+        Ok(signer) => MwResponse {
+            response: Some(Response::RespVerifyMessage(VerifyMessageResp {
+                valid: signer.eq_ignore_ascii_case(&param.address),
+                signer,
+            })),
+        },
+        Err(error) => get_error_response_by_error(error),
+    }
+}
+
+pub fn sign_typed_data(param: SignTypedDataParam) -> MwResponse {
+    let coin_info = get_coin_info(param.coin);
+    let coin = match coin_info {
+        Some(coin_info) => coin_info,
+        None => {
+            return MwResponse {
+                response: Some(Response::Error(MwResponseError {
+                    error_code: "-1".to_owned(),
+                    error_msg: "Invalid Coin Type".to_owned(),
+                })),
+            };
+        }
+    };
+    let stored_key = match StoredKey::from_json(&param.stored_key_data) {
+        Ok(key) => key,
+        Err(error) => {
+            return get_load_stored_key_error_response(error);
+        }
+    };
+    match stored_key.sign_typed_data(
+        coin,
+        &param.password,
+        &param.derivation_path,
+        &param.typed_data,
+    ) {
+        Ok(signature) => MwResponse {
+            response: Some(Response::RespSignTypedData(SignTypedDataResp { signature })),
+        },
+        Err(error) => get_error_response_by_error(error),
+    }
+}
+
+pub fn decode_transaction(param: DecodeTransactionParam) -> MwResponse {
+    let coin_info = get_coin_info(param.coin);
+    let coin = match coin_info {
+        Some(coin_info) => coin_info,
+        None => {
+            return MwResponse {
+                response: Some(Response::Error(MwResponseError {
+                    error_code: "-1".to_owned(),
+                    error_msg: "Invalid Coin Type".to_owned(),
+                })),
+            };
+        }
+    };
+    let decode_output = CoinDispatcher::get_entry(coin)
+        .and_then(|entry| Ok(entry.decode_transaction(coin, &param.data, param.chain_id)?));
+    let decode_output = match decode_output {
+        Ok(decode_output) => decode_output,
+        Err(error) => {
+            return get_error_response_by_error(error);
+        }
+    };
+    // Only the coins decoding transactions have outputs
+    let decoded_output_result = match Coin::from_i32(param.coin) {
+        Some(Coin::Ethereum) => ethereum::DecodeOutput::decode(&decode_output[..])
+            .map(decode_transaction_resp::Output::EthereumDecodeOutput)
+            .ok(),
+        _ => None,
+    };
+    match decoded_output_result {
+        Some(decoded_output) => MwResponse {
+            response: Some(Response::RespDecodeTransaction(DecodeTransactionResp {
+                output: Some(decoded_output),
+            })),
+        },
+        None => MwResponse {
+            response: Some(Response::Error(MwResponseError {
+                error_code: "-1".to_owned(),
+                error_msg: "Invalid decode output".to_owned(),
+            })),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_input_of_another_coin() {
+        let param = SignTransactionParam {
+            stored_key_data: vec![],
+            derivation_path: "m/44'/501'/0'".to_owned(),
+            password: "".to_owned(),
+            coin: Coin::Solana as i32,
+            input: Some(sign_transaction_param::Input::SignInput(
+                ethereum::SignInput::default(),
+            )),
+        };
+        assert_eq!(sign_transaction(param), get_invalid_proto_resposne());
+    }
+}
@@ -1,15 +1,23 @@
+use super::common::{
+    get_extended_public_key_version, get_kdf_policy, get_mnemonic_language, get_mnemonic_word_count,
+};
 use crate::coins::get_coin_info;
 use crate::response_util::*;
 use chain_common::api::mw_response::Response;
 use chain_common::api::*;
+use crypto::bip39::Language;
+use crypto::kdf_params::KdfParams;
+use crypto::pbkdf2_params::Pbkdf2Params;
+use crypto::scrypt_params::ScryptParams;
 use wallet::coin_dispatcher::CoinDispatcher;
+use wallet::hd_wallet::ACCOUNT_DEPTH;
 use wallet::stored_key::StoredKey;
 
 pub fn load_stored_keys(param: LoadStoredKeyParam) -> MwResponse {
     let stored_keys_result: Result<Vec<StoredKey>, _> = param
         .data
         .iter()
-        .map(|json| serde_json::from_slice(&json))
+        .map(|json| StoredKey::from_json(json))
         .collect();
     match stored_keys_result {
         Ok(stored_keys) => MwResponse {
@@ -17,12 +25,22 @@ pub fn load_stored_keys(param: LoadStoredKeyParam) -> MwResponse {
                 stored_keys: stored_keys.into_iter().map(StoredKeyInfo::from).collect(),
             })),
         },
-        Err(_) => get_json_error_response(),
+        Err(error) => get_load_stored_key_error_response(error),
     }
 }
 
 pub fn create_stored_key(param: CreateStoredKeyParam) -> MwResponse {
-    let (stored_key, mnemonic) = match StoredKey::create_with_mnemonic_random(&param.password) {
+    let language = match MnemonicLanguage::from_i32(param.language) {
+        Some(language) => get_mnemonic_language(language).unwrap_or(Language::English),
+        None => return get_invalid_proto_resposne(),
+    };
+    let (stored_key, mnemonic) = match StoredKey::create_with_mnemonic_random(
+        &param.password,
+        &param.passphrase,
+        get_mnemonic_word_count(param.word_count),
+        language,
+        get_kdf_policy(param.kdf_policy),
+    ) {
         Ok(key) => key,
         Err(error) => {
             return get_error_response_by_error(error);
@@ -49,8 +67,21 @@ pub fn create_stored_key_with_private_key(param: ImportPrivateStoredKeyParam) ->
             };
         }
     };
-    let stored_key =
-        StoredKey::create_with_private_key_and_coin(&param.password, &param.private_key, &coin);
+    let stored_key = StoredKey::create_with_private_key_and_coin(
+        &param.password,
+        &param.private_key,
+        coin,
+        get_kdf_policy(param.kdf_policy.clone()),
+    )
+    .and_then(|mut key| {
+        key.add_new_account_of_coin_and_derivation_path_by_password(
+            &param.name,
+            coin,
+            &coin.derivation_path,
+            &param.password,
+        )?;
+        Ok(key)
+    });
     match stored_key {
         Ok(key) => MwResponse {
             response: Some(Response::RespImportPrivateKey(ImportPrivateStoredKeyResp {
@@ -62,13 +93,22 @@ pub fn create_stored_key_with_private_key(param: ImportPrivateStoredKeyParam) ->
 }
 
 pub fn create_stored_key_with_mnemonic(param: ImportMnemonicStoredKeyParam) -> MwResponse {
-    let stored_key: StoredKey =
-        match StoredKey::create_with_mnemonic(&param.password, &param.mnemonic) {
-            Ok(key) => key,
-            Err(error) => {
-                return get_error_response_by_error(error);
-            }
-        };
+    let language = match MnemonicLanguage::from_i32(param.language) {
+        Some(language) => get_mnemonic_language(language),
+        None => return get_invalid_proto_resposne(),
+    };
+    let stored_key: StoredKey = match StoredKey::create_with_mnemonic_and_passphrase(
+        &param.password,
+        &param.mnemonic,
+        &param.passphrase,
+        language,
+        get_kdf_policy(param.kdf_policy),
+    ) {
+        Ok(key) => key,
+        Err(error) => {
+            return get_error_response_by_error(error);
+        }
+    };
     MwResponse {
         response: Some(Response::RespImportMnemonic(ImportMnemonicStoredKeyResp {
             stored_key: Some(StoredKeyInfo::from(stored_key)),
@@ -89,17 +129,26 @@ pub fn create_stored_key_with_json(param: ImportJsonStoredKeyParam) -> MwRespons
             };
         }
     };
-    let stored_key: StoredKey = match StoredKey::create_with_json(
+    let mut stored_key: StoredKey = match StoredKey::create_with_json(
         &param.key_store_json_password,
         &param.password,
         &param.json,
         coin,
+        get_kdf_policy(param.kdf_policy),
     ) {
         Ok(key) => key,
         Err(error) => {
             return get_error_response_by_error(error);
         }
     };
+    if let Err(error) = stored_key.add_new_account_of_coin_and_derivation_path_by_password(
+        &param.name,
+        coin,
+        &coin.derivation_path,
+        &param.password,
+    ) {
+        return get_error_response_by_error(error);
+    }
     MwResponse {
         response: Some(Response::RespImportJson(ImportJsonStoredKeyResp {
             stored_key: Some(StoredKeyInfo::from(stored_key)),
@@ -107,6 +156,58 @@ pub fn create_stored_key_with_json(param: ImportJsonStoredKeyParam) -> MwRespons
     }
 }
 
+pub fn create_stored_key_with_jwk(param: ImportJwkStoredKeyParam) -> MwResponse {
+    let stored_key: StoredKey = match StoredKey::create_with_jwk(
+        &param.password,
+        &param.jwk,
+        get_kdf_policy(param.kdf_policy),
+    ) {
+        Ok(key) => key,
+        Err(error) => {
+            return get_error_response_by_error(error);
+        }
+    };
+    MwResponse {
+        response: Some(Response::RespImportJwk(ImportJwkStoredKeyResp {
+            stored_key: Some(StoredKeyInfo::from(stored_key)),
+        })),
+    }
+}
+
+pub fn create_watch_only_stored_key(param: ImportWatchOnlyStoredKeyParam) -> MwResponse {
+    let coin_info = get_coin_info(param.coin);
+    let coin = match coin_info {
+        Some(coin_info) => coin_info,
+        None => {
+            return MwResponse {
+                response: Some(Response::Error(MwResponseError {
+                    error_code: "-1".to_owned(),
+                    error_msg: "Invalid Coin Type".to_owned(),
+                })),
+            };
+        }
+    };
+    let stored_key = if param.extended_public_key.is_empty() {
+        StoredKey::create_watch_only_with_public_key(&param.public_key, coin)
+    } else {
+        StoredKey::create_watch_only_with_extended_public_key(&param.extended_public_key)
+    }
+    .and_then(|mut key| {
+        key.add_new_account_of_coin_and_derivation_path_by_password(&param.name, coin, "", "")?;
+        Ok(key)
+    });
+    match stored_key {
+        Ok(key) => MwResponse {
+            response: Some(Response::RespImportWatchOnly(
+                ImportWatchOnlyStoredKeyResp {
+                    stored_key: Some(StoredKeyInfo::from(key)),
+                },
+            )),
+        },
+        Err(error) => get_error_response_by_error(error),
+    }
+}
+
 pub fn export_private_key(param: ExportKeyStorePrivateKeyParam) -> MwResponse {
     let coin_info = get_coin_info(param.coin);
     let coin = match coin_info {
@@ -120,10 +221,10 @@ pub fn export_private_key(param: ExportKeyStorePrivateKeyParam) -> MwResponse {
             };
         }
     };
-    let mut stored_key: StoredKey = match serde_json::from_slice(&param.stored_key_data) {
+    let stored_key = match StoredKey::from_json(&param.stored_key_data) {
         Ok(key) => key,
-        Err(_) => {
-            return get_json_error_response();
+        Err(error) => {
+            return get_load_stored_key_error_response(error);
         }
     };
     let private_key = match stored_key.export_private_key(&param.password, coin) {
@@ -134,7 +235,9 @@ pub fn export_private_key(param: ExportKeyStorePrivateKeyParam) -> MwResponse {
     };
     MwResponse {
         response: Some(Response::RespExportPrivateKey(
-            ExportKeyStorePrivateKeyResp { private_key },
+            ExportKeyStorePrivateKeyResp {
+                private_key: private_key.to_string(),
+            },
         )),
     }
 }
@@ -152,10 +255,10 @@ pub fn export_private_key_of_path(param: ExportKeyStorePrivateKeyOfPathParam) ->
             };
         }
     };
-    let mut stored_key: StoredKey = match serde_json::from_slice(&param.stored_key_data) {
+    let stored_key = match StoredKey::from_json(&param.stored_key_data) {
         Ok(key) => key,
-        Err(_) => {
-            return get_json_error_response();
+        Err(error) => {
+            return get_load_stored_key_error_response(error);
         }
     };
     let private_key = match stored_key.export_private_key_of_path(
@@ -170,16 +273,18 @@ pub fn export_private_key_of_path(param: ExportKeyStorePrivateKeyOfPathParam) ->
     };
     MwResponse {
         response: Some(Response::RespExportPrivateKey(
-            ExportKeyStorePrivateKeyResp { private_key },
+            ExportKeyStorePrivateKeyResp {
+                private_key: private_key.to_string(),
+            },
         )),
     }
 }
 
 pub fn export_mnemonic(param: ExportKeyStoreMnemonicParam) -> MwResponse {
-    let stored_key: StoredKey = match serde_json::from_slice(&param.stored_key_data) {
+    let stored_key = match StoredKey::from_json(&param.stored_key_data) {
         Ok(key) => key,
-        Err(_) => {
-            return get_json_error_response();
+        Err(error) => {
+            return get_load_stored_key_error_response(error);
         }
     };
     let mnemonic = match stored_key.export_mnemonic(&param.password) {
@@ -190,11 +295,63 @@ pub fn export_mnemonic(param: ExportKeyStoreMnemonicParam) -> MwResponse {
     };
     MwResponse {
         response: Some(Response::RespExportMnemonic(ExportKeyStoreMnemonicResp {
-            mnemonic,
+            mnemonic: mnemonic.to_string(),
         })),
     }
 }
 
+pub fn export_extended_public_key(param: GetExtendedPublicKeyParam) -> MwResponse {
+    let coin_info = get_coin_info(param.coin);
+    let coin = match coin_info {
+        Some(coin_info) => coin_info,
+        None => {
+            return MwResponse {
+                response: Some(Response::Error(MwResponseError {
+                    error_code: "-1".to_owned(),
+                    error_msg: "Invalid Coin Type".to_owned(),
+                })),
+            };
+        }
+    };
+    let version = match ExtendedPublicKeyVersion::from_i32(param.version) {
+        Some(version) => get_extended_public_key_version(version),
+        None => return get_invalid_proto_resposne(),
+    };
+    let stored_key = match StoredKey::from_json(&param.stored_key_data) {
+        Ok(key) => key,
+        Err(error) => {
+            return get_load_stored_key_error_response(error);
+        }
+    };
+    let derivation_path = match param.derivation_path.as_str() {
+        "" => &coin.derivation_path,
+        path => path,
+    };
+    let depth = match param.depth {
+        0 => ACCOUNT_DEPTH,
+        depth => depth as usize,
+    };
+    let extended_public_key = match stored_key.export_extended_public_key(
+        &param.password,
+        coin,
+        derivation_path,
+        depth,
+        version,
+    ) {
+        Ok(key) => key,
+        Err(error) => {
+            return get_error_response_by_error(error);
+        }
+    };
+    MwResponse {
+        response: Some(Response::RespGetExtendedPublicKey(
+            GetExtendedPublicKeyResp {
+                extended_public_key,
+            },
+        )),
+    }
+}
+
 pub fn export_key_store_json_of_address(param: ExportKeyStoreJsonOfAddressParam) -> MwResponse {
     let coin_info = get_coin_info(param.coin);
     let coin = match coin_info {
@@ -208,17 +365,22 @@ pub fn export_key_store_json_of_address(param: ExportKeyStoreJsonOfAddressParam)
             };
         }
     };
-    let mut stored_key: StoredKey = match serde_json::from_slice(&param.stored_key_data) {
+    let stored_key = match StoredKey::from_json(&param.stored_key_data) {
         Ok(key) => key,
-        Err(_) => {
-            return get_json_error_response();
+        Err(error) => {
+            return get_load_stored_key_error_response(error);
         }
     };
+    let kdf_params = match KdfType::from_i32(param.kdf) {
+        Some(kdf) => get_kdf_params(kdf),
+        None => return get_invalid_proto_resposne(),
+    };
     let json = match stored_key.export_key_store_json_of_address(
         &param.password,
         &param.new_password,
         coin,
         &param.address,
+        kdf_params,
     ) {
         Ok(key) => key,
         Err(error) => {
@@ -245,17 +407,22 @@ pub fn export_key_store_json_of_path(param: ExportKeyStoreJsonOfPathParam) -> Mw
             };
         }
     };
-    let mut stored_key: StoredKey = match serde_json::from_slice(&param.stored_key_data) {
+    let stored_key = match StoredKey::from_json(&param.stored_key_data) {
         Ok(key) => key,
-        Err(_) => {
-            return get_json_error_response();
+        Err(error) => {
+            return get_load_stored_key_error_response(error);
         }
     };
+    let kdf_params = match KdfType::from_i32(param.kdf) {
+        Some(kdf) => get_kdf_params(kdf),
+        None => return get_invalid_proto_resposne(),
+    };
     let json = match stored_key.export_key_store_json_of_path(
         &param.password,
         &param.new_password,
         coin,
         &param.derivation_path,
+        kdf_params,
     ) {
         Ok(key) => key,
         Err(error) => {
@@ -269,14 +436,25 @@ pub fn export_key_store_json_of_path(param: ExportKeyStoreJsonOfPathParam) -> Mw
     }
 }
 
+fn get_kdf_params(kdf: KdfType) -> KdfParams {
+    match kdf {
+        KdfType::Scrypt => KdfParams::ScryptParam(ScryptParams::default()),
+        KdfType::Pbkdf2 => KdfParams::Pbkdf2Param(Pbkdf2Params::default()),
+    }
+}
+
 pub fn update_key_store_password(param: UpdateStoredKeyPasswordParam) -> MwResponse {
-    let mut stored_key: StoredKey = match serde_json::from_slice(&param.stored_key_data) {
+    let mut stored_key = match StoredKey::from_json(&param.stored_key_data) {
         Ok(key) => key,
-        Err(_) => {
-            return get_json_error_response();
+        Err(error) => {
+            return get_load_stored_key_error_response(error);
         }
     };
-    match stored_key.update_password(&param.old_password, &param.new_password) {
+    match stored_key.update_password(
+        &param.old_password,
+        &param.new_password,
+        get_kdf_policy(param.kdf_policy),
+    ) {
         Ok(_) => MwResponse {
             response: Some(Response::RespUpdateKeyStorePassword(
                 UpdateStoredKeyPasswordResp {
@@ -288,6 +466,23 @@ pub fn update_key_store_password(param: UpdateStoredKeyPasswordParam) -> MwRespo
     }
 }
 
+pub fn rekey_stored_key(param: RekeyStoredKeyParam) -> MwResponse {
+    let mut stored_key = match StoredKey::from_json(&param.stored_key_data) {
+        Ok(key) => key,
+        Err(error) => {
+            return get_load_stored_key_error_response(error);
+        }
+    };
+    match stored_key.rekey(&param.password, get_kdf_policy(param.kdf_policy)) {
+        Ok(_) => MwResponse {
+            response: Some(Response::RespRekeyStoredKey(RekeyStoredKeyResp {
+                stored_key: Some(StoredKeyInfo::from(stored_key)),
+            })),
+        },
+        Err(error) => get_error_response_by_error(error),
+    }
+}
+
 pub fn get_supported_import_types(param: GetKeyStoreSupportImportTypeParam) -> MwResponse {
     let coin_info = get_coin_info(param.coin);
     let coin = match coin_info {
@@ -378,6 +573,6 @@ mod tests {
             .into_iter()
             .map(|r#type| r#type as i32)
             .collect();
-        assert_eq!(types, vec![0, 1, 2]);
+        assert_eq!(types, vec![0, 1, 2, 4]);
     }
 }
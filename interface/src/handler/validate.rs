@@ -21,13 +21,12 @@ pub fn validate(param: ValidateParam) -> MwResponse {
         }
         validate_param::Input::KeyStoreJson(json) => KeyStoreJson::from_str(&json).is_ok(),
         validate_param::Input::StoredKeyPassword(password_param) => {
-            let stored_key: StoredKey =
-                match serde_json::from_slice(&password_param.stored_key_data) {
-                    Ok(key) => key,
-                    Err(_) => {
-                        return get_json_error_response();
-                    }
-                };
+            let stored_key = match StoredKey::from_json(&password_param.stored_key_data) {
+                Ok(key) => key,
+                Err(error) => {
+                    return get_load_stored_key_error_response(error);
+                }
+            };
             stored_key.validate_password(&password_param.password)
         }
         validate_param::Input::AddressValidationParam(addr_param) => {
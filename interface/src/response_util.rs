@@ -36,3 +36,11 @@ pub fn get_error_response_by_error(error: Error) -> MwResponse {
         response: Some(get_error_response(error)),
     }
 }
+
+/// Error response of a StoredKey failed to load, malformed data is reported as invalid data
+pub fn get_load_stored_key_error_response(error: Error) -> MwResponse {
+    match error {
+        Error::JsonSerializationError => get_json_error_response(),
+        error => get_error_response_by_error(error),
+    }
+}
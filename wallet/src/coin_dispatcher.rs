@@ -1,11 +1,13 @@
 use crate::Error;
+use arweave::entry::ArweaveEntry;
+use bitcoin::entry::BitcoinEntry;
 use chain_common::api::Coin as ProtoCoinType;
 use chain_common::coin::Coin;
 use chain_common::entry::Entry;
 use chain_common::private_key::PrivateKey;
 use chain_common::public_key::PublicKey;
-use chain_common::Error as ChainError;
 use ethereum::entry::EthereumEntry;
+use polkadot::entry::PolkadotEntry;
 use solana::entry::SolanaEntry;
 use std::str::FromStr;
 
@@ -16,8 +18,10 @@ impl CoinDispatcher {
         let coin_proto_type = ProtoCoinType::from_str(&coin.name)?;
         match coin_proto_type {
             ProtoCoinType::Ethereum => Ok(Box::new(EthereumEntry {})),
+            ProtoCoinType::Polkadot => Ok(Box::new(PolkadotEntry::new(coin))),
             ProtoCoinType::Solana => Ok(Box::new(SolanaEntry {})),
-            _ => Err(Error::ChainError(ChainError::NotSupportedCoin)),
+            ProtoCoinType::Bitcoin => Ok(Box::new(BitcoinEntry::new(coin))),
+            ProtoCoinType::Arweave => Ok(Box::new(ArweaveEntry {})),
         }
     }
 }
@@ -25,21 +29,29 @@ impl CoinDispatcher {
 pub fn derive_address_with_private_key(
     coin: &Coin,
     private_key: &PrivateKey,
+    derivation_path: &str,
 ) -> Result<String, Error> {
     let public_key = private_key.get_public_key(&coin.public_key_type)?;
-    derive_address_with_public_key(&coin, &public_key)
+    derive_address_with_public_key(coin, &public_key, derivation_path)
 }
 
 pub fn derive_address_with_public_key(
     coin: &Coin,
     public_key: &PublicKey,
+    derivation_path: &str,
 ) -> Result<String, Error> {
-    let p2pkh = coin.get_value("p2pkh").unwrap_or_default();
+    // The version byte of P2PKH addresses, e.g. `"p2pkh": 0` for Bitcoin
+    let p2pkh: Vec<u8> = coin
+        .get_value("p2pkh")
+        .and_then(|p2pkh| p2pkh.parse().ok())
+        .into_iter()
+        .collect();
     let hrp = coin.get_value("hrp").unwrap_or_default();
-    Ok(CoinDispatcher::get_entry(&coin)?.derive_address(
-        &coin,
-        &public_key,
-        p2pkh.as_bytes(),
+    Ok(CoinDispatcher::get_entry(coin)?.derive_address_of_path(
+        coin,
+        public_key,
+        &p2pkh,
         hrp.as_bytes(),
+        derivation_path,
     )?)
 }
@@ -1,16 +1,26 @@
 use crate::Error;
+use crypto::sr25519::JunctionPath;
 use crypto::Error as CryptoError;
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
+use std::str::FromStr;
 use std::string::ToString;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DerivationPath {
     indices: Vec<DerivationPathIndex>,
+
+    // Junctions of a Substrate derivation path like `//hard/soft`, which has no indices
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    junctions: Option<Vec<DerivationJunction>>,
 }
 
 impl DerivationPath {
     pub fn new(path: &str) -> Result<DerivationPath, Error> {
+        if JunctionPath::is_junction_path(path) {
+            return Self::new_junction_path(path);
+        }
         let children: Vec<&str> = path.split('/').into_iter().collect();
         let mut indices: Vec<DerivationPathIndex> = vec![];
         for child in children {
@@ -33,12 +43,62 @@ impl DerivationPath {
                 hardened,
             });
         }
-        Ok(DerivationPath { indices })
+        Ok(DerivationPath {
+            indices,
+            junctions: None,
+        })
+    }
+
+    fn new_junction_path(path: &str) -> Result<DerivationPath, Error> {
+        let junction_path = JunctionPath::from_str(path)?;
+        // Derivation paths are stored in plain text, so they must not carry a password
+        if junction_path.password.is_some() {
+            return Err(Error::CryptoError(CryptoError::InvalidDerivationpath));
+        }
+        let junctions = junction_path
+            .junctions
+            .into_iter()
+            .map(|junction| DerivationJunction {
+                value: junction.code,
+                hardened: junction.hard,
+            })
+            .collect();
+        Ok(DerivationPath {
+            indices: vec![],
+            junctions: Some(junctions),
+        })
+    }
+
+    /// Number of indices of the path
+    pub fn depth(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// The path of the first `depth` indices, e.g. the account path `m/44'/60'/0'` of depth 3
+    pub fn prefix(&self, depth: usize) -> Result<DerivationPath, Error> {
+        if self.junctions.is_some() || depth > self.indices.len() {
+            return Err(Error::CryptoError(CryptoError::InvalidDerivationpath));
+        }
+        Ok(DerivationPath {
+            indices: self.indices[..depth].to_vec(),
+            junctions: None,
+        })
+    }
+
+    /// The BIP purpose of the first index, e.g. 84 of `m/84'/0'/0'/0/0`
+    pub fn purpose(&self) -> Option<u32> {
+        match self.indices.first() {
+            Some(index) if index.hardened => Some(index.value),
+            _ => None,
+        }
     }
 }
 
 impl ToString for DerivationPath {
     fn to_string(&self) -> String {
+        if let Some(junctions) = &self.junctions {
+            return junctions.iter().map(|x| x.to_string()).collect();
+        }
         let mut path = String::from("m/");
         self.indices.iter().for_each(|index| {
             path.push_str(&index.value.to_string());
@@ -58,6 +118,7 @@ impl ToString for DerivationPath {
 impl PartialEq for DerivationPath {
     fn eq(&self, other: &DerivationPath) -> bool {
         (self.indices.len() == other.indices.len())
+            && self.junctions == other.junctions
             && self
                 .indices
                 .iter()
@@ -82,6 +143,22 @@ impl ToString for DerivationPathIndex {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct DerivationJunction {
+    value: String,
+    hardened: bool,
+}
+
+impl ToString for DerivationJunction {
+    fn to_string(&self) -> String {
+        if self.hardened {
+            format!("//{}", self.value)
+        } else {
+            format!("/{}", self.value)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::derivation_path::*;
@@ -119,4 +196,25 @@ mod tests {
         assert_eq!(path1 != path3, true);
         assert_eq!(path3 != path4, true);
     }
+
+    #[test]
+    fn test_junction_path_parse() {
+        let test_path_str = "//polkadot//0/1";
+        let derivation_path = DerivationPath::new(test_path_str).unwrap();
+        assert_eq!(derivation_path.to_string(), test_path_str);
+        assert!(derivation_path != DerivationPath::new("//polkadot//0//1").unwrap());
+
+        let root_path = DerivationPath::new("").unwrap();
+        assert_eq!(root_path.to_string(), "");
+        assert!(root_path != DerivationPath::new("m").unwrap());
+
+        assert_eq!(
+            DerivationPath::new("//polkadot///password").unwrap_err(),
+            Error::CryptoError(CryptoError::InvalidDerivationpath)
+        );
+        assert_eq!(
+            DerivationPath::new("//polkadot//").unwrap_err(),
+            Error::CryptoError(CryptoError::InvalidDerivationpath)
+        );
+    }
 }
@@ -1,95 +1,234 @@
 use crate::Error;
+use crypto::aead::{self, AeadType};
 use crypto::aes;
 use crypto::aes::AesType;
 use crypto::aes_params::AesParams;
-use crypto::hash;
-use crypto::kdf_params::{KdfParams, KdfParamsType};
+use crypto::hash::{self, Hasher, Keccak256};
+use crypto::kdf_params::{KdfParams, KdfParamsType, KdfPolicy};
 use crypto::key_store_json::{Crypto, KeyStoreJson};
-use crypto::scrypt_params::ScryptParams;
+use crypto::number_util::random_iv;
 use crypto::Error as CryptoError;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 use uuid::Uuid;
+use zeroize::Zeroizing;
+
+// AES of the V3 KeyStoreJSON authenticated by the Keccak MAC, or an AEAD cipher which
+// authenticates the encrypted data itself
+#[derive(Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+enum Cipher {
+    Aes(AesType),
+    Aead(AeadType),
+}
+
+impl ToString for Cipher {
+    fn to_string(&self) -> String {
+        match self {
+            Self::Aes(aes_type) => aes_type.to_string(),
+            Self::Aead(aead_type) => aead_type.to_string(),
+        }
+    }
+}
 
 #[derive(Serialize, Deserialize)]
 pub struct EncryptionParams {
     encrypted: Vec<u8>,
-    cipher: AesType,
+    cipher: Cipher,
+    // The iv of AES, or the nonce of the AEAD cipher
     pub cipher_params: AesParams,
+    // Empty for AEAD ciphers
     mac: String,
     kdf_params: KdfParams,
 }
 
 impl EncryptionParams {
     pub fn new(password: &[u8], data: &[u8]) -> Result<EncryptionParams, Error> {
-        let kdf_params = KdfParams::ScryptParam(ScryptParams::default());
-        let derived_key = kdf_params.generate_derived_key(password)?;
+        Self::new_with_kdf_policy(password, data, KdfPolicy::default())
+    }
+
+    pub fn new_with_kdf_policy(
+        password: &[u8],
+        data: &[u8],
+        kdf_policy: KdfPolicy,
+    ) -> Result<EncryptionParams, Error> {
+        Self::new_with_kdf_params(password, data, kdf_policy.new_kdf_params()?)
+    }
+
+    pub fn new_with_kdf_params(
+        password: &[u8],
+        data: &[u8],
+        kdf_params: KdfParams,
+    ) -> Result<EncryptionParams, Error> {
+        let derived_key = Zeroizing::new(kdf_params.generate_derived_key(password)?);
         let cipher_params = AesParams::default();
         let iv = hex::decode(&cipher_params.iv).expect("fail to decode iv");
-        let encrypted = aes::ctr::encrypt(data, &derived_key[0..16], &iv, 128)?;
-        let mac = hash::compute_mac(&derived_key[16..32], &encrypted);
+        let encrypted = aes::ctr::encrypt(data, cipher_key(&derived_key, 128)?, &iv, 128)?;
+        let mac = hash::compute_mac(mac_key(&derived_key, 128)?, &encrypted);
 
         Ok(EncryptionParams {
             encrypted,
-            cipher: AesType::Ctr(128),
+            cipher: Cipher::Aes(AesType::Ctr(128)),
             cipher_params,
             mac: hex::encode(mac),
             kdf_params,
         })
     }
 
+    /// Encrypt `data` with `aead_type`, `associated_data` must be the same to decrypt it
+    pub fn new_aead(
+        password: &[u8],
+        data: &[u8],
+        kdf_policy: KdfPolicy,
+        aead_type: AeadType,
+        associated_data: &[u8],
+    ) -> Result<EncryptionParams, Error> {
+        let (params, _) =
+            Self::new_aead_with_key(password, data, kdf_policy, aead_type, associated_data)?;
+        Ok(params)
+    }
+
+    /// `new_aead`, which also returns the derived key so that other data of the same password
+    /// is sealed without deriving a key again
+    pub fn new_aead_with_key(
+        password: &[u8],
+        data: &[u8],
+        kdf_policy: KdfPolicy,
+        aead_type: AeadType,
+        associated_data: &[u8],
+    ) -> Result<(EncryptionParams, AeadKey), Error> {
+        let kdf_params = kdf_policy.new_kdf_params()?;
+        let key = AeadKey::derive(&kdf_params, aead_type, password)?;
+        let sealed = key.seal(data, associated_data)?;
+        let params = EncryptionParams {
+            encrypted: sealed.encrypted,
+            cipher: Cipher::Aead(aead_type),
+            cipher_params: sealed.cipher_params,
+            mac: "".to_owned(),
+            kdf_params,
+        };
+        Ok((params, key))
+    }
+
     pub fn new_from_json_struct(
         json_struct: &KeyStoreJson,
         password: &[u8],
-    ) -> Result<(EncryptionParams, Vec<u8>), Error> {
+    ) -> Result<(EncryptionParams, Zeroizing<Vec<u8>>), Error> {
         let cipher = AesType::from_str(&json_struct.crypto.cipher)?;
+        if json_struct.crypto.kdf != json_struct.crypto.kdfparams.name() {
+            return Err(Error::CryptoError(CryptoError::KdfParamsInvalid));
+        }
         let encrypted_hexdecoded = hex::decode(&json_struct.crypto.ciphertext)
             .or(Err(Error::CryptoError(CryptoError::KdfParamsInvalid)))?;
         let unverified_encryption_param = Self {
             encrypted: encrypted_hexdecoded,
-            cipher,
+            cipher: Cipher::Aes(cipher),
             cipher_params: json_struct.crypto.cipherparams.clone(),
             mac: json_struct.crypto.mac.clone(),
             kdf_params: json_struct.crypto.kdfparams.clone(),
         };
-        let decrypted = unverified_encryption_param.decrypt(&password)?;
+        let decrypted = match json_struct.version {
+            1 => unverified_encryption_param.decrypt_v1(password)?,
+            _ => unverified_encryption_param.decrypt(password)?,
+        };
         Ok((unverified_encryption_param, decrypted))
     }
 
-    pub fn decrypt(&self, password: &[u8]) -> Result<Vec<u8>, Error> {
-        let derived_key = self.kdf_params.generate_derived_key(&password)?;
-        let mac = hash::compute_mac(&derived_key[16..32], &self.encrypted);
-        let mac_hex = hex::encode(mac);
-        if mac_hex != self.mac {
-            return Err(Error::CryptoError(CryptoError::PasswordIncorrect));
-        }
-        let iv = hex::decode(&self.cipher_params.iv).expect("fail to decode iv");
+    /// Whether the data is encrypted with an AEAD cipher
+    pub fn is_aead(&self) -> bool {
+        matches!(self.cipher, Cipher::Aead(_))
+    }
+
+    /// The key of AEAD encrypted data derived from `password`
+    pub fn aead_key(&self, password: &[u8]) -> Result<AeadKey, Error> {
         match self.cipher {
-            AesType::Ctr(bits) => Ok(aes::ctr::decrypt(
-                &self.encrypted,
-                &derived_key[0..16],
-                &iv,
-                bits,
-            )?),
-            AesType::Cbc(_) => Err(Error::CryptoError(CryptoError::NotSupportedCipher)),
+            Cipher::Aead(aead_type) => AeadKey::derive(&self.kdf_params, aead_type, password),
+            Cipher::Aes(_) => Err(Error::CryptoError(CryptoError::NotSupportedCipher)),
         }
     }
 
-    pub fn export_to_key_store_json(
+    /// Decrypt AEAD encrypted data with the `key` of `aead_key`
+    pub fn decrypt_with_key(
+        &self,
+        key: &AeadKey,
+        associated_data: &[u8],
+    ) -> Result<Zeroizing<Vec<u8>>, Error> {
+        if self.cipher != Cipher::Aead(key.aead_type) {
+            return Err(Error::CryptoError(CryptoError::NotSupportedCipher));
+        }
+        key.decrypt(&self.encrypted, &self.cipher_params, associated_data)
+    }
+
+    pub fn decrypt(&self, password: &[u8]) -> Result<Zeroizing<Vec<u8>>, Error> {
+        self.decrypt_with_associated_data(password, &[])
+    }
+
+    /// Decrypt the data, `associated_data` is only authenticated by AEAD ciphers.
+    /// The decrypted data is wiped when it's dropped
+    pub fn decrypt_with_associated_data(
+        &self,
+        password: &[u8],
+        associated_data: &[u8],
+    ) -> Result<Zeroizing<Vec<u8>>, Error> {
+        let aes_type = match self.cipher {
+            Cipher::Aes(aes_type) => aes_type,
+            Cipher::Aead(_) => {
+                return self.decrypt_with_key(&self.aead_key(password)?, associated_data);
+            }
+        };
+        let bits = aes_type.bits();
+        let derived_key = self.verified_derived_key(password, bits)?;
+        self.decrypt_aes(aes_type, cipher_key(&derived_key, bits)?)
+    }
+
+    // Geth's V1 KeyStoreJSON is encrypted with AES-128-CBC, whose key is the leading 16 bytes of
+    // the Keccak-256 hash of the leading 16 bytes of the derived key
+    fn decrypt_v1(&self, password: &[u8]) -> Result<Zeroizing<Vec<u8>>, Error> {
+        if self.cipher != Cipher::Aes(AesType::Cbc(128)) {
+            return Err(Error::CryptoError(CryptoError::NotSupportedCipher));
+        }
+        let derived_key = self.verified_derived_key(password, 128)?;
+        let key = Zeroizing::new(Hasher::hash(Keccak256, cipher_key(&derived_key, 128)?)?);
+        self.decrypt_aes(AesType::Cbc(128), &key[..16])
+    }
+
+    // The key derived from `password`, after checking the Keccak MAC of the encrypted data
+    fn verified_derived_key(
         &self,
+        password: &[u8],
+        bits: u32,
+    ) -> Result<Zeroizing<Vec<u8>>, Error> {
+        let derived_key = Zeroizing::new(self.kdf_params.generate_derived_key(password)?);
+        let mac = hex::decode(&self.mac)
+            .map_err(|_| Error::CryptoError(CryptoError::PasswordIncorrect))?;
+        if !hash::verify_mac(mac_key(&derived_key, bits)?, &self.encrypted, &mac) {
+            return Err(Error::CryptoError(CryptoError::PasswordIncorrect));
+        }
+        Ok(derived_key)
+    }
+
+    fn decrypt_aes(&self, aes_type: AesType, key: &[u8]) -> Result<Zeroizing<Vec<u8>>, Error> {
+        let iv = hex::decode(&self.cipher_params.iv)
+            .map_err(|_| Error::CryptoError(CryptoError::InvalidKeyIvLength))?;
+        let decrypted = match aes_type {
+            AesType::Ctr(bits) => aes::ctr::decrypt(&self.encrypted, key, &iv, bits)?,
+            AesType::Cbc(bits) => aes::cbc::decrypt(&self.encrypted, key, &iv, bits)?,
+        };
+        Ok(Zeroizing::new(decrypted))
+    }
+
+    /// Export `data` as a V3 KeyStoreJSON encrypted with `password`, the key of which
+    /// is derived with the type of `kdf_params`
+    pub fn export_to_key_store_json(
+        data: &[u8],
         password: &str,
-        new_password: &str,
+        kdf_params: KdfParams,
     ) -> Result<String, Error> {
-        // 1. Check the password by using the decrypt method
-        let decrypted = self.decrypt(&password.as_bytes())?;
-
-        // 2. Generate a temp new EncryptionParam using the new_password
-        let new_encryption_param = Self::new(&new_password.as_bytes(), &decrypted)?;
+        let new_encryption_param =
+            Self::new_with_kdf_params(password.as_bytes(), data, kdf_params)?;
 
         let new_encrypted_text = hex::encode(&new_encryption_param.encrypted);
-        let kdf = match new_encryption_param.kdf_params {
-            KdfParams::ScryptParam(_) => "scrypt".to_owned(),
-        };
+        let kdf = new_encryption_param.kdf_params.name().to_owned();
         let crypto = Crypto {
             cipher: new_encryption_param.cipher.to_string(),
             cipherparams: new_encryption_param.cipher_params,
@@ -109,6 +248,88 @@ impl EncryptionParams {
     }
 }
 
+/// Data sealed with an `AeadKey`, which has a nonce of its own but no kdf
+#[derive(Serialize, Deserialize)]
+pub struct SealedData {
+    encrypted: Vec<u8>,
+    cipher_params: AesParams,
+}
+
+/// The key of an AEAD cipher derived from a password, which is wiped when it's dropped.
+/// Each data sealed with the key has a random nonce
+pub struct AeadKey {
+    key: Zeroizing<Vec<u8>>,
+    aead_type: AeadType,
+}
+
+impl AeadKey {
+    fn derive(kdf_params: &KdfParams, aead_type: AeadType, password: &[u8]) -> Result<Self, Error> {
+        let derived_key = Zeroizing::new(kdf_params.generate_derived_key(password)?);
+        let key = derived_key
+            .get(..aead::KEY_LEN)
+            .ok_or(Error::CryptoError(CryptoError::KdfParamsInvalid))?;
+        Ok(AeadKey {
+            key: Zeroizing::new(key.to_vec()),
+            aead_type,
+        })
+    }
+
+    /// Encrypt `data`, `associated_data` must be the same to open it
+    pub fn seal(&self, data: &[u8], associated_data: &[u8]) -> Result<SealedData, Error> {
+        let nonce = random_iv(self.aead_type.nonce_len());
+        let encrypted = aead::encrypt(self.aead_type, &self.key, &nonce, data, associated_data)?;
+        Ok(SealedData {
+            encrypted,
+            cipher_params: AesParams {
+                iv: hex::encode(nonce),
+            },
+        })
+    }
+
+    /// Decrypt the data of `seal`, the decrypted data is wiped when it's dropped
+    pub fn open(
+        &self,
+        sealed: &SealedData,
+        associated_data: &[u8],
+    ) -> Result<Zeroizing<Vec<u8>>, Error> {
+        self.decrypt(&sealed.encrypted, &sealed.cipher_params, associated_data)
+    }
+
+    fn decrypt(
+        &self,
+        encrypted: &[u8],
+        cipher_params: &AesParams,
+        associated_data: &[u8],
+    ) -> Result<Zeroizing<Vec<u8>>, Error> {
+        let nonce = hex::decode(&cipher_params.iv)
+            .map_err(|_| Error::CryptoError(CryptoError::InvalidKeyIvLength))?;
+        Ok(Zeroizing::new(aead::decrypt(
+            self.aead_type,
+            &self.key,
+            &nonce,
+            encrypted,
+            associated_data,
+        )?))
+    }
+}
+
+// The encryption key is the leading bytes of the derived key, as long as the key of the cipher
+fn cipher_key(derived_key: &[u8], bits: u32) -> Result<&[u8], Error> {
+    let key_len = aes::key_len(bits)?;
+    derived_key
+        .get(..key_len)
+        .ok_or(Error::CryptoError(CryptoError::KdfParamsInvalid))
+}
+
+// The MAC key is the 16 bytes following the encryption key, which are the second 16 bytes of the
+// derived key of AES-128. AES-192 and AES-256 need a longer derived key
+fn mac_key(derived_key: &[u8], bits: u32) -> Result<&[u8], Error> {
+    let key_len = aes::key_len(bits)?;
+    derived_key
+        .get(key_len..key_len + 16)
+        .ok_or(Error::CryptoError(CryptoError::KdfParamsInvalid))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -118,7 +339,7 @@ mod tests {
         let data = "team engine square letter hero song dizzy scrub tornado fabric divert saddle";
         let enc_param = EncryptionParams::new(password.as_bytes(), data.as_bytes()).unwrap();
         let decrypted = enc_param.decrypt(password.as_bytes()).unwrap();
-        assert_eq!(data.as_bytes(), &decrypted);
+        assert_eq!(data.as_bytes(), decrypted.as_slice());
     }
 
     #[test]
@@ -151,7 +372,7 @@ mod tests {
         let encrypted_hexdecoded = hex::decode(&json_struct.crypto.ciphertext).unwrap();
         let unverified_encryption_param = EncryptionParams {
             encrypted: encrypted_hexdecoded,
-            cipher,
+            cipher: Cipher::Aes(cipher),
             cipher_params: json_struct.crypto.cipherparams.clone(),
             mac: json_struct.crypto.mac.clone(),
             kdf_params: json_struct.crypto.kdfparams.clone(),
@@ -167,18 +388,120 @@ mod tests {
         let iv =
             hex::decode(&unverified_encryption_param.cipher_params.iv).expect("fail to decode iv");
         let result_bits = match unverified_encryption_param.cipher {
-            AesType::Ctr(bits) => aes::ctr::decrypt(
+            Cipher::Aes(AesType::Ctr(bits)) => aes::ctr::decrypt(
                 &unverified_encryption_param.encrypted,
                 &derived_key[0..16],
                 &iv,
                 bits,
             )
             .unwrap(),
-            AesType::Cbc(_) => vec![],
+            _ => vec![],
         };
         let test_encrypted =
             aes::ctr::encrypt(&result_bits, &derived_key[0..16], &iv, 128).unwrap();
         assert_eq!(test_encrypted.len() as u8, 32);
         assert_eq!(result_bits.len() as u8, 32);
     }
+
+    #[test]
+    fn test_decrypt_json_of_ciphers() {
+        let json = |cipher: &str, ciphertext: &str, mac: &str| {
+            format!(
+                r#"
+                {{
+                    "version":3,
+                    "id":"E511D153-EB10-484A-A649-56A3E015E4D3",
+                    "crypto":{{
+                        "ciphertext":"{}",
+                        "cipherparams":{{
+                            "iv":"e83921ccf41447518b27dd1a22129494"
+                        }},
+                        "kdf":"scrypt",
+                        "kdfparams":{{
+                            "r":8,
+                            "p":1,
+                            "n":1024,
+                            "dklen":32,
+                            "salt":"ae2ef76580540174997df3191d32e577fb44693c037eae3cf1842a22b892c02a"
+                        }},
+                        "mac":"{}",
+                        "cipher":"{}"
+                    }}
+                }}
+                "#,
+                ciphertext, mac, cipher
+            )
+        };
+        let password = "MaskWallet".as_bytes();
+        let private_key = "3a1076bf45ab87712ad64ccb3b10217737f7faacbf2872e88fdd9a537d8fe266";
+
+        let cbc = json(
+            "aes-128-cbc",
+            "69c55d4952b6f25d2c1574a3e24c196f33317f2a710f2d3dbb8bb09c407800272925f575704cf6c5f5a0b8a3ac682c59",
+            "ed32f811da4c2a20119adbaa7a99358e6df795911ca8cc1d750c22744ec859a0",
+        );
+        let json_struct = KeyStoreJson::from_str(&cbc).unwrap();
+        let (_, decrypted) =
+            EncryptionParams::new_from_json_struct(&json_struct, password).unwrap();
+        assert_eq!(hex::encode(decrypted.as_slice()), private_key);
+
+        // The MAC key of AES-256 is the 16 bytes following its 32-byte key
+        let ctr = json(
+            "aes-256-ctr",
+            "e785e86760e668d89d561c12df91c22ad6689dc63762d45f4f95a56fcba95256",
+            "033212139ad5f6108ed3ff3b1d35afa96a5aa0b589034363bcdbcdf67601e730",
+        )
+        .replace(r#""dklen":32"#, r#""dklen":48"#);
+        let json_struct = KeyStoreJson::from_str(&ctr).unwrap();
+        let (_, decrypted) =
+            EncryptionParams::new_from_json_struct(&json_struct, password).unwrap();
+        assert_eq!(hex::encode(decrypted.as_slice()), private_key);
+
+        // The MAC key mustn't overlap the key of AES-256
+        let short_key = ctr.replace(r#""dklen":48"#, r#""dklen":32"#);
+        let json_struct = KeyStoreJson::from_str(&short_key).unwrap();
+        assert_eq!(
+            EncryptionParams::new_from_json_struct(&json_struct, password).err(),
+            Some(Error::CryptoError(CryptoError::KdfParamsInvalid))
+        );
+    }
+    #[test]
+    fn test_decrypt_geth_v1_json() {
+        // The V1 test vector of go-ethereum
+        let json = r#"
+        {
+            "Crypto": {
+                "cipher": "aes-128-cbc",
+                "cipherparams": {
+                    "iv": "35337770fc2117994ecdcad026bccff4"
+                },
+                "ciphertext": "6143d3192db8b66eabd693d9c4e414dcfaee52abda451af79ccf474dafb35f1bfc7ea013aa9d2ee35969a1a2e8d752d0",
+                "kdf": "scrypt",
+                "kdfparams": {
+                    "dklen": 32,
+                    "n": 262144,
+                    "p": 1,
+                    "r": 8,
+                    "salt": "9afcddebca541253a2f4053391c673ff9fe23097cd8555d149d929e4ccf1257f"
+                },
+                "mac": "3f3d5af884b17a100b0b3232c0636c230a54dc2ac8d986227219b0dd89197644",
+                "version": "1"
+            },
+            "address": "cb61d5a9c4896fb9658090b597ef0e7be6f7b67e",
+            "id": "e25f7c1f-d318-4f29-b62c-687190d4d299",
+            "version": "1"
+        }
+        "#;
+        let json_struct = KeyStoreJson::from_str(json).unwrap();
+        assert_eq!(json_struct.version, 1);
+        let (_, decrypted) = EncryptionParams::new_from_json_struct(&json_struct, b"g").unwrap();
+        assert_eq!(
+            hex::encode(decrypted.as_slice()),
+            "d1b1178d3529626a1a93e073f65028370d14c7eb0936eb42abef05db6f37ad7d"
+        );
+        assert_eq!(
+            EncryptionParams::new_from_json_struct(&json_struct, b"wrong").err(),
+            Some(Error::CryptoError(CryptoError::PasswordIncorrect))
+        );
+    }
 }
@@ -3,13 +3,19 @@ use super::derivation_path::DerivationPath;
 use crate::Error;
 use chain_common::coin::Coin;
 use chain_common::private_key::{PrivateKey, PrivateKeyType};
-use crypto::bip32;
-use crypto::bip39::Mnemonic;
+use crypto::bip32::{self, ExtendedPublicKeyVersion};
+use crypto::bip39::{Language, Mnemonic};
 use crypto::curve::Curve;
+use crypto::sr25519;
 use crypto::Error as CryptoError;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
+use zeroize::Zeroize;
 
+/// Depth of the hardened account keys at `m/purpose'/coin'/account'`
+pub const ACCOUNT_DEPTH: usize = 3;
+
+/// The mnemonic, seed, passphrase and entropy of a wallet are wiped when it's dropped
 #[derive(Serialize, Deserialize)]
 pub struct HdWallet {
     seed: Vec<u8>,
@@ -18,25 +24,44 @@ pub struct HdWallet {
     entropy: Vec<u8>,
 }
 
+impl Zeroize for HdWallet {
+    fn zeroize(&mut self) {
+        self.seed.zeroize();
+        self.mnemonic.zeroize();
+        self.password.zeroize();
+        self.entropy.zeroize();
+    }
+}
+
+impl Drop for HdWallet {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
 impl HdWallet {
     pub fn new(word_count: u32, password: &str) -> Result<HdWallet, Error> {
-        let mnemonic = Mnemonic::generate(word_count, password)?;
-        Ok(HdWallet {
-            seed: mnemonic.seed,
-            mnemonic: mnemonic.words,
-            password: password.to_owned(),
-            entropy: mnemonic.entropy,
-        })
+        Self::new_in(Language::English, word_count, password)
+    }
+
+    pub fn new_in(language: Language, word_count: u32, password: &str) -> Result<HdWallet, Error> {
+        let mnemonic = Mnemonic::generate_in(language, word_count, password)?;
+        Ok(Self::from_mnemonic(mnemonic, password))
     }
 
     pub fn new_with_mnemonic(mnemonic: &str, password: &str) -> Result<HdWallet, Error> {
         let mnemonic = Mnemonic::new(&mnemonic, &password)?;
-        Ok(HdWallet {
-            seed: mnemonic.seed,
-            mnemonic: mnemonic.words,
+        Ok(Self::from_mnemonic(mnemonic, password))
+    }
+
+    // The secrets are moved out of `mnemonic` without being copied
+    fn from_mnemonic(mut mnemonic: Mnemonic, password: &str) -> HdWallet {
+        HdWallet {
+            seed: std::mem::take(&mut mnemonic.seed),
+            mnemonic: std::mem::take(&mut mnemonic.words),
             password: password.to_owned(),
-            entropy: mnemonic.entropy,
-        })
+            entropy: std::mem::take(&mut mnemonic.entropy),
+        }
     }
 }
 
@@ -48,12 +73,23 @@ impl HdWallet {
     ) -> Result<PrivateKey, Error> {
         let curve = Curve::from_str(&coin.curve)?;
         let private_key_type = PrivateKey::get_private_key_type(&curve);
-        let node = bip32::HdNode::get_node(&self.seed, &derivation_path.to_string(), curve)?;
+        let node = match curve {
+            Curve::Sr25519 => {
+                sr25519::get_node(&self.entropy, &self.password, &derivation_path.to_string())?
+            }
+            _ => bip32::HdNode::get_node(&self.seed, &derivation_path.to_string(), curve)?,
+        };
         match private_key_type {
             PrivateKeyType::PrivateKeyTypeDefault32 => {
                 Ok(PrivateKey::new(&node.private_key_bytes)?)
             }
-            PrivateKeyType::PrivateKeyTypeExtended96 | PrivateKeyType::PrivateKeyTypeHd => {
+            PrivateKeyType::PrivateKeyTypeSr25519 => Ok(PrivateKey::new_of_type(
+                &[node.private_key_bytes, node.private_key_extension].concat(),
+                private_key_type,
+            )?),
+            PrivateKeyType::PrivateKeyTypeExtended96
+            | PrivateKeyType::PrivateKeyTypeHd
+            | PrivateKeyType::PrivateKeyTypeRsa => {
                 Err(Error::CryptoError(CryptoError::InvalidPrivateKey))
             }
         }
@@ -68,21 +104,67 @@ impl HdWallet {
         coin: &Coin,
         derivation_path: &str,
     ) -> Result<String, Error> {
-        let derivation_path = DerivationPath::new(&derivation_path)?;
-        let private_key = self.get_key(&coin, &derivation_path)?;
-        derive_address_with_private_key(&coin, &private_key)
+        let path = DerivationPath::new(derivation_path)?;
+        let private_key = self.get_key(coin, &path)?;
+        derive_address_with_private_key(coin, &private_key, derivation_path)
     }
 
-    pub fn get_extended_public_key(&self, coin: &Coin) -> String {
+    pub fn get_extended_public_key(&self, coin: &Coin) -> Result<String, Error> {
         self.get_extended_public_key_of_path(&coin, &coin.derivation_path)
     }
 
-    pub fn get_extended_public_key_of_path(&self, coin: &Coin, derivation_path: &str) -> String {
-        if coin.get_xpub().is_none() {
-            return "".to_owned();
+    /// The public key of the account of `derivation_path`, which is empty for curves without
+    /// account public keys
+    pub fn get_extended_public_key_of_path(
+        &self,
+        coin: &Coin,
+        derivation_path: &str,
+    ) -> Result<String, Error> {
+        let path = DerivationPath::new(derivation_path)?;
+        let depth = ACCOUNT_DEPTH.min(path.depth());
+        match self.get_extended_public_key_at_depth(coin, &path, depth, None) {
+            Err(Error::CryptoError(CryptoError::NotSupportedCurve)) => Ok("".to_owned()),
+            result => result,
+        }
+    }
+
+    /// The public key at the first `depth` indices of `derivation_path`. It's an extended public
+    /// key for secp256k1 coins, whose version defaults to the one of the purpose of the path, and
+    /// the hex encoded SLIP-10 public key for ed25519 coins
+    pub fn get_extended_public_key_at_depth(
+        &self,
+        coin: &Coin,
+        derivation_path: &DerivationPath,
+        depth: usize,
+        version: Option<ExtendedPublicKeyVersion>,
+    ) -> Result<String, Error> {
+        // Curves without account public keys are rejected first, their paths like the junctions
+        // of sr25519 have no prefix
+        let curve = Curve::from_str(&coin.curve)?;
+        if !matches!(curve, Curve::Secp256k1 | Curve::Ed25519) {
+            return Err(Error::CryptoError(CryptoError::NotSupportedCurve));
+        }
+        let path = derivation_path.prefix(depth)?;
+        match curve {
+            Curve::Secp256k1 => {
+                let version = version.unwrap_or_else(|| {
+                    ExtendedPublicKeyVersion::from_purpose(path.purpose().unwrap_or_default())
+                });
+                Ok(bip32::get_extended_public_key(
+                    &self.seed,
+                    &path.to_string(),
+                    version,
+                )?)
+            }
+            // SLIP-10 ed25519 keys have no public derivation, so there's no chain code to share
+            Curve::Ed25519 => {
+                let public_key = self
+                    .get_key(coin, &path)?
+                    .get_public_key(&coin.public_key_type)?;
+                Ok(hex::encode(public_key.data))
+            }
+            _ => Err(Error::CryptoError(CryptoError::NotSupportedCurve)),
         }
-        bip32::get_extended_public_key(&self.seed, &derivation_path)
-            .expect("fail to get extended public key")
     }
 }
 
@@ -201,8 +283,46 @@ mod tests {
             public_key_type: "secp256k1Extended".to_owned(),
             all_info: HashMap::new(),
         };
-        let extended_public_key = wallet.get_extended_public_key(&coin);
-        assert_eq!(extended_public_key, "")
+        assert_eq!(
+            wallet.get_extended_public_key(&coin).unwrap(),
+            "xpub6DCoCpSuQZB2jawqnGMEPS63ePKWkwWPH4TU45Q7LPXWuNd8TMtVxRrgjtEshuqpK3mdhaWHPFsBngh5GFZaM6si3yZdUsT8ddYM3PwnATt"
+        );
+        let path = DerivationPath::new(derivation_path).unwrap();
+        assert_eq!(
+            wallet
+                .get_extended_public_key_at_depth(
+                    &coin,
+                    &path,
+                    3,
+                    Some(ExtendedPublicKeyVersion::Zpub)
+                )
+                .unwrap(),
+            "zpub6rsKp9njhvFzSBL5SyvUocH3zKcQeBVP7HVucsBt6QHH1aFaxgDdCZAxnJA3hj9f8L1FCXhQJaaHZFvChePbwaEunexUeh67B5fdpWEscjP"
+        );
+        assert_eq!(
+            wallet
+                .get_extended_public_key_at_depth(&coin, &path, 6, None)
+                .err(),
+            Some(Error::CryptoError(CryptoError::InvalidDerivationpath))
+        );
+
+        let solana = Coin {
+            id: "solana".to_owned(),
+            name: "solana".to_owned(),
+            coin_id: 501,
+            symbol: "SOL".to_owned(),
+            decimals: 9,
+            blockchain: "Solana".to_owned(),
+            derivation_path: "m/44'/501'/0'/0'".to_owned(),
+            curve: "ed25519".to_owned(),
+            public_key_type: "ed25519".to_owned(),
+            all_info: HashMap::new(),
+        };
+        // SLIP-10 public key of m/44'/501'/0'
+        assert_eq!(
+            wallet.get_extended_public_key(&solana).unwrap(),
+            "e9b6062841bb977ad21de71ec961900633c26f21384e015b014a637a61499547"
+        );
     }
 
     #[test]
@@ -229,4 +349,97 @@ mod tests {
         // let address2 = wallet.get_address_for_coin(&coin).unwrap();
         assert_eq!(address1, "2bUBiBNZyD29gP1oV6de7nxowMLoDBtopMMTGgMvjG5m");
     }
+
+    #[test]
+    fn test_get_address_for_coin_polkadot() {
+        let wallet = HdWallet::new_with_mnemonic(
+            "bottom drive obey lake curtain smoke basket hold race lonely fit walk",
+            "",
+        )
+        .unwrap();
+        let coin = Coin {
+            id: "polkadot".to_owned(),
+            name: "polkadot".to_owned(),
+            coin_id: 354,
+            symbol: "DOT".to_owned(),
+            decimals: 10,
+            blockchain: "Polkadot".to_owned(),
+            derivation_path: "".to_owned(),
+            curve: "sr25519".to_owned(),
+            public_key_type: "sr25519".to_owned(),
+            all_info: HashMap::new(),
+        };
+        let address = wallet
+            .get_address_for_coin_of_path(&coin, "//Alice")
+            .unwrap();
+        assert_eq!(address, "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5");
+
+        let root_address = wallet.get_address_for_coin(&coin).unwrap();
+        assert_eq!(
+            root_address,
+            "12bzRJfh7arnnfPPUZHeJUaE62QLEwhK48QnH9LXeK2m1iZU"
+        );
+    }
+
+    #[test]
+    fn test_get_address_for_coin_bitcoin() {
+        let wallet = HdWallet::new_with_mnemonic(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            "",
+        )
+        .unwrap();
+        let all_info: HashMap<String, serde_json::Value> =
+            serde_json::from_str(r#"{"p2pkh": 0, "p2sh": 5, "hrp": "bc"}"#).unwrap();
+        let coin = Coin {
+            id: "bitcoin".to_owned(),
+            name: "Bitcoin".to_owned(),
+            coin_id: 0,
+            symbol: "BTC".to_owned(),
+            decimals: 8,
+            blockchain: "Bitcoin".to_owned(),
+            derivation_path: "m/84'/0'/0'/0/0".to_owned(),
+            curve: "secp256k1".to_owned(),
+            public_key_type: "secp256k1".to_owned(),
+            all_info,
+        };
+        let cases = [
+            ("m/44'/0'/0'/0/0", "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA"),
+            ("m/49'/0'/0'/0/0", "37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf"),
+            (
+                "m/84'/0'/0'/0/0",
+                "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+            ),
+            (
+                "m/86'/0'/0'/0/0",
+                "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr",
+            ),
+        ];
+        for (path, expected) in cases.iter() {
+            let address = wallet.get_address_for_coin_of_path(&coin, path).unwrap();
+            assert_eq!(address, *expected);
+        }
+        assert_eq!(
+            wallet.get_address_for_coin(&coin).unwrap(),
+            "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu"
+        );
+    }
+
+    #[test]
+    fn test_zeroize() {
+        let mut wallet = HdWallet::new_with_mnemonic(
+            "suffer artefact burst review network fantasy easy century mom unique pupil boy",
+            "TREZOR",
+        )
+        .unwrap();
+        let seed = wallet.seed.as_ptr();
+        let password = wallet.password.as_ptr();
+        wallet.zeroize();
+        assert!(wallet.mnemonic.is_empty());
+        assert!(wallet.entropy.is_empty());
+        // The seed and the passphrase are wiped in their buffers, which the wallet still owns
+        unsafe {
+            assert_eq!(std::slice::from_raw_parts(seed, 64), [0u8; 64]);
+            assert_eq!(std::slice::from_raw_parts(password, 6), [0u8; 6]);
+        }
+    }
 }
@@ -18,6 +18,8 @@ pub enum Error {
     RequstedAccountNotFound,
     AccountAlreadyExist,
     JsonSerializationError,
+    NotSupportedStoredKeyVersion,
+    RequestNotSupportedOnWatchOnlyStoredKey,
 }
 
 impl From<CryptoError> for Error {
@@ -40,6 +42,8 @@ impl Error {
             Error::RequestNotSupportedOnPrivateKeyTypeStoredKey => "-1003".to_owned(),
             Error::RequstedAccountNotFound => "-1004".to_owned(),
             Error::AccountAlreadyExist => "-1005".to_owned(),
+            Error::NotSupportedStoredKeyVersion => "-1006".to_owned(),
+            Error::RequestNotSupportedOnWatchOnlyStoredKey => "-1007".to_owned(),
             Error::CryptoError(crypto_error) => crypto_error.get_code(),
             Error::ChainError(chain_error) => chain_error.get_code(),
         }
@@ -56,6 +60,13 @@ impl Error {
                 "Account of requested address is not found".to_owned()
             }
             Error::AccountAlreadyExist => "The requested account already exists".to_owned(),
+            Error::NotSupportedStoredKeyVersion => {
+                "The version of the StoredKey is not supported by this library".to_owned()
+            }
+            Error::RequestNotSupportedOnWatchOnlyStoredKey => {
+                "Request is not supported on a watch-only StoredKey, which has no private key"
+                    .to_owned()
+            }
             Error::CryptoError(crypto_error) => crypto_error.get_message(),
             Error::ChainError(chain_error) => chain_error.get_message(),
         }
@@ -3,25 +3,59 @@ use std::str::FromStr;
 use uuid::Uuid;
 
 use super::account::Account;
-use super::coin_dispatcher::CoinDispatcher;
+use super::coin_dispatcher::{derive_address_with_public_key, CoinDispatcher};
 use super::derivation_path::DerivationPath;
-use super::encryption_params::EncryptionParams;
+use super::encryption_params::{EncryptionParams, SealedData};
 use super::hd_wallet::HdWallet;
 use crate::Error;
-use chain_common::api::{StoredKeyInfo, StoredKeyType as ProtoStoreKeyType};
+use chain_common::api::{Coin as ProtoCoinType, StoredKeyInfo, StoredKeyType as ProtoStoreKeyType};
 use chain_common::coin::Coin;
 use chain_common::private_key::PrivateKey;
-use crypto::bip39::Mnemonic;
+use chain_common::public_key::PublicKey;
+use crypto::aead::AeadType;
+use crypto::bip32::{ExtendedPublicKey, ExtendedPublicKeyVersion};
+use crypto::bip39::{Language, Mnemonic};
+use crypto::curve::Curve;
 use crypto::hash;
+use crypto::kdf_params::{KdfParams, KdfPolicy};
 use crypto::key_store_json::KeyStoreJson;
+use crypto::public_key::{convert_public_key, PublicKeyType};
+use crypto::rsa::Jwk;
 use crypto::Error as CryptoError;
+use zeroize::Zeroizing;
 
-const VERSION: &str = "0.1.0";
+// Version of new StoredKeys, whose payload is encrypted with an AEAD cipher
+const VERSION: &str = "0.2.0";
+// Version of StoredKeys whose payload is encrypted with AES-128-CTR and a Keccak MAC, and whose
+// mnemonic hash is derived with the password as the BIP39 passphrase
+const LEGACY_VERSION: &str = "0.1.0";
+const AEAD_TYPE: AeadType = AeadType::XChaCha20Poly1305;
+// Names of the encrypted fields, which are bound to the associated data
+const PAYLOAD_FIELD: &str = "payload";
+const PASSPHRASE_FIELD: &str = "passphrase";
+// SLIP-44 coin type of the testnets of all coins
+const TESTNET_COIN_ID: i32 = 1;
+// BIP purposes of legacy and taproot addresses, whose keys share the xpub and tpub versions
+const BIP44_PURPOSE: u32 = 44;
+const BIP86_PURPOSE: u32 = 86;
+
+// The decrypted payload and passphrase of a StoredKey
+type DecryptedFields = (Zeroizing<Vec<u8>>, Zeroizing<Vec<u8>>);
 
 #[derive(Serialize, Deserialize, PartialEq)]
 pub enum StoredKeyType {
     PrivateKey = 0,
     Mnemonic,
+    WatchOnly,
+}
+
+// The public key of a watch-only StoredKey, which is stored in plain text
+#[derive(Serialize, Deserialize)]
+enum WatchedKey {
+    // An account-level extended public key of any SLIP-132 version
+    ExtendedPublicKey(String),
+    // A hex encoded public key of a single address
+    PublicKey(String),
 }
 
 #[derive(Serialize, Deserialize)]
@@ -34,7 +68,23 @@ pub struct StoredKey {
 
     pub version: String,
 
-    payload: EncryptionParams,
+    // The encrypted mnemonic or private key, which a watch-only StoredKey doesn't have
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    payload: Option<EncryptionParams>,
+
+    // BIP39 passphrase of a mnemonic StoredKey, sealed with the key of the payload
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    passphrase: Option<SealedData>,
+
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    watched_key: Option<WatchedKey>,
+
+    // Accounts added to the StoredKey, which can be listed without the password
+    #[serde(default)]
+    accounts: Vec<Account>,
 }
 
 // Create & Import function
@@ -43,60 +93,110 @@ impl StoredKey {
         r#type: StoredKeyType,
         password: &str,
         data: &[u8],
+        passphrase: &str,
+        kdf_policy: KdfPolicy,
     ) -> Result<StoredKey, Error> {
-        let uuid = Uuid::new_v4();
-        let payload = EncryptionParams::new(password.as_bytes(), &data)?;
-        let hash = match r#type {
-            StoredKeyType::PrivateKey => hash::dsha256(&data),
-            StoredKeyType::Mnemonic => {
-                let mnemonic_str = std::str::from_utf8(&data)
-                    .map_err(|_| Error::CryptoError(CryptoError::PasswordIncorrect))?;
-                let mnemonic = Mnemonic::new(&mnemonic_str, &password)?;
-                hash::dsha256(&mnemonic.seed)
-            }
-        };
+        let id = Uuid::new_v4().to_string();
+        let hash = compute_hash(&r#type, data, passphrase)?;
+        let (payload, passphrase) =
+            encrypt_fields(&id, &r#type, password, data, passphrase, kdf_policy)?;
         Ok(StoredKey {
             r#type,
-            id: uuid.to_string(),
-            hash: hex::encode(hash),
+            id,
+            hash,
             version: VERSION.to_owned(),
-            payload,
+            payload: Some(payload),
+            passphrase,
+            watched_key: None,
+            accounts: vec![],
         })
     }
 
+    /// Create a StoredKey of `private_key` encrypted with the default `KdfPolicy`
     pub fn create_with_private_key(password: &str, private_key: &str) -> Result<StoredKey, Error> {
         let priv_key_bytes =
-            hex::decode(private_key).map_err(|_| CryptoError::InvalidPrivateKey)?;
-        Self::create_with_data(StoredKeyType::PrivateKey, &password, &priv_key_bytes)
+            Zeroizing::new(hex::decode(private_key).map_err(|_| CryptoError::InvalidPrivateKey)?);
+        Self::create_with_data(
+            StoredKeyType::PrivateKey,
+            password,
+            &priv_key_bytes,
+            "",
+            KdfPolicy::default(),
+        )
     }
 
     pub fn create_with_private_key_and_coin(
         password: &str,
         private_key: &str,
         coin: &Coin,
+        kdf_policy: KdfPolicy,
     ) -> Result<StoredKey, Error> {
         let priv_key_bytes =
-            hex::decode(private_key).map_err(|_| CryptoError::InvalidPrivateKey)?;
+            Zeroizing::new(hex::decode(private_key).map_err(|_| CryptoError::InvalidPrivateKey)?);
         PrivateKey::is_valid(&priv_key_bytes, &coin.curve)?;
-        let stored_key = StoredKey::create_with_private_key(password, private_key)?;
-        Ok(stored_key)
+        Self::create_with_data(
+            StoredKeyType::PrivateKey,
+            password,
+            &priv_key_bytes,
+            "",
+            kdf_policy,
+        )
     }
 
+    /// Create a StoredKey of `mnemonic` encrypted with the default `KdfPolicy`
     pub fn create_with_mnemonic(password: &str, mnemonic: &str) -> Result<StoredKey, Error> {
-        if !Mnemonic::is_valid(mnemonic) {
+        Self::create_with_mnemonic_and_passphrase(
+            password,
+            mnemonic,
+            "",
+            None,
+            KdfPolicy::default(),
+        )
+    }
+
+    /// Create a StoredKey of `mnemonic` whose seed is derived with the BIP39 `passphrase`,
+    /// an empty passphrase is the same as no passphrase. The mnemonic must be in `language`,
+    /// or in any supported language if it's None
+    pub fn create_with_mnemonic_and_passphrase(
+        password: &str,
+        mnemonic: &str,
+        passphrase: &str,
+        language: Option<Language>,
+        kdf_policy: KdfPolicy,
+    ) -> Result<StoredKey, Error> {
+        let is_valid = match language {
+            Some(language) => Mnemonic::is_valid_in(language, mnemonic),
+            None => Mnemonic::is_valid(mnemonic),
+        };
+        if !is_valid {
             return Err(Error::CryptoError(CryptoError::InvalidMnemonic));
         }
-        Self::create_with_data(StoredKeyType::Mnemonic, &password, &mnemonic.as_bytes())
+        Self::create_with_data(
+            StoredKeyType::Mnemonic,
+            password,
+            mnemonic.as_bytes(),
+            passphrase,
+            kdf_policy,
+        )
     }
 
-    pub fn create_with_mnemonic_random(password: &str) -> Result<(StoredKey, String), Error> {
-        let wallet = HdWallet::new(12, "")?;
+    pub fn create_with_mnemonic_random(
+        password: &str,
+        passphrase: &str,
+        word_count: u32,
+        language: Language,
+        kdf_policy: KdfPolicy,
+    ) -> Result<(StoredKey, String), Error> {
+        let mut wallet = HdWallet::new_in(language, word_count, passphrase)?;
         let stored_key = Self::create_with_data(
             StoredKeyType::Mnemonic,
             &password,
-            &wallet.mnemonic.as_bytes(),
+            wallet.mnemonic.as_bytes(),
+            passphrase,
+            kdf_policy,
         )?;
-        Ok((stored_key, wallet.mnemonic))
+        // The mnemonic is returned to the caller, the rest of the wallet is wiped
+        Ok((stored_key, std::mem::take(&mut wallet.mnemonic)))
     }
 
     pub fn create_with_json(
@@ -104,176 +204,442 @@ impl StoredKey {
         password: &str,
         json: &str,
         coin: &Coin,
+        kdf_policy: KdfPolicy,
     ) -> Result<StoredKey, Error> {
         let key_store_json_struct = KeyStoreJson::from_str(&json)?;
         let (_, decrypted) = EncryptionParams::new_from_json_struct(
             &key_store_json_struct,
             key_store_json_password.as_bytes(),
         )?;
-        let decrypted_str = hex::encode(&decrypted);
+        let decrypted_str = Zeroizing::new(hex::encode(decrypted.as_slice()));
         if Mnemonic::is_valid(&decrypted_str) {
-            return Self::create_with_mnemonic(&password, &decrypted_str);
+            return Self::create_with_mnemonic_and_passphrase(
+                password,
+                &decrypted_str,
+                "",
+                None,
+                kdf_policy,
+            );
+        }
+        let private_key = PrivateKey::new_of_coin(&decrypted, coin)?;
+        let private_key_hex = Zeroizing::new(private_key.to_string());
+        Self::create_with_private_key_and_coin(password, &private_key_hex, coin, kdf_policy)
+    }
+
+    /// Create a StoredKey of the RSA private key in the JSON Web Key `jwk`, which is stored
+    /// in its PKCS#1 DER encoding
+    pub fn create_with_jwk(
+        password: &str,
+        jwk: &str,
+        kdf_policy: KdfPolicy,
+    ) -> Result<StoredKey, Error> {
+        let der = Jwk::from_str(jwk)?.to_der()?;
+        Self::create_with_data(StoredKeyType::PrivateKey, password, &der, "", kdf_policy)
+    }
+
+    /// Create a watch-only StoredKey of the account-level `extended_public_key` of any SLIP-132
+    /// version (xpub, ypub, zpub or their testnet tpub, upub and vpub), whose receive and change
+    /// addresses of secp256k1 coins are derived without any password
+    pub fn create_watch_only_with_extended_public_key(
+        extended_public_key: &str,
+    ) -> Result<StoredKey, Error> {
+        let key = ExtendedPublicKey::from_str(extended_public_key)?;
+        if key.account().is_none() {
+            return Err(Error::CryptoError(CryptoError::InvalidPublicKey));
+        }
+        Self::create_watch_only(WatchedKey::ExtendedPublicKey(
+            extended_public_key.to_owned(),
+        ))
+    }
+
+    /// Create a watch-only StoredKey of the hex encoded `public_key` of `coin`, which has the
+    /// address of the public key only
+    pub fn create_watch_only_with_public_key(
+        public_key: &str,
+        coin: &Coin,
+    ) -> Result<StoredKey, Error> {
+        let public_key_bytes =
+            hex::decode(public_key).map_err(|_| CryptoError::InvalidPublicKey)?;
+        coin_public_key(coin, &public_key_bytes)?;
+        Self::create_watch_only(WatchedKey::PublicKey(hex::encode(public_key_bytes)))
+    }
+
+    fn create_watch_only(watched_key: WatchedKey) -> Result<StoredKey, Error> {
+        let key = match &watched_key {
+            WatchedKey::ExtendedPublicKey(key) | WatchedKey::PublicKey(key) => key,
+        };
+        Ok(StoredKey {
+            r#type: StoredKeyType::WatchOnly,
+            id: Uuid::new_v4().to_string(),
+            hash: compute_hash(&StoredKeyType::WatchOnly, key.as_bytes(), "")?,
+            version: VERSION.to_owned(),
+            payload: None,
+            passphrase: None,
+            watched_key: Some(watched_key),
+            accounts: vec![],
+        })
+    }
+}
+
+// Load & migration methods
+impl StoredKey {
+    /// Deserialize a StoredKey, StoredKeys of a version unknown to this library are refused
+    pub fn from_json(json: &[u8]) -> Result<StoredKey, Error> {
+        let stored_key: StoredKey =
+            serde_json::from_slice(json).map_err(|_| Error::JsonSerializationError)?;
+        match stored_key.version.as_str() {
+            VERSION | LEGACY_VERSION => Ok(stored_key),
+            _ => Err(Error::NotSupportedStoredKeyVersion),
+        }
+    }
+
+    /// Whether the StoredKey has an older version, which is upgraded by `migrate`
+    pub fn needs_migration(&self) -> bool {
+        self.version != VERSION
+    }
+
+    /// Upgrade a StoredKey of an older version to the current one: the fields are encrypted
+    /// with the AEAD cipher and the default `KdfPolicy`, and the hash is computed with the
+    /// current scheme. Legacy StoredKeys have no account list, the client keeps their accounts
+    /// and must add them again, so the list of a migrated StoredKey starts empty.
+    /// Updating the password, rekeying and adding an account migrate the StoredKey first, as
+    /// their requests return it. Signing and exporting read a legacy StoredKey as it is
+    pub fn migrate(&mut self, password: &str) -> Result<(), Error> {
+        if !self.needs_migration() {
+            return Ok(());
         }
-        let private_key = PrivateKey::new(&decrypted)?;
-        let private_key_hex = hex::encode(&private_key.data);
-        Self::create_with_private_key_and_coin(&password, &private_key_hex, coin)
+        self.reencrypt(password, password, KdfPolicy::default())
+    }
+
+    // Decrypt the fields with `password` and encrypt them with `new_password` in the scheme of
+    // the current version, the StoredKey is unchanged if any step fails
+    fn reencrypt(
+        &mut self,
+        password: &str,
+        new_password: &str,
+        kdf_policy: KdfPolicy,
+    ) -> Result<(), Error> {
+        let (decrypted, passphrase_bytes) = self.decrypt_payload_and_passphrase(password)?;
+        let passphrase = std::str::from_utf8(&passphrase_bytes)
+            .map_err(|_| Error::CryptoError(CryptoError::PasswordIncorrect))?;
+        let hash = compute_hash(&self.r#type, &decrypted, passphrase)?;
+        let (payload, passphrase) = encrypt_fields(
+            &self.id,
+            &self.r#type,
+            new_password,
+            &decrypted,
+            passphrase,
+            kdf_policy,
+        )?;
+        self.hash = hash;
+        self.version = VERSION.to_owned();
+        self.payload = Some(payload);
+        self.passphrase = passphrase;
+        Ok(())
     }
 }
 
 // Update methods
 impl StoredKey {
-    pub fn update_password(&mut self, old_password: &str, new_password: &str) -> Result<(), Error> {
-        let decrypted = self.payload.decrypt(&old_password.as_bytes())?;
-        self.payload = EncryptionParams::new(new_password.as_bytes(), &decrypted)?;
-        Ok(())
+    /// Re-encrypt the StoredKey with `new_password`, the key of which is derived by `kdf_policy`,
+    /// StoredKeys encrypted with any older kdf or of any older version are upgraded this way
+    pub fn update_password(
+        &mut self,
+        old_password: &str,
+        new_password: &str,
+        kdf_policy: KdfPolicy,
+    ) -> Result<(), Error> {
+        self.reencrypt(old_password, new_password, kdf_policy)
+    }
+
+    /// Re-encrypt the StoredKey with the same `password`, the key of which is derived by
+    /// `kdf_policy`, to upgrade the kdf and the cipher of the StoredKey
+    pub fn rekey(&mut self, password: &str, kdf_policy: KdfPolicy) -> Result<(), Error> {
+        self.reencrypt(password, password, kdf_policy)
     }
 }
 
 // Export methods
 impl StoredKey {
-    pub fn export_private_key(&mut self, password: &str, coin: &Coin) -> Result<String, Error> {
+    pub fn export_private_key(
+        &self,
+        password: &str,
+        coin: &Coin,
+    ) -> Result<Zeroizing<String>, Error> {
         let private_key = self.decrypt_private_key(&password, &coin)?;
-        Ok(private_key.to_string())
+        Ok(Zeroizing::new(private_key.to_string()))
     }
 
     pub fn export_private_key_of_path(
-        &mut self,
+        &self,
         password: &str,
         coin: &Coin,
         derivation_path: &str,
-    ) -> Result<String, Error> {
-        if self.r#type != StoredKeyType::Mnemonic {
-            return Err(Error::RequestNotSupportedOnPrivateKeyTypeStoredKey);
-        }
+    ) -> Result<Zeroizing<String>, Error> {
+        self.check_mnemonic_type()?;
         let wallet = self.get_wallet(&password)?;
         let derivation_path = DerivationPath::new(&derivation_path)?;
         let private_key = wallet.get_key(&coin, &derivation_path)?;
-        Ok(private_key.to_string())
+        Ok(Zeroizing::new(private_key.to_string()))
     }
 
-    pub fn export_mnemonic(&self, password: &str) -> Result<String, Error> {
-        if self.r#type != StoredKeyType::Mnemonic {
-            return Err(Error::RequestNotSupportedOnPrivateKeyTypeStoredKey);
-        }
-        let mnemonic_bytes = self.payload.decrypt(&password.as_bytes())?;
+    pub fn export_mnemonic(&self, password: &str) -> Result<Zeroizing<String>, Error> {
+        self.check_mnemonic_type()?;
+        let mnemonic_bytes = self.decrypt_payload(password)?;
         let mnemonic = std::str::from_utf8(&mnemonic_bytes)
             .map_err(|_| Error::CryptoError(CryptoError::PasswordIncorrect))?;
-        Ok(mnemonic.to_owned())
+        Ok(Zeroizing::new(mnemonic.to_owned()))
+    }
+
+    /// The public key at the first `depth` indices of `derivation_path`, which is an extended
+    /// public key for secp256k1 coins and the hex encoded SLIP-10 public key for ed25519 coins.
+    /// The version of extended public keys defaults to the one of the purpose of the path
+    pub fn export_extended_public_key(
+        &self,
+        password: &str,
+        coin: &Coin,
+        derivation_path: &str,
+        depth: usize,
+        version: Option<ExtendedPublicKeyVersion>,
+    ) -> Result<String, Error> {
+        self.check_mnemonic_type()?;
+        let wallet = self.get_wallet(password)?;
+        let derivation_path = DerivationPath::new(derivation_path)?;
+        wallet.get_extended_public_key_at_depth(coin, &derivation_path, depth, version)
     }
 
     pub fn export_key_store_json(
-        &mut self,
+        &self,
         password: &str,
         new_password: &str,
+        kdf_params: KdfParams,
     ) -> Result<String, Error> {
-        self.payload
-            .export_to_key_store_json(&password, &new_password)
+        let decrypted = self.decrypt_payload(password)?;
+        EncryptionParams::export_to_key_store_json(&decrypted, new_password, kdf_params)
     }
 
     pub fn export_key_store_json_of_address(
-        &mut self,
+        &self,
         password: &str,
         new_password: &str,
         coin: &Coin,
-        derivation_path: &str,
+        address: &str,
+        kdf_params: KdfParams,
     ) -> Result<String, Error> {
         if self.r#type == StoredKeyType::PrivateKey {
             // Convert the payload to KeyStoreJSON
-            self.payload
-                .export_to_key_store_json(&password, &new_password)
+            let decrypted = self.decrypt_payload(password)?;
+            EncryptionParams::export_to_key_store_json(&decrypted, new_password, kdf_params)
         } else {
-            // 1. If this StoredKey is created from a mnemonic, derive to the specific path to get the private key
+            // 1. If this StoredKey is created from a mnemonic, derive to the path of the address's account to get the private key
+            let derivation_path = self
+                .account_for_address(address, coin)?
+                .derivation_path
+                .clone();
             let wallet = self.get_wallet(&password)?;
-            let derivation_path_struct = DerivationPath::new(&derivation_path)?;
-            let private_key = wallet.get_key(&coin, &derivation_path_struct)?;
+            let private_key = wallet.get_key(coin, &derivation_path)?;
 
-            // 2. Create a temp EncryptionParam with new password for exporting
-            let temp_encryption_param =
-                EncryptionParams::new(new_password.as_bytes(), &private_key.data)?;
-            temp_encryption_param.export_to_key_store_json(&new_password, &new_password)
+            // 2. Encrypt the private key with the new password
+            EncryptionParams::export_to_key_store_json(&private_key.data, new_password, kdf_params)
         }
     }
 
     pub fn export_key_store_json_of_path(
-        &mut self,
+        &self,
         password: &str,
         new_password: &str,
         coin: &Coin,
         derivation_path: &str,
+        kdf_params: KdfParams,
     ) -> Result<String, Error> {
         if self.r#type == StoredKeyType::PrivateKey {
             // 1. If this StoredKey is created by importing a private key, simply export it
-            return self
-                .payload
-                .export_to_key_store_json(&password, &new_password);
+            let decrypted = self.decrypt_payload(password)?;
+            return EncryptionParams::export_to_key_store_json(
+                &decrypted,
+                new_password,
+                kdf_params,
+            );
         }
         // 2. If this StoredKey is created from a mnemonic, derive to the specific path to get the private key
         let wallet = self.get_wallet(&password)?;
         let derivation_path = DerivationPath::new(&derivation_path)?;
         let private_key = wallet.get_key(&coin, &derivation_path)?;
 
-        // 3. Create a temp EncryptionParam with new password for exporting
-        let temp_encryption_param =
-            EncryptionParams::new(new_password.as_bytes(), &private_key.data)?;
-        temp_encryption_param.export_to_key_store_json(&new_password, &new_password)
+        // 3. Encrypt the private key with the new password
+        EncryptionParams::export_to_key_store_json(&private_key.data, new_password, kdf_params)
+    }
+}
+
+// Decryption of the payload and the passphrase
+impl StoredKey {
+    // The scheme of the decrypted fields is selected by the version of the StoredKey
+    fn decrypt_field(
+        &self,
+        params: &EncryptionParams,
+        field: &str,
+        password: &str,
+    ) -> Result<Zeroizing<Vec<u8>>, Error> {
+        if params.is_aead() == (self.version == LEGACY_VERSION) {
+            return Err(Error::CryptoError(CryptoError::NotSupportedCipher));
+        }
+        params.decrypt_with_associated_data(
+            password.as_bytes(),
+            &associated_data(&self.id, &self.r#type, &self.version, field),
+        )
+    }
+
+    fn decrypt_payload(&self, password: &str) -> Result<Zeroizing<Vec<u8>>, Error> {
+        let payload = self
+            .payload
+            .as_ref()
+            .ok_or(Error::RequestNotSupportedOnWatchOnlyStoredKey)?;
+        self.decrypt_field(payload, PAYLOAD_FIELD, password)
     }
+
+    // The passphrase is opened with the key of the payload, which is derived once
+    fn decrypt_payload_and_passphrase(&self, password: &str) -> Result<DecryptedFields, Error> {
+        let sealed_passphrase = match &self.passphrase {
+            Some(passphrase) => passphrase,
+            None => return Ok((self.decrypt_payload(password)?, Zeroizing::new(vec![]))),
+        };
+        let payload = self
+            .payload
+            .as_ref()
+            .ok_or(Error::RequestNotSupportedOnWatchOnlyStoredKey)?;
+        let associated_data =
+            |field: &str| associated_data(&self.id, &self.r#type, &self.version, field);
+        let key = payload.aead_key(password.as_bytes())?;
+        let decrypted = payload.decrypt_with_key(&key, &associated_data(PAYLOAD_FIELD))?;
+        let passphrase = key.open(sealed_passphrase, &associated_data(PASSPHRASE_FIELD))?;
+        Ok((decrypted, passphrase))
+    }
+
+    // Requests of the mnemonic, which only mnemonic StoredKeys have
+    fn check_mnemonic_type(&self) -> Result<(), Error> {
+        match self.r#type {
+            StoredKeyType::Mnemonic => Ok(()),
+            StoredKeyType::PrivateKey => Err(Error::RequestNotSupportedOnPrivateKeyTypeStoredKey),
+            StoredKeyType::WatchOnly => Err(Error::RequestNotSupportedOnWatchOnlyStoredKey),
+        }
+    }
+}
+
+// Hash identifying the key of the StoredKey, for a mnemonic it's the hash of the seed, and for
+// a watch-only StoredKey it's the hash of the encoded public key
+fn compute_hash(r#type: &StoredKeyType, data: &[u8], passphrase: &str) -> Result<String, Error> {
+    let hash = match r#type {
+        StoredKeyType::PrivateKey | StoredKeyType::WatchOnly => hash::dsha256(data),
+        StoredKeyType::Mnemonic => {
+            let mnemonic_str = std::str::from_utf8(data)
+                .map_err(|_| Error::CryptoError(CryptoError::PasswordIncorrect))?;
+            let mnemonic = Mnemonic::new(mnemonic_str, passphrase)?;
+            hash::dsha256(&mnemonic.seed)
+        }
+    };
+    Ok(hex::encode(hash))
+}
+
+// The public key of `coin` encoded from `public_key`, secp256k1 keys may be either compressed
+// or uncompressed
+fn coin_public_key(coin: &Coin, public_key: &[u8]) -> Result<PublicKey, Error> {
+    let public_key_type = PublicKeyType::from_str(&coin.public_key_type)
+        .map_err(|_| CryptoError::NotSupportedPublicKeyType)?;
+    let data = convert_public_key(&coin.public_key_type, public_key)?;
+    Ok(PublicKey::new(public_key_type, &data)?)
+}
+
+// Encrypt `data` with the AEAD cipher of the current version, and seal the passphrase with the
+// same key, so that the key is derived only once
+fn encrypt_fields(
+    id: &str,
+    r#type: &StoredKeyType,
+    password: &str,
+    data: &[u8],
+    passphrase: &str,
+    kdf_policy: KdfPolicy,
+) -> Result<(EncryptionParams, Option<SealedData>), Error> {
+    let associated_data = |field: &str| associated_data(id, r#type, VERSION, field);
+    let (payload, key) = EncryptionParams::new_aead_with_key(
+        password.as_bytes(),
+        data,
+        kdf_policy,
+        AEAD_TYPE,
+        &associated_data(PAYLOAD_FIELD),
+    )?;
+    let passphrase = match passphrase {
+        "" => None,
+        passphrase => Some(key.seal(passphrase.as_bytes(), &associated_data(PASSPHRASE_FIELD))?),
+    };
+    Ok((payload, passphrase))
+}
+
+// Metadata authenticated together with the encrypted `field` of a StoredKey, so that tampering
+// with the id, type or version of the StoredKey fails the decryption
+fn associated_data(id: &str, r#type: &StoredKeyType, version: &str, field: &str) -> Vec<u8> {
+    let r#type = match r#type {
+        StoredKeyType::PrivateKey => "PrivateKey",
+        StoredKeyType::Mnemonic => "Mnemonic",
+        StoredKeyType::WatchOnly => "WatchOnly",
+    };
+    format!("{}|{}|{}|{}", id, r#type, version, field).into_bytes()
 }
 
 // Get Hd Wallet
 impl StoredKey {
     fn get_wallet(&self, password: &str) -> Result<HdWallet, Error> {
-        if self.r#type != StoredKeyType::Mnemonic {
-            return Err(Error::RequestNotSupportedOnPrivateKeyTypeStoredKey);
-        }
-        let mnemonic_bytes = self.payload.decrypt(&password.as_bytes())?;
+        self.check_mnemonic_type()?;
+        let (mnemonic_bytes, passphrase_bytes) = self.decrypt_payload_and_passphrase(password)?;
         let mnemonic = std::str::from_utf8(&mnemonic_bytes)
             .map_err(|_| Error::CryptoError(CryptoError::PasswordIncorrect))?;
-        HdWallet::new_with_mnemonic(&mnemonic, "")
+        let passphrase = std::str::from_utf8(&passphrase_bytes)
+            .map_err(|_| Error::CryptoError(CryptoError::PasswordIncorrect))?;
+        HdWallet::new_with_mnemonic(mnemonic, passphrase)
     }
 }
 
 // Account related methods
 impl StoredKey {
-    fn get_or_create_account_for_coin(
+    pub fn add_new_account_of_coin_and_derivation_path_by_password(
         &mut self,
         name: &str,
         coin: &Coin,
-        hd_wallet: &HdWallet,
+        derivation_path: &str,
+        password: &str,
     ) -> Result<Account, Error> {
-        // No valid account found for the coin, create a new one
-        let address = hd_wallet.get_address_for_coin(&coin)?;
-        let extended_public_key = hd_wallet.get_extended_public_key(&coin);
-        let account = Account::new(
-            &address,
-            name,
-            coin.clone(),
-            &coin.derivation_path,
-            &extended_public_key,
-        )?;
+        self.migrate(password)?;
+        let account =
+            self.new_account_of_coin_and_derivation_path(name, coin, derivation_path, password)?;
+        if self.account_for_address(&account.address, coin).is_ok() {
+            return Err(Error::AccountAlreadyExist);
+        }
+        self.accounts.push(account.clone());
         Ok(account)
     }
 
-    pub fn add_new_account_of_coin_and_derivation_path_by_password(
+    fn new_account_of_coin_and_derivation_path(
         &self,
         name: &str,
         coin: &Coin,
         derivation_path: &str,
         password: &str,
     ) -> Result<Account, Error> {
+        if self.r#type == StoredKeyType::WatchOnly {
+            return self.new_watch_only_account(name, coin, derivation_path);
+        }
         if self.r#type == StoredKeyType::PrivateKey {
-            let decrypted = self.payload.decrypt(&password.as_bytes())?;
-            let private_key = PrivateKey::new(&decrypted)?;
+            let decrypted = self.decrypt_payload(password)?;
+            let private_key = PrivateKey::new_of_coin(&decrypted, coin)?;
 
             let public_key = private_key.get_public_key(&coin.public_key_type)?;
-            let address =
-                CoinDispatcher::get_entry(&coin)?.derive_address(&coin, &public_key, &[], &[])?;
+            let address = derive_address_with_public_key(coin, &public_key, &coin.derivation_path)?;
             let account = Account::new(&address, name, coin.clone(), &coin.derivation_path, "")?;
             return Ok(account);
         }
 
         let wallet = self.get_wallet(&password)?;
         let address = wallet.get_address_for_coin_of_path(&coin, &derivation_path)?;
-        let extended_public_key = wallet.get_extended_public_key_of_path(&coin, &derivation_path);
+        let extended_public_key = wallet.get_extended_public_key_of_path(coin, derivation_path)?;
         let account = Account::new(
             &address,
             name,
@@ -283,29 +649,155 @@ impl StoredKey {
         )?;
         Ok(account)
     }
+
+    // Accounts of a watch-only StoredKey need no password. An account of an extended public key
+    // must be at a non-hardened `change/index` path below the key, an empty path is the first
+    // receive address. The key must be of the network of the coin, testnet keys of the testnet
+    // coin type only, and segwit and taproot keys of bitcoin only. A public key has the same
+    // address at every path
+    fn new_watch_only_account(
+        &self,
+        name: &str,
+        coin: &Coin,
+        derivation_path: &str,
+    ) -> Result<Account, Error> {
+        let (public_key, derivation_path) = match &self.watched_key {
+            Some(WatchedKey::ExtendedPublicKey(key)) => {
+                if !matches!(Curve::from_str(&coin.curve)?, Curve::Secp256k1) {
+                    return Err(Error::CryptoError(CryptoError::NotSupportedCurve));
+                }
+                let key = ExtendedPublicKey::from_str(key)?;
+                if key.version.is_testnet() != (coin.coin_id == TESTNET_COIN_ID) {
+                    return Err(Error::CryptoError(CryptoError::InvalidPublicKey));
+                }
+                let path = match derivation_path {
+                    "" => None,
+                    path => Some(DerivationPath::new(path)?),
+                };
+                // Taproot keys have no version of their own, so the purpose of the path tells them
+                // from legacy keys
+                let purpose = match path.as_ref().and_then(|path| path.purpose()) {
+                    Some(BIP86_PURPOSE) if key.purpose() == BIP44_PURPOSE => BIP86_PURPOSE,
+                    _ => key.purpose(),
+                };
+                if purpose != BIP44_PURPOSE
+                    && ProtoCoinType::from_str(&coin.name)? != ProtoCoinType::Bitcoin
+                {
+                    return Err(Error::CryptoError(CryptoError::InvalidPublicKey));
+                }
+                let account_path = format!(
+                    "m/{}'/{}'/{}'",
+                    purpose,
+                    coin.coin_id,
+                    key.account()
+                        .ok_or(Error::CryptoError(CryptoError::InvalidPublicKey))?
+                );
+                let derivation_path = match path {
+                    Some(path) => path.to_string(),
+                    None => format!("{}/0/0", account_path),
+                };
+                let relative_path = derivation_path
+                    .strip_prefix(&account_path)
+                    .filter(|path| path.matches('/').count() == 2)
+                    .ok_or(Error::CryptoError(CryptoError::InvalidDerivationpath))?;
+                let public_key = key.derive_public_key(&format!("m{}", relative_path))?;
+                (coin_public_key(coin, &public_key)?, derivation_path)
+            }
+            Some(WatchedKey::PublicKey(key)) => {
+                let public_key = hex::decode(key).map_err(|_| CryptoError::InvalidPublicKey)?;
+                (
+                    coin_public_key(coin, &public_key)?,
+                    coin.derivation_path.clone(),
+                )
+            }
+            None => return Err(Error::CryptoError(CryptoError::InvalidPublicKey)),
+        };
+        let address = derive_address_with_public_key(coin, &public_key, &derivation_path)?;
+        Account::new(&address, name, coin.clone(), &derivation_path, "")
+    }
+
+    pub fn get_all_accounts(&self) -> &[Account] {
+        &self.accounts
+    }
+
+    /// The account of `coin` whose address is `address`
+    pub fn account_for_address(&self, address: &str, coin: &Coin) -> Result<&Account, Error> {
+        self.accounts
+            .iter()
+            .find(|account| account.address == address && account.coin.id == coin.id)
+            .ok_or(Error::RequstedAccountNotFound)
+    }
+
+    /// Accounts matching every given filter, an empty filter matches any account
+    pub fn get_accounts(
+        &self,
+        coins: &[&Coin],
+        derivation_path: &str,
+        address: &str,
+    ) -> Result<Vec<&Account>, Error> {
+        let derivation_path = match derivation_path {
+            "" => None,
+            path => Some(DerivationPath::new(path)?),
+        };
+        let accounts = self
+            .accounts
+            .iter()
+            .filter(|account| {
+                coins.is_empty() || coins.iter().any(|coin| coin.id == account.coin.id)
+            })
+            .filter(|account| match &derivation_path {
+                Some(path) => &account.derivation_path == path,
+                None => true,
+            })
+            .filter(|account| address.is_empty() || account.address == address)
+            .collect();
+        Ok(accounts)
+    }
+
+    pub fn remove_account(&mut self, address: &str, coin: &Coin) -> Result<Account, Error> {
+        let index = self
+            .accounts
+            .iter()
+            .position(|account| account.address == address && account.coin.id == coin.id)
+            .ok_or(Error::RequstedAccountNotFound)?;
+        Ok(self.accounts.remove(index))
+    }
+
+    pub fn rename_account(
+        &mut self,
+        address: &str,
+        coin: &Coin,
+        name: &str,
+    ) -> Result<Account, Error> {
+        let account = self
+            .accounts
+            .iter_mut()
+            .find(|account| account.address == address && account.coin.id == coin.id)
+            .ok_or(Error::RequstedAccountNotFound)?;
+        account.name = name.to_owned();
+        Ok(account.clone())
+    }
 }
 
 // Decrypt methods
 impl StoredKey {
     pub fn validate_password(&self, password: &str) -> bool {
-        self.payload.decrypt(password.as_bytes()).is_ok()
+        self.decrypt_payload(password).is_ok()
     }
 
-    pub fn decrypt_private_key(
-        &mut self,
-        password: &str,
-        coin: &Coin,
-    ) -> Result<PrivateKey, Error> {
+    /// The private key of `coin`, a mnemonic StoredKey derives it at the derivation path of
+    /// `coin`
+    pub fn decrypt_private_key(&self, password: &str, coin: &Coin) -> Result<PrivateKey, Error> {
         match self.r#type {
             StoredKeyType::Mnemonic => {
-                let wallet = self.get_wallet(&password)?;
-                let account = self.get_or_create_account_for_coin("", &coin, &wallet)?;
-                wallet.get_key(&coin, &account.derivation_path)
+                let derivation_path = DerivationPath::new(&coin.derivation_path)?;
+                self.get_wallet(password)?.get_key(coin, &derivation_path)
             }
             StoredKeyType::PrivateKey => {
-                let decrypted = self.payload.decrypt(&password.as_bytes())?;
-                Ok(PrivateKey::new(&decrypted)?)
+                let decrypted = self.decrypt_payload(password)?;
+                Ok(PrivateKey::new_of_coin(&decrypted, coin)?)
             }
+            StoredKeyType::WatchOnly => Err(Error::RequestNotSupportedOnWatchOnlyStoredKey),
         }
     }
 }
@@ -313,24 +805,86 @@ impl StoredKey {
 // Sign methods
 impl StoredKey {
     pub fn sign(
-        &mut self,
+        &self,
         coin: &Coin,
         password: &str,
         derivation_path: &str,
         payload: &[u8],
     ) -> Result<Vec<u8>, Error> {
-        let private_key = match self.r#type {
+        let (private_key, wallet) = match self.r#type {
             StoredKeyType::Mnemonic => {
                 let deriation_path_struct = DerivationPath::new(&derivation_path)?;
                 let wallet = self.get_wallet(&password)?;
-                wallet.get_key(&coin, &deriation_path_struct)?
+                (wallet.get_key(coin, &deriation_path_struct)?, Some(wallet))
             }
             StoredKeyType::PrivateKey => {
-                let decrypted = self.payload.decrypt(&password.as_bytes())?;
-                PrivateKey::new(&decrypted)?
+                let decrypted = self.decrypt_payload(password)?;
+                (PrivateKey::new_of_coin(&decrypted, coin)?, None)
             }
+            StoredKeyType::WatchOnly => return Err(Error::RequestNotSupportedOnWatchOnlyStoredKey),
         };
-        Ok(CoinDispatcher::get_entry(&coin)?.sign(&coin, &private_key, &payload)?)
+        // Keys of the other derivation paths the payload is signed with,
+        // a private key StoredKey has the same key at every path
+        let get_key = |path: &str| -> Result<PrivateKey, CryptoError> {
+            let key = match &wallet {
+                Some(wallet) => {
+                    DerivationPath::new(path).and_then(|path| wallet.get_key(coin, &path))
+                }
+                None => Ok(private_key.clone()),
+            };
+            key.map_err(|error| match error {
+                Error::CryptoError(error) => error,
+                _ => CryptoError::InvalidDerivationpath,
+            })
+        };
+        Ok(CoinDispatcher::get_entry(coin)?.sign_with_keys(
+            coin,
+            &private_key,
+            &get_key,
+            payload,
+        )?)
+    }
+
+    /// Sign `message` with the key at `derivation_path`, see `Entry::sign_message`
+    pub fn sign_message(
+        &self,
+        coin: &Coin,
+        password: &str,
+        derivation_path: &str,
+        message: &[u8],
+        raw_hash: bool,
+    ) -> Result<Vec<u8>, Error> {
+        let private_key = self.get_signing_key(coin, password, derivation_path)?;
+        Ok(CoinDispatcher::get_entry(coin)?.sign_message(coin, &private_key, message, raw_hash)?)
+    }
+
+    /// Sign the JSON `typed_data` with the key at `derivation_path`, see
+    /// `Entry::sign_typed_data`
+    pub fn sign_typed_data(
+        &self,
+        coin: &Coin,
+        password: &str,
+        derivation_path: &str,
+        typed_data: &str,
+    ) -> Result<Vec<u8>, Error> {
+        let private_key = self.get_signing_key(coin, password, derivation_path)?;
+        Ok(CoinDispatcher::get_entry(coin)?.sign_typed_data(coin, &private_key, typed_data)?)
+    }
+
+    // The key at `derivation_path` of a mnemonic, or the private key of other stored keys
+    fn get_signing_key(
+        &self,
+        coin: &Coin,
+        password: &str,
+        derivation_path: &str,
+    ) -> Result<PrivateKey, Error> {
+        match self.r#type {
+            StoredKeyType::Mnemonic => {
+                let derivation_path = DerivationPath::new(derivation_path)?;
+                Ok(self.get_wallet(password)?.get_key(coin, &derivation_path)?)
+            }
+            _ => self.decrypt_private_key(password, coin),
+        }
     }
 }
 
@@ -339,6 +893,7 @@ impl From<StoredKey> for StoredKeyInfo {
         let json = serde_json::to_vec(&stored_key).unwrap().to_vec();
         StoredKeyInfo {
             data: json,
+            needs_migration: stored_key.needs_migration(),
             id: stored_key.id,
             hash: stored_key.hash,
             r#type: stored_key.r#type as i32,
@@ -351,6 +906,7 @@ impl From<StoredKeyType> for ProtoStoreKeyType {
         match stored_key_type {
             StoredKeyType::PrivateKey => ProtoStoreKeyType::PrivateKey,
             StoredKeyType::Mnemonic => ProtoStoreKeyType::Mnemonic,
+            StoredKeyType::WatchOnly => ProtoStoreKeyType::WatchOnly,
         }
     }
 }
@@ -359,6 +915,7 @@ impl From<StoredKeyType> for ProtoStoreKeyType {
 mod tests {
     use super::*;
     use chain_common::coin::Coin;
+    use crypto::pbkdf2_params::Pbkdf2Params;
     use std::collections::HashMap;
     #[test]
     fn test_create_stored_key_with_private_key() {
@@ -378,8 +935,13 @@ mod tests {
             all_info: HashMap::new(),
         };
 
-        let stored_key =
-            StoredKey::create_with_private_key_and_coin(&password, priv_key_str, &coin).unwrap();
+        let mut stored_key = StoredKey::create_with_private_key_and_coin(
+            password,
+            priv_key_str,
+            &coin,
+            KdfPolicy::default(),
+        )
+        .unwrap();
         assert_eq!(stored_key.version, VERSION);
         let account = stored_key
             .add_new_account_of_coin_and_derivation_path_by_password("mask", &coin, "", &password)
@@ -411,7 +973,7 @@ mod tests {
             all_info: HashMap::new(),
         };
 
-        let stored_key = StoredKey::create_with_mnemonic(&password, &mnemonic).unwrap();
+        let mut stored_key = StoredKey::create_with_mnemonic(password, mnemonic).unwrap();
         let account = stored_key
             .add_new_account_of_coin_and_derivation_path_by_password(
                 "mask",
@@ -421,16 +983,19 @@ mod tests {
             )
             .unwrap();
         assert_eq!(stored_key.r#type == StoredKeyType::Mnemonic, true);
-        let decrypted = stored_key.payload.decrypt(password.as_bytes()).unwrap();
-        assert_eq!(&decrypted, mnemonic.as_bytes());
+        let decrypted = stored_key.decrypt_payload(password).unwrap();
+        assert_eq!(decrypted.as_slice(), mnemonic.as_bytes());
         assert_eq!(
             account.address,
             "0x494f60cb6Ac2c8F5E1393aD9FdBdF4Ad589507F7"
         );
         assert_eq!(account.derivation_path.to_string(), derivation_path);
         assert_eq!(account.coin.name, "ethereum");
-        assert_eq!(account.extended_public_key, "");
-        assert_eq!(stored_key.export_mnemonic(password).unwrap(), mnemonic);
+        assert_eq!(account.extended_public_key, "xpub6DPKpgmhfUaM23JAxQMXPx9SuLNQbP9s5ph1eUfLejosCb1T3Tg2fheaH153K6t5pPUuorTGHVPfoegDzQki3ioQSpXDH68YaogEeMYrZCR");
+        assert_eq!(
+            stored_key.export_mnemonic(password).unwrap().as_str(),
+            mnemonic
+        );
     }
 
     #[test]
@@ -478,6 +1043,7 @@ mod tests {
             &stored_key_password,
             &json,
             &coin,
+            KdfPolicy::default(),
         )
         .unwrap();
         let account = stored_key
@@ -494,11 +1060,22 @@ mod tests {
         let new_password = "password_new";
         let new_password2 = "password_new2";
         let exported_json = stored_key
-            .export_key_store_json_of_address(&stored_key_password, &new_password, &coin, &address)
+            .export_key_store_json_of_address(
+                stored_key_password,
+                new_password,
+                &coin,
+                address,
+                KdfParams::default(),
+            )
             .unwrap();
-        let stored_key2 =
-            StoredKey::create_with_json(&new_password, &new_password2, &exported_json, &coin)
-                .unwrap();
+        let mut stored_key2 = StoredKey::create_with_json(
+            new_password,
+            new_password2,
+            &exported_json,
+            &coin,
+            KdfPolicy::default(),
+        )
+        .unwrap();
         let account2 = stored_key2
             .add_new_account_of_coin_and_derivation_path_by_password(
                 "mask",
@@ -530,7 +1107,7 @@ mod tests {
             all_info: HashMap::new(),
         };
 
-        let stored_key = StoredKey::create_with_mnemonic(&password, &mnemonic).unwrap();
+        let mut stored_key = StoredKey::create_with_mnemonic(password, mnemonic).unwrap();
         let test_derivation_path1 = "m/44'/60'/0'/0/1";
         let account1 = stored_key
             .add_new_account_of_coin_and_derivation_path_by_password(
@@ -541,75 +1118,872 @@ mod tests {
             )
             .unwrap();
         assert_eq!(account1.derivation_path.to_string(), test_derivation_path1);
-    }
-
-    #[test]
-    fn test_hash() {
-        let mnemonic1 =
-            "suffer artefact burst review network fantasy easy century mom unique pupil boy";
-        let password = "";
-        let stored_key1 = StoredKey::create_with_mnemonic(&password, &mnemonic1).unwrap();
-        let stored_key2 = StoredKey::create_with_mnemonic(&password, &mnemonic1).unwrap();
-        assert_eq!(stored_key1.hash, stored_key2.hash);
 
-        let (stored_key_random, _) = StoredKey::create_with_mnemonic_random(&password).unwrap();
-        assert_ne!(stored_key1.hash, stored_key_random.hash);
-        assert_ne!(stored_key2.hash, stored_key_random.hash);
-    }
-
-    #[test]
-    fn test_update_password() {
-        let mnemonic1 =
-            "suffer artefact burst review network fantasy easy century mom unique pupil boy";
-        let password1 = "password 1";
-        let password2 = "password 2";
-        let mut stored_key1 = StoredKey::create_with_mnemonic(&password1, &mnemonic1).unwrap();
-        stored_key1.update_password(&password1, &password2).unwrap();
-        let mnemonic2 = stored_key1.export_mnemonic(&password2).unwrap();
-        assert_eq!(mnemonic1, mnemonic2);
-
-        let failed = stored_key1.export_mnemonic(&password1);
-        assert_eq!(failed.is_err(), true);
+        // The private key of the coin is at its derivation path, whatever the accounts are
+        assert_eq!(
+            stored_key.export_private_key(password, &coin).unwrap(),
+            stored_key
+                .export_private_key_of_path(password, &coin, derivation_path)
+                .unwrap()
+        );
+        assert_ne!(
+            stored_key.export_private_key(password, &coin).unwrap(),
+            stored_key
+                .export_private_key_of_path(password, &coin, test_derivation_path1)
+                .unwrap()
+        );
+        assert_eq!(stored_key.get_all_accounts().len(), 1);
     }
 
     #[test]
-    fn test_create_solana_account_at_path() {
+    fn test_persist_accounts() {
         let mnemonic =
-            "shoot island position soft burden budget tooth cruel issue economy destroy above";
+            "suffer artefact burst review network fantasy easy century mom unique pupil boy";
         let password = "";
-        let derivation_path = "m/44'/501'/0'";
         let coin = Coin {
-            id: "501".to_owned(),
-            name: "solana".to_owned(),
-            coin_id: 501,
-            symbol: "SOL".to_owned(),
-            decimals: 9,
-            blockchain: "Solana".to_owned(),
-            derivation_path: derivation_path.to_owned(),
-            curve: "ed25519".to_owned(),
-            public_key_type: "ed25519".to_owned(),
+            id: "60".to_owned(),
+            name: "ethereum".to_owned(),
+            coin_id: 60,
+            symbol: "ETH".to_owned(),
+            decimals: 18,
+            blockchain: "Ethereum".to_owned(),
+            derivation_path: "m/44'/60'/0'/0/0".to_owned(),
+            curve: "secp256k1".to_owned(),
+            public_key_type: "secp256k1Extended".to_owned(),
             all_info: HashMap::new(),
         };
 
-        let stored_key = StoredKey::create_with_mnemonic(&password, &mnemonic).unwrap();
-        let test_derivation_path1 = "m/44'/501'/0'";
+        let mut stored_key = StoredKey::create_with_mnemonic(password, mnemonic).unwrap();
+        assert!(stored_key.get_all_accounts().is_empty());
         let account1 = stored_key
             .add_new_account_of_coin_and_derivation_path_by_password(
-                "mask",
+                "account 1",
                 &coin,
-                &test_derivation_path1,
-                &password,
+                "m/44'/60'/0'/0/0",
+                password,
             )
             .unwrap();
-        assert_eq!(account1.derivation_path.to_string(), "m/44'/501'/0'");
-        assert_eq!(
-            account1.address,
-            "2bUBiBNZyD29gP1oV6de7nxowMLoDBtopMMTGgMvjG5m"
-        );
-    }
-
-    #[test]
-    fn test_create_solana_account2_at_path() {
+        let account2 = stored_key
+            .add_new_account_of_coin_and_derivation_path_by_password(
+                "account 2",
+                &coin,
+                "m/44'/60'/0'/0/1",
+                password,
+            )
+            .unwrap();
+        let duplicated = stored_key.add_new_account_of_coin_and_derivation_path_by_password(
+            "account 3",
+            &coin,
+            "m/44'/60'/0'/0/1",
+            password,
+        );
+        assert_eq!(duplicated.err(), Some(Error::AccountAlreadyExist));
+
+        // Accounts are kept in the serialized StoredKey
+        let json = serde_json::to_string(&stored_key).unwrap();
+        let mut stored_key: StoredKey = serde_json::from_str(&json).unwrap();
+        let addresses: Vec<&str> = stored_key
+            .get_all_accounts()
+            .iter()
+            .map(|account| account.address.as_str())
+            .collect();
+        assert_eq!(addresses, vec![&account1.address, &account2.address]);
+
+        let renamed = stored_key
+            .rename_account(&account2.address, &coin, "renamed")
+            .unwrap();
+        assert_eq!(renamed.name, "renamed");
+        assert_eq!(
+            stored_key
+                .account_for_address(&account2.address, &coin)
+                .unwrap()
+                .name,
+            "renamed"
+        );
+
+        let removed = stored_key.remove_account(&account1.address, &coin).unwrap();
+        assert_eq!(removed.address, account1.address);
+        assert_eq!(stored_key.get_all_accounts().len(), 1);
+        assert_eq!(
+            stored_key.remove_account(&account1.address, &coin).err(),
+            Some(Error::RequstedAccountNotFound)
+        );
+        assert_eq!(
+            stored_key
+                .rename_account(&account1.address, &coin, "name")
+                .err(),
+            Some(Error::RequstedAccountNotFound)
+        );
+
+        // StoredKeys serialized without accounts can still be loaded
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value.as_object_mut().unwrap().remove("accounts");
+        let stored_key: StoredKey = serde_json::from_value(value).unwrap();
+        assert!(stored_key.get_all_accounts().is_empty());
+    }
+
+    #[test]
+    fn test_get_accounts() {
+        let mnemonic =
+            "shoot island position soft burden budget tooth cruel issue economy destroy above";
+        let password = "";
+        let ethereum = Coin {
+            id: "60".to_owned(),
+            name: "ethereum".to_owned(),
+            coin_id: 60,
+            symbol: "ETH".to_owned(),
+            decimals: 18,
+            blockchain: "Ethereum".to_owned(),
+            derivation_path: "m/44'/60'/0'/0/0".to_owned(),
+            curve: "secp256k1".to_owned(),
+            public_key_type: "secp256k1Extended".to_owned(),
+            all_info: HashMap::new(),
+        };
+        let solana = Coin {
+            id: "501".to_owned(),
+            name: "solana".to_owned(),
+            coin_id: 501,
+            symbol: "SOL".to_owned(),
+            decimals: 9,
+            blockchain: "Solana".to_owned(),
+            derivation_path: "m/44'/501'/0'".to_owned(),
+            curve: "ed25519".to_owned(),
+            public_key_type: "ed25519".to_owned(),
+            all_info: HashMap::new(),
+        };
+
+        let mut stored_key = StoredKey::create_with_mnemonic(password, mnemonic).unwrap();
+        let ethereum1 = stored_key
+            .add_new_account_of_coin_and_derivation_path_by_password(
+                "",
+                &ethereum,
+                "m/44'/60'/0'/0/0",
+                password,
+            )
+            .unwrap();
+        let ethereum2 = stored_key
+            .add_new_account_of_coin_and_derivation_path_by_password(
+                "",
+                &ethereum,
+                "m/44'/60'/0'/0/1",
+                password,
+            )
+            .unwrap();
+        let solana1 = stored_key
+            .add_new_account_of_coin_and_derivation_path_by_password(
+                "",
+                &solana,
+                "m/44'/501'/0'",
+                password,
+            )
+            .unwrap();
+
+        let addresses = |accounts: Vec<&Account>| -> Vec<String> {
+            accounts
+                .iter()
+                .map(|account| account.address.to_owned())
+                .collect()
+        };
+        assert_eq!(
+            addresses(stored_key.get_accounts(&[], "", "").unwrap()),
+            vec![
+                ethereum1.address.clone(),
+                ethereum2.address.clone(),
+                solana1.address.clone()
+            ]
+        );
+        assert_eq!(
+            addresses(stored_key.get_accounts(&[&ethereum], "", "").unwrap()),
+            vec![ethereum1.address.clone(), ethereum2.address.clone()]
+        );
+        assert_eq!(
+            addresses(
+                stored_key
+                    .get_accounts(&[&ethereum, &solana], "m/44'/60'/0'/0/1", "")
+                    .unwrap()
+            ),
+            vec![ethereum2.address.clone()]
+        );
+        assert_eq!(
+            addresses(stored_key.get_accounts(&[], "", &solana1.address).unwrap()),
+            vec![solana1.address.clone()]
+        );
+        assert!(stored_key
+            .get_accounts(&[&solana], "", &ethereum1.address)
+            .unwrap()
+            .is_empty());
+        assert!(stored_key.get_accounts(&[], "m/44'/x", "").is_err());
+
+        let account = stored_key
+            .account_for_address(&ethereum2.address, &ethereum)
+            .unwrap();
+        assert_eq!(account.derivation_path.to_string(), "m/44'/60'/0'/0/1");
+        assert_eq!(
+            stored_key
+                .account_for_address(&ethereum2.address, &solana)
+                .err(),
+            Some(Error::RequstedAccountNotFound)
+        );
+    }
+
+    #[test]
+    fn test_export_key_store_json_of_address() {
+        let mnemonic =
+            "suffer artefact burst review network fantasy easy century mom unique pupil boy";
+        let password = "";
+        let coin = Coin {
+            id: "60".to_owned(),
+            name: "ethereum".to_owned(),
+            coin_id: 60,
+            symbol: "ETH".to_owned(),
+            decimals: 18,
+            blockchain: "Ethereum".to_owned(),
+            derivation_path: "m/44'/60'/0'/0/0".to_owned(),
+            curve: "secp256k1".to_owned(),
+            public_key_type: "secp256k1Extended".to_owned(),
+            all_info: HashMap::new(),
+        };
+
+        let mut stored_key = StoredKey::create_with_mnemonic(password, mnemonic).unwrap();
+        let account = stored_key
+            .add_new_account_of_coin_and_derivation_path_by_password(
+                "",
+                &coin,
+                "m/44'/60'/0'/0/3",
+                password,
+            )
+            .unwrap();
+        let json = stored_key
+            .export_key_store_json_of_address(
+                password,
+                "new",
+                &coin,
+                &account.address,
+                KdfParams::default(),
+            )
+            .unwrap();
+
+        // The exported key is the one of the address, not of the default path
+        let mut imported =
+            StoredKey::create_with_json("new", "new", &json, &coin, KdfPolicy::default()).unwrap();
+        let imported_account = imported
+            .add_new_account_of_coin_and_derivation_path_by_password("", &coin, "", "new")
+            .unwrap();
+        assert_eq!(imported_account.address, account.address);
+
+        let unknown_address = stored_key.export_key_store_json_of_address(
+            password,
+            "new",
+            &coin,
+            "0x0000000000000000000000000000000000000000",
+            KdfParams::default(),
+        );
+        assert_eq!(unknown_address.err(), Some(Error::RequstedAccountNotFound));
+    }
+
+    #[test]
+    fn test_pbkdf2_key_store_json() {
+        // PBKDF2 test vector of the Web3 Secret Storage Definition
+        let json = r#"
+        {
+            "crypto" : {
+                "cipher" : "aes-128-ctr",
+                "cipherparams" : {
+                    "iv" : "6087dab2f9fdbbfaddc31a909735c1e6"
+                },
+                "ciphertext" : "5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46",
+                "kdf" : "pbkdf2",
+                "kdfparams" : {
+                    "c" : 262144,
+                    "dklen" : 32,
+                    "prf" : "hmac-sha256",
+                    "salt" : "ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd"
+                },
+                "mac" : "517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2"
+            },
+            "id" : "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+            "version" : 3
+        }
+        "#;
+        let private_key = "7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d";
+        let coin = Coin {
+            id: "60".to_owned(),
+            name: "ethereum".to_owned(),
+            coin_id: 60,
+            symbol: "ETH".to_owned(),
+            decimals: 18,
+            blockchain: "Ethereum".to_owned(),
+            derivation_path: "m/44'/60'/0'/0/0".to_owned(),
+            curve: "secp256k1".to_owned(),
+            public_key_type: "secp256k1Extended".to_owned(),
+            all_info: HashMap::new(),
+        };
+
+        let stored_key = StoredKey::create_with_json(
+            "testpassword",
+            "password",
+            json,
+            &coin,
+            KdfPolicy::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            stored_key
+                .export_private_key("password", &coin)
+                .unwrap()
+                .as_str(),
+            private_key
+        );
+        assert!(StoredKey::create_with_json(
+            "wrong password",
+            "password",
+            json,
+            &coin,
+            KdfPolicy::default()
+        )
+        .is_err());
+        // The kdf must match the type of the kdfparams
+        let mismatched_kdf = json.replace(r#""kdf" : "pbkdf2""#, r#""kdf" : "scrypt""#);
+        assert!(StoredKey::create_with_json(
+            "testpassword",
+            "password",
+            &mismatched_kdf,
+            &coin,
+            KdfPolicy::default()
+        )
+        .is_err());
+
+        // Export with PBKDF2 and re-import
+        let kdf_params = KdfParams::Pbkdf2Param(Pbkdf2Params {
+            c: 1024,
+            ..Pbkdf2Params::default()
+        });
+        let exported = stored_key
+            .export_key_store_json("password", "new", kdf_params)
+            .unwrap();
+        let exported_json: KeyStoreJson = serde_json::from_str(&exported).unwrap();
+        assert_eq!(exported_json.crypto.kdf, "pbkdf2");
+        assert_eq!(exported_json.crypto.kdfparams.name(), "pbkdf2");
+        let imported =
+            StoredKey::create_with_json("new", "password", &exported, &coin, KdfPolicy::default())
+                .unwrap();
+        assert_eq!(
+            imported
+                .export_private_key("password", &coin)
+                .unwrap()
+                .as_str(),
+            private_key
+        );
+    }
+
+    #[test]
+    fn test_hash() {
+        let mnemonic1 =
+            "suffer artefact burst review network fantasy easy century mom unique pupil boy";
+        let password = "";
+        let stored_key1 = StoredKey::create_with_mnemonic(password, mnemonic1).unwrap();
+        let stored_key2 = StoredKey::create_with_mnemonic(password, mnemonic1).unwrap();
+        assert_eq!(stored_key1.hash, stored_key2.hash);
+
+        let (stored_key_random, _) = StoredKey::create_with_mnemonic_random(
+            password,
+            "",
+            12,
+            Language::English,
+            KdfPolicy::default(),
+        )
+        .unwrap();
+        assert_ne!(stored_key1.hash, stored_key_random.hash);
+        assert_ne!(stored_key2.hash, stored_key_random.hash);
+    }
+
+    #[test]
+    fn test_passphrase() {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let password = "password";
+        let derivation_path = "m/84'/0'/0'/0/0";
+        let coin = Coin {
+            id: "bitcoin".to_owned(),
+            name: "Bitcoin".to_owned(),
+            coin_id: 0,
+            symbol: "BTC".to_owned(),
+            decimals: 8,
+            blockchain: "Bitcoin".to_owned(),
+            derivation_path: derivation_path.to_owned(),
+            curve: "secp256k1".to_owned(),
+            public_key_type: "secp256k1".to_owned(),
+            all_info: HashMap::new(),
+        };
+
+        // Hash of the seed of the BIP39 test vector with the passphrase "TREZOR"
+        let stored_key = StoredKey::create_with_mnemonic_and_passphrase(
+            password,
+            mnemonic,
+            "TREZOR",
+            None,
+            KdfPolicy::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            stored_key.hash,
+            "358f4f4a6d2e6d82920aa11d908ee6497dd65b17009339127b0e24777b806c40"
+        );
+        assert_eq!(
+            stored_key
+                .export_private_key_of_path(password, &coin, derivation_path)
+                .unwrap()
+                .as_str(),
+            "697e2dcb4c29ef4af9c937cd915c3872afc35217ad240794df09dd6fccdfb059"
+        );
+        assert_eq!(
+            stored_key.export_mnemonic(password).unwrap().as_str(),
+            mnemonic
+        );
+
+        // The passphrase is kept encrypted through serialization and password updates
+        let json = serde_json::to_string(&stored_key).unwrap();
+        assert!(!json.contains("TREZOR"));
+        let mut stored_key: StoredKey = serde_json::from_str(&json).unwrap();
+        stored_key
+            .update_password(password, "new password", KdfPolicy::default())
+            .unwrap();
+        assert_eq!(
+            stored_key
+                .export_private_key_of_path("new password", &coin, derivation_path)
+                .unwrap()
+                .as_str(),
+            "697e2dcb4c29ef4af9c937cd915c3872afc35217ad240794df09dd6fccdfb059"
+        );
+
+        // The hash doesn't depend on the encryption password
+        let without_passphrase = StoredKey::create_with_mnemonic(password, mnemonic).unwrap();
+        let other_password = StoredKey::create_with_mnemonic("other", mnemonic).unwrap();
+        assert_eq!(without_passphrase.hash, other_password.hash);
+        assert_ne!(without_passphrase.hash, stored_key.hash);
+        assert_eq!(
+            without_passphrase
+                .export_private_key_of_path(password, &coin, derivation_path)
+                .unwrap()
+                .as_str(),
+            "4604b4b710fe91f584fff084e1a9159fe4f8408fff380596a604948474ce4fa3"
+        );
+        assert!(!serde_json::to_string(&without_passphrase)
+            .unwrap()
+            .contains("passphrase"));
+    }
+
+    #[test]
+    fn test_mnemonic_language() {
+        let password = "password";
+        let (stored_key, mnemonic) = StoredKey::create_with_mnemonic_random(
+            password,
+            "",
+            24,
+            Language::Japanese,
+            KdfPolicy::default(),
+        )
+        .unwrap();
+        assert_eq!(mnemonic.split('\u{3000}').count(), 24);
+        assert_eq!(
+            stored_key.export_mnemonic(password).unwrap().as_str(),
+            mnemonic
+        );
+
+        let imported = StoredKey::create_with_mnemonic_and_passphrase(
+            password,
+            &mnemonic,
+            "",
+            Some(Language::Japanese),
+            KdfPolicy::default(),
+        )
+        .unwrap();
+        assert_eq!(imported.hash, stored_key.hash);
+        let detected = StoredKey::create_with_mnemonic(password, &mnemonic).unwrap();
+        assert_eq!(detected.hash, stored_key.hash);
+
+        let result = StoredKey::create_with_mnemonic_and_passphrase(
+            password,
+            &mnemonic,
+            "",
+            Some(Language::English),
+            KdfPolicy::default(),
+        );
+        assert_eq!(
+            result.err(),
+            Some(Error::CryptoError(CryptoError::InvalidMnemonic))
+        );
+        assert!(StoredKey::create_with_mnemonic_random(
+            password,
+            "",
+            13,
+            Language::English,
+            KdfPolicy::default()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_update_password() {
+        let mnemonic1 =
+            "suffer artefact burst review network fantasy easy century mom unique pupil boy";
+        let password1 = "password 1";
+        let password2 = "password 2";
+        let mut stored_key1 = StoredKey::create_with_mnemonic(password1, mnemonic1).unwrap();
+        stored_key1
+            .update_password(password1, password2, KdfPolicy::default())
+            .unwrap();
+        let mnemonic2 = stored_key1.export_mnemonic(password2).unwrap();
+        assert_eq!(mnemonic1, mnemonic2.as_str());
+
+        let failed = stored_key1.export_mnemonic(password1);
+        assert!(failed.is_err());
+    }
+
+    #[test]
+    fn test_rekey() {
+        let mnemonic =
+            "suffer artefact burst review network fantasy easy century mom unique pupil boy";
+        let password = "password";
+        let scrypt = KdfPolicy::Scrypt {
+            n: 32768,
+            r: 8,
+            p: 1,
+        };
+        let mut stored_key = StoredKey::create_with_mnemonic_and_passphrase(
+            password, mnemonic, "TREZOR", None, scrypt,
+        )
+        .unwrap();
+        let id = stored_key.id.clone();
+        let hash = stored_key.hash.clone();
+        assert_eq!(
+            stored_key
+                .rekey("wrong password", KdfPolicy::default())
+                .err(),
+            Some(Error::CryptoError(CryptoError::PasswordIncorrect))
+        );
+
+        let argon2id = KdfPolicy::Argon2id {
+            m_cost: 19456,
+            t_cost: 2,
+            p_cost: 1,
+        };
+        // A wallet can't be rekeyed below the minimum cost
+        let weak = KdfPolicy::Argon2id {
+            m_cost: 1024,
+            t_cost: 2,
+            p_cost: 1,
+        };
+        assert_eq!(
+            stored_key.rekey(password, weak).err(),
+            Some(Error::CryptoError(CryptoError::KdfParamsTooWeak))
+        );
+        let weak = KdfPolicy::Scrypt {
+            n: 4096,
+            r: 8,
+            p: 1,
+        };
+        assert_eq!(
+            stored_key.rekey(password, weak).err(),
+            Some(Error::CryptoError(CryptoError::KdfParamsTooWeak))
+        );
+        assert_eq!(stored_key.hash, hash);
+        assert!(serde_json::to_string(&stored_key)
+            .unwrap()
+            .contains("\"n\":32768"));
+
+        stored_key.rekey(password, argon2id).unwrap();
+        let json = serde_json::to_string(&stored_key).unwrap();
+        assert!(json.contains("\"m_cost\":19456"));
+        assert!(!json.contains("\"n\":32768"));
+        assert_eq!(stored_key.id, id);
+        // The hash is computed from the decrypted passphrase, so it's kept as well
+        assert_eq!(stored_key.hash, hash);
+        assert_eq!(
+            stored_key.export_mnemonic(password).unwrap().as_str(),
+            mnemonic
+        );
+    }
+
+    // A mnemonic StoredKey of version 0.1.0, encrypted with AES-128-CTR and the password "password"
+    const LEGACY_STORED_KEY: &str = r#"{"type":"Mnemonic","id":"62f97792-df75-4883-a5f5-6cd5501a3aa1","hash":"29e30089e1042f6f90eacb883b054b7bc857ac5ae70cc322b4e6d19dc90c52c5","version":"0.1.0","payload":{"encrypted":[188,220,54,145,146,139,82,117,96,230,20,159,70,25,57,44,226,58,49,235,44,24,34,79,206,22,102,149,128,157,230,246,105,167,151,54,154,183,1,242,38,114,156,249,78,217,29,124,177,255,254,39,161,47,161,227,61,166,152,142,97,215,17,222,42,124,65,130,228,21,124,53,13,237,225,151,74,213],"cipher":{"Ctr":128},"cipher_params":{"iv":"2766ba0bbf36b1eb03bc6005eb42dbf1"},"mac":"d4dec2fc2d01473d3ceb01ef58be28c85384aa61c1f1e0512e64e6b9354a5797","kdf_params":{"n":4096,"p":1,"r":8,"dklen":32,"salt":"88cbe7ee38a7128cda2fba13121e26309c85aadcba02c48f770aee249e1e8ba8"}}}"#;
+
+    #[test]
+    fn test_aead_payload() {
+        let mnemonic =
+            "suffer artefact burst review network fantasy easy century mom unique pupil boy";
+        let password = "password";
+        let stored_key = StoredKey::create_with_mnemonic_and_passphrase(
+            password,
+            mnemonic,
+            "TREZOR",
+            None,
+            KdfPolicy::default(),
+        )
+        .unwrap();
+        assert_eq!(stored_key.version, VERSION);
+        assert!(stored_key.payload.as_ref().unwrap().is_aead());
+        assert_eq!(
+            stored_key.export_mnemonic(password).unwrap().as_str(),
+            mnemonic
+        );
+        assert_eq!(
+            stored_key.export_mnemonic("wrong password").err(),
+            Some(Error::CryptoError(CryptoError::PasswordIncorrect))
+        );
+
+        // Tampering with the metadata fails the decryption
+        let json = serde_json::to_string(&stored_key).unwrap();
+        let tampered_id = json.replace(&stored_key.id, &Uuid::new_v4().to_string());
+        let tampered: StoredKey = serde_json::from_str(&tampered_id).unwrap();
+        assert_eq!(
+            tampered.export_mnemonic(password).err(),
+            Some(Error::CryptoError(CryptoError::PasswordIncorrect))
+        );
+        let tampered_type = json.replace(r#""type":"Mnemonic""#, r#""type":"PrivateKey""#);
+        let tampered: StoredKey = serde_json::from_str(&tampered_type).unwrap();
+        assert!(!tampered.validate_password(password));
+        let tampered_version = json.replace(VERSION, LEGACY_VERSION);
+        let tampered: StoredKey = serde_json::from_str(&tampered_version).unwrap();
+        assert!(!tampered.validate_password(password));
+        // The passphrase is sealed with the key of the payload, and can't be swapped with it
+        let payload = stored_key.payload.as_ref().unwrap();
+        let key = payload.aead_key(password.as_bytes()).unwrap();
+        let associated_data =
+            |field: &str| associated_data(&stored_key.id, &stored_key.r#type, VERSION, field);
+        let passphrase = stored_key.passphrase.as_ref().unwrap();
+        assert_eq!(
+            key.open(passphrase, &associated_data(PASSPHRASE_FIELD))
+                .unwrap()
+                .as_slice(),
+            b"TREZOR"
+        );
+        assert!(key
+            .open(passphrase, &associated_data(PAYLOAD_FIELD))
+            .is_err());
+        assert!(payload
+            .decrypt_with_key(&key, &associated_data(PASSPHRASE_FIELD))
+            .is_err());
+
+        // Legacy StoredKeys still decrypt
+        let mut legacy: StoredKey = serde_json::from_str(LEGACY_STORED_KEY).unwrap();
+        assert_eq!(legacy.version, LEGACY_VERSION);
+        assert!(!legacy.payload.as_ref().unwrap().is_aead());
+        assert_eq!(legacy.export_mnemonic(password).unwrap().as_str(), mnemonic);
+        legacy
+            .update_password(password, "new password", KdfPolicy::default())
+            .unwrap();
+        assert_eq!(
+            legacy.export_mnemonic("new password").unwrap().as_str(),
+            mnemonic
+        );
+        // The client adds the accounts of a migrated StoredKey again
+        assert!(!legacy.needs_migration());
+        assert!(legacy.get_all_accounts().is_empty());
+    }
+
+    #[test]
+    fn test_migration() {
+        let mnemonic =
+            "suffer artefact burst review network fantasy easy century mom unique pupil boy";
+        let password = "password";
+        let coin = Coin {
+            id: "60".to_owned(),
+            name: "ethereum".to_owned(),
+            coin_id: 60,
+            symbol: "ETH".to_owned(),
+            decimals: 18,
+            blockchain: "Ethereum".to_owned(),
+            derivation_path: "m/44'/60'/0'/0/0".to_owned(),
+            curve: "secp256k1".to_owned(),
+            public_key_type: "secp256k1Extended".to_owned(),
+            all_info: HashMap::new(),
+        };
+        let mut legacy = StoredKey::from_json(LEGACY_STORED_KEY.as_bytes()).unwrap();
+        assert!(legacy.needs_migration());
+        assert!(
+            StoredKeyInfo::from(StoredKey::from_json(LEGACY_STORED_KEY.as_bytes()).unwrap())
+                .needs_migration
+        );
+
+        // Exporting reads the legacy StoredKey without upgrading it
+        let private_key = legacy.export_private_key(password, &coin).unwrap();
+        assert_eq!(legacy.version, LEGACY_VERSION);
+
+        // A wrong password leaves the StoredKey untouched
+        assert_eq!(
+            legacy
+                .add_new_account_of_coin_and_derivation_path_by_password(
+                    "mask",
+                    &coin,
+                    "m/44'/60'/0'/0/0",
+                    "wrong password",
+                )
+                .err(),
+            Some(Error::CryptoError(CryptoError::PasswordIncorrect))
+        );
+        assert_eq!(legacy.version, LEGACY_VERSION);
+
+        let account = legacy
+            .add_new_account_of_coin_and_derivation_path_by_password(
+                "mask",
+                &coin,
+                "m/44'/60'/0'/0/0",
+                password,
+            )
+            .unwrap();
+        assert!(!legacy.needs_migration());
+        assert_eq!(legacy.version, VERSION);
+        assert!(legacy.payload.as_ref().unwrap().is_aead());
+        assert!(serde_json::to_string(&legacy)
+            .unwrap()
+            .contains("\"m_cost\":65536"));
+        // The hash no longer depends on the password
+        let created = StoredKey::create_with_mnemonic("another password", mnemonic).unwrap();
+        assert_ne!(
+            legacy.hash,
+            "29e30089e1042f6f90eacb883b054b7bc857ac5ae70cc322b4e6d19dc90c52c5"
+        );
+        assert_eq!(legacy.hash, created.hash);
+        assert_eq!(legacy.get_all_accounts().len(), 1);
+        assert_eq!(legacy.get_all_accounts()[0].address, account.address);
+        assert_eq!(legacy.export_mnemonic(password).unwrap().as_str(), mnemonic);
+        assert_eq!(
+            legacy.export_private_key(password, &coin).unwrap(),
+            private_key
+        );
+
+        let json = serde_json::to_vec(&legacy).unwrap();
+        let migrated = StoredKey::from_json(&json).unwrap();
+        assert!(!StoredKeyInfo::from(migrated).needs_migration);
+
+        // StoredKeys of newer versions are refused
+        let newer = LEGACY_STORED_KEY.replace("\"0.1.0\"", "\"0.3.0\"");
+        assert_eq!(
+            StoredKey::from_json(newer.as_bytes()).err(),
+            Some(Error::NotSupportedStoredKeyVersion)
+        );
+        assert_eq!(
+            StoredKey::from_json(b"{}").err(),
+            Some(Error::JsonSerializationError)
+        );
+    }
+
+    #[test]
+    fn test_kdf_policy() {
+        let mnemonic =
+            "suffer artefact burst review network fantasy easy century mom unique pupil boy";
+        let password = "password";
+        let stored_key = StoredKey::create_with_mnemonic(password, mnemonic).unwrap();
+        assert!(serde_json::to_string(&stored_key)
+            .unwrap()
+            .contains("\"m_cost\":65536"));
+
+        // StoredKeys encrypted with scrypt are still readable, and upgraded by updating the password
+        let scrypt = KdfPolicy::Scrypt {
+            n: 32768,
+            r: 8,
+            p: 1,
+        };
+        let stored_key = StoredKey::create_with_mnemonic_and_passphrase(
+            password, mnemonic, "TREZOR", None, scrypt,
+        )
+        .unwrap();
+        let json = serde_json::to_string(&stored_key).unwrap();
+        assert!(json.contains("\"n\":32768"));
+        assert!(!json.contains("m_cost"));
+        let mut stored_key: StoredKey = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            stored_key.export_mnemonic(password).unwrap().as_str(),
+            mnemonic
+        );
+        stored_key
+            .update_password(password, password, KdfPolicy::default())
+            .unwrap();
+        let json = serde_json::to_string(&stored_key).unwrap();
+        assert!(!json.contains("\"n\":32768"));
+        // The passphrase is sealed with the key of the payload, which is derived once
+        assert_eq!(json.matches("\"m_cost\":65536").count(), 1);
+        assert_eq!(
+            stored_key.export_mnemonic(password).unwrap().as_str(),
+            mnemonic
+        );
+
+        // The cost of Argon2id is configurable
+        let argon2id = KdfPolicy::Argon2id {
+            m_cost: 19456,
+            t_cost: 2,
+            p_cost: 1,
+        };
+        let stored_key =
+            StoredKey::create_with_mnemonic_and_passphrase(password, mnemonic, "", None, argon2id)
+                .unwrap();
+        assert!(serde_json::to_string(&stored_key)
+            .unwrap()
+            .contains("\"m_cost\":19456"));
+        assert_eq!(
+            stored_key.export_mnemonic(password).unwrap().as_str(),
+            mnemonic
+        );
+        let weak = KdfPolicy::Argon2id {
+            m_cost: 1,
+            t_cost: 1,
+            p_cost: 1,
+        };
+        assert_eq!(
+            StoredKey::create_with_mnemonic_and_passphrase(password, mnemonic, "", None, weak)
+                .err(),
+            Some(Error::CryptoError(CryptoError::KdfParamsTooWeak))
+        );
+        let invalid = KdfPolicy::Argon2id {
+            m_cost: 19456,
+            t_cost: 2,
+            p_cost: 0,
+        };
+        assert_eq!(
+            StoredKey::create_with_mnemonic_and_passphrase(password, mnemonic, "", None, invalid)
+                .err(),
+            Some(Error::CryptoError(CryptoError::KdfParamsInvalid))
+        );
+    }
+
+    #[test]
+    fn test_create_solana_account_at_path() {
+        let mnemonic =
+            "shoot island position soft burden budget tooth cruel issue economy destroy above";
+        let password = "";
+        let derivation_path = "m/44'/501'/0'";
+        let coin = Coin {
+            id: "501".to_owned(),
+            name: "solana".to_owned(),
+            coin_id: 501,
+            symbol: "SOL".to_owned(),
+            decimals: 9,
+            blockchain: "Solana".to_owned(),
+            derivation_path: derivation_path.to_owned(),
+            curve: "ed25519".to_owned(),
+            public_key_type: "ed25519".to_owned(),
+            all_info: HashMap::new(),
+        };
+
+        let mut stored_key = StoredKey::create_with_mnemonic(password, mnemonic).unwrap();
+        let test_derivation_path1 = "m/44'/501'/0'";
+        let account1 = stored_key
+            .add_new_account_of_coin_and_derivation_path_by_password(
+                "mask",
+                &coin,
+                test_derivation_path1,
+                password,
+            )
+            .unwrap();
+        assert_eq!(account1.derivation_path.to_string(), "m/44'/501'/0'");
+        assert_eq!(
+            account1.address,
+            "2bUBiBNZyD29gP1oV6de7nxowMLoDBtopMMTGgMvjG5m"
+        );
+    }
+
+    #[test]
+    fn test_create_solana_account2_at_path() {
         let mnemonic =
             "hurt behind mosquito scare enable gym learn unlock render grab reflect world";
         let password = "";
@@ -627,23 +2001,23 @@ mod tests {
             all_info: HashMap::new(),
         };
 
-        let stored_key = StoredKey::create_with_mnemonic(&password, &mnemonic).unwrap();
+        let mut stored_key = StoredKey::create_with_mnemonic(password, mnemonic).unwrap();
         let test_derivation_path1 = "m/44'/501'/0'/0'";
         let test_derivation_path2 = "m/44'/501'/1'/0'";
         let account1 = stored_key
             .add_new_account_of_coin_and_derivation_path_by_password(
                 "mask",
                 &coin,
-                &test_derivation_path1,
-                &password,
+                test_derivation_path1,
+                password,
             )
             .unwrap();
         let account2 = stored_key
             .add_new_account_of_coin_and_derivation_path_by_password(
                 "mask",
                 &coin,
-                &test_derivation_path2,
-                &password,
+                test_derivation_path2,
+                password,
             )
             .unwrap();
         assert_eq!(
@@ -655,4 +2029,537 @@ mod tests {
             "3TMqEHi5cVT86NeSuXmSfGf4PJZH1NQeh6rzc6bneKZ7"
         );
     }
+
+    #[test]
+    fn test_sign_bitcoin_psbt() {
+        use chain_common::bitcoin::{SignInput, SignOutput};
+        use prost::Message;
+
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let password = "";
+        let coin = Coin {
+            id: "bitcoin".to_owned(),
+            name: "Bitcoin".to_owned(),
+            coin_id: 0,
+            symbol: "BTC".to_owned(),
+            decimals: 8,
+            blockchain: "Bitcoin".to_owned(),
+            derivation_path: "m/84'/0'/0'/0/0".to_owned(),
+            curve: "secp256k1".to_owned(),
+            public_key_type: "secp256k1".to_owned(),
+            all_info: HashMap::new(),
+        };
+        let stored_key = StoredKey::create_with_mnemonic(password, mnemonic).unwrap();
+
+        // Inputs of the legacy, nested segwit, native segwit and taproot accounts
+        let input = SignInput {
+            psbt: hex::decode("70736274ff0100f8020000000496c8b9266068d68e9721f95eacff4aa4cbf904c03aa692aa0573fd9ef2675e170000000000fdffffff11111111111111111111111111111111111111111111111111111111111111110100000000fdffffff22222222222222222222222222222222222222222222222222222222222222220000000000fdffffff33333333333333333333333333333333333333333333333333333333333333330200000000fdffffff02a0bb0d00000000002200201863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262905f010000000000160014c0cebcd6c3d3ca8c75dc5ec62ebe55330ef910e2000000000001005501000000010000000000000000000000000000000000000000000000000000000000000000ffffffff00ffffffff01a0860100000000001976a914d986ed01b7a22225a70edbf2ba7cfb63a15cb3aa88ac0000000000010120400d03000000000017a9143fb6e95812e57bb4691f9a4a628862a61a4f769b870001011fe093040000000000160014c0cebcd6c3d3ca8c75dc5ec62ebe55330ef910e20001012b801a060000000000225120a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c000000").unwrap(),
+            derivation_paths: vec![
+                "m/44'/0'/0'/0/0".to_owned(),
+                "m/49'/0'/0'/0/0".to_owned(),
+                "m/84'/0'/0'/0/0".to_owned(),
+                "m/86'/0'/0'/0/0".to_owned(),
+            ],
+        };
+        let mut payload = vec![];
+        input.encode(&mut payload).unwrap();
+        let encoded_output = stored_key
+            .sign(&coin, password, &coin.derivation_path, &payload)
+            .unwrap();
+        let output = SignOutput::decode(&encoded_output[..]).unwrap();
+        assert_eq!(hex::encode(output.transaction), "0200000000010496c8b9266068d68e9721f95eacff4aa4cbf904c03aa692aa0573fd9ef2675e17000000006b483045022100f4de1b30cb9c8c64dc9491f12f00728b8f9a1c972c66926ca57591281d878433022021a32602aa7b2e0455273f23cabf30e9f936533c51fc277362cf8d2d849805e9012103aaeb52dd7494c361049de67cc680e83ebcbbbdbeb13637d92cd845f70308af5efdffffff11111111111111111111111111111111111111111111111111111111111111110100000017160014f990679acafe25c27615373b40bf22446d24ff44fdffffff22222222222222222222222222222222222222222222222222222222222222220000000000fdffffff33333333333333333333333333333333333333333333333333333333333333330200000000fdffffff02a0bb0d00000000002200201863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262905f010000000000160014c0cebcd6c3d3ca8c75dc5ec62ebe55330ef910e20002473044022034afb3e08b5a935ca130897b21e03d16db17167c081933ed00866cd824ec379302205143040ce6239815d2ef00a44454ea045a39eedef1287d7c0e00532273576bcb0121039b3b694b8fc5b5e07fb069c783cac754f5d38c3e08bed1960e31fdb1dda35c240247304402204705e5116a3c6dfecb250570aa26727dbd4b40f8b13ffe9b1a08fd631a8caaa2022015bc9f60732ca0f20f46ebe6011f5989b0c43fbf5a2d9c7a8236980a6b21a22f01210330d54fd0dd420a6e5f8d3624f5f3482cae350f79d5f0753bf5beef9c2d91af3c01401a73005fc0b288229b8d542346c9d0f8a19a50294c67060c6006c8d7d3f192f4b7d86080b94e2f089d55eef333725976399aa395f3dd25f1aa9db86e85c4fca900000000");
+    }
+
+    #[test]
+    fn test_create_with_jwk() {
+        let jwk = include_str!("../../crypto/resource/test_rsa_key.json");
+        let password = "mask";
+        let coin = Coin {
+            id: "arweave".to_owned(),
+            name: "Arweave".to_owned(),
+            coin_id: 999,
+            symbol: "AR".to_owned(),
+            decimals: 9,
+            blockchain: "Arweave".to_owned(),
+            derivation_path: "".to_owned(),
+            curve: "".to_owned(),
+            public_key_type: "rsa".to_owned(),
+            all_info: HashMap::new(),
+        };
+
+        let mut stored_key =
+            StoredKey::create_with_jwk(password, jwk, KdfPolicy::default()).unwrap();
+        assert!(stored_key.r#type == StoredKeyType::PrivateKey);
+        let account = stored_key
+            .add_new_account_of_coin_and_derivation_path_by_password("mask", &coin, "", password)
+            .unwrap();
+        assert_eq!(
+            account.address,
+            "qpTbAIgu-kfWmBueOI9u1gze_YD3we2Nwkq4kJb_zNI"
+        );
+        assert_eq!(
+            StoredKey::create_with_jwk(password, "{}", KdfPolicy::default()).err(),
+            Some(Error::CryptoError(CryptoError::InvalidJwk))
+        );
+    }
+
+    #[test]
+    fn test_create_accounts_without_extended_public_key() {
+        let mnemonic = "bottom drive obey lake curtain smoke basket hold race lonely fit walk";
+        let password = "password";
+        let polkadot = Coin {
+            id: "polkadot".to_owned(),
+            name: "Polkadot".to_owned(),
+            coin_id: 354,
+            symbol: "DOT".to_owned(),
+            decimals: 10,
+            blockchain: "Polkadot".to_owned(),
+            derivation_path: "".to_owned(),
+            curve: "sr25519".to_owned(),
+            public_key_type: "sr25519".to_owned(),
+            all_info: HashMap::new(),
+        };
+        let arweave = Coin {
+            id: "arweave".to_owned(),
+            name: "Arweave".to_owned(),
+            coin_id: 999,
+            symbol: "AR".to_owned(),
+            decimals: 9,
+            blockchain: "Arweave".to_owned(),
+            derivation_path: "".to_owned(),
+            curve: "".to_owned(),
+            public_key_type: "rsa".to_owned(),
+            all_info: HashMap::new(),
+        };
+
+        // Junction paths of sr25519 have no account public key
+        let mut stored_key = StoredKey::create_with_mnemonic(password, mnemonic).unwrap();
+        let account = stored_key
+            .add_new_account_of_coin_and_derivation_path_by_password("", &polkadot, "", password)
+            .unwrap();
+        assert_eq!(
+            account.address,
+            "12bzRJfh7arnnfPPUZHeJUaE62QLEwhK48QnH9LXeK2m1iZU"
+        );
+        assert_eq!(account.extended_public_key, "");
+        let account = stored_key
+            .add_new_account_of_coin_and_derivation_path_by_password(
+                "", &polkadot, "//Alice", password,
+            )
+            .unwrap();
+        assert_eq!(
+            account.address,
+            "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5"
+        );
+        assert_eq!(account.extended_public_key, "");
+
+        let jwk = include_str!("../../crypto/resource/test_rsa_key.json");
+        let mut stored_key =
+            StoredKey::create_with_jwk(password, jwk, KdfPolicy::default()).unwrap();
+        let account = stored_key
+            .add_new_account_of_coin_and_derivation_path_by_password("", &arweave, "", password)
+            .unwrap();
+        assert_eq!(
+            account.address,
+            "qpTbAIgu-kfWmBueOI9u1gze_YD3we2Nwkq4kJb_zNI"
+        );
+        assert_eq!(account.extended_public_key, "");
+        assert_eq!(stored_key.get_all_accounts().len(), 1);
+    }
+
+    #[test]
+    fn test_watch_only() {
+        // Account keys of the BIP39 test vector "abandon abandon ... about"
+        let zpub = "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs";
+        let xpub = "xpub6DCoCpSuQZB2jawqnGMEPS63ePKWkwWPH4TU45Q7LPXWuNd8TMtVxRrgjtEshuqpK3mdhaWHPFsBngh5GFZaM6si3yZdUsT8ddYM3PwnATt";
+        let bitcoin = Coin {
+            id: "bitcoin".to_owned(),
+            name: "Bitcoin".to_owned(),
+            coin_id: 0,
+            symbol: "BTC".to_owned(),
+            decimals: 8,
+            blockchain: "Bitcoin".to_owned(),
+            derivation_path: "m/84'/0'/0'/0/0".to_owned(),
+            curve: "secp256k1".to_owned(),
+            public_key_type: "secp256k1".to_owned(),
+            all_info: HashMap::new(),
+        };
+        let ethereum = Coin {
+            id: "60".to_owned(),
+            name: "ethereum".to_owned(),
+            coin_id: 60,
+            symbol: "ETH".to_owned(),
+            decimals: 18,
+            blockchain: "Ethereum".to_owned(),
+            derivation_path: "m/44'/60'/0'/0/0".to_owned(),
+            curve: "secp256k1".to_owned(),
+            public_key_type: "secp256k1Extended".to_owned(),
+            all_info: HashMap::new(),
+        };
+
+        let mut stored_key = StoredKey::create_watch_only_with_extended_public_key(zpub).unwrap();
+        assert!(stored_key.r#type == StoredKeyType::WatchOnly);
+        let account = stored_key
+            .add_new_account_of_coin_and_derivation_path_by_password("", &bitcoin, "", "")
+            .unwrap();
+        assert_eq!(
+            account.address,
+            "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu"
+        );
+        assert_eq!(account.derivation_path.to_string(), "m/84'/0'/0'/0/0");
+        let change = stored_key
+            .add_new_account_of_coin_and_derivation_path_by_password(
+                "",
+                &bitcoin,
+                "m/84'/0'/0'/1/0",
+                "",
+            )
+            .unwrap();
+        assert_ne!(change.address, account.address);
+        assert_eq!(
+            stored_key
+                .add_new_account_of_coin_and_derivation_path_by_password(
+                    "",
+                    &bitcoin,
+                    "m/84'/0'/1'/0/0",
+                    ""
+                )
+                .err(),
+            Some(Error::CryptoError(CryptoError::InvalidDerivationpath))
+        );
+        assert_eq!(
+            stored_key
+                .add_new_account_of_coin_and_derivation_path_by_password(
+                    "",
+                    &bitcoin,
+                    "m/84'/0'/0'/0'/0",
+                    ""
+                )
+                .err(),
+            Some(Error::CryptoError(CryptoError::InvalidDerivationpath))
+        );
+
+        // Nothing is encrypted, and the key can't be used for anything needing a private key
+        assert!(!stored_key.validate_password(""));
+        assert_eq!(
+            stored_key.export_private_key("", &bitcoin).err(),
+            Some(Error::RequestNotSupportedOnWatchOnlyStoredKey)
+        );
+        assert_eq!(
+            stored_key.export_mnemonic("").err(),
+            Some(Error::RequestNotSupportedOnWatchOnlyStoredKey)
+        );
+        assert_eq!(
+            stored_key
+                .update_password("", "password", KdfPolicy::default())
+                .err(),
+            Some(Error::RequestNotSupportedOnWatchOnlyStoredKey)
+        );
+        assert_eq!(
+            stored_key.decrypt_private_key("", &bitcoin).err(),
+            Some(Error::RequestNotSupportedOnWatchOnlyStoredKey)
+        );
+
+        let json = serde_json::to_vec(&stored_key).unwrap();
+        let loaded = StoredKey::from_json(&json).unwrap();
+        assert_eq!(loaded.hash, stored_key.hash);
+        assert_eq!(loaded.get_all_accounts().len(), 2);
+
+        let mut stored_key = StoredKey::create_watch_only_with_extended_public_key(xpub).unwrap();
+        let account = stored_key
+            .add_new_account_of_coin_and_derivation_path_by_password("", &ethereum, "", "")
+            .unwrap();
+        assert_eq!(
+            account.address,
+            "0x9858EfFD232B4033E47d90003D41EC34EcaEda94"
+        );
+
+        let mut stored_key = StoredKey::create_watch_only_with_public_key(
+            "0330d54fd0dd420a6e5f8d3624f5f3482cae350f79d5f0753bf5beef9c2d91af3c",
+            &bitcoin,
+        )
+        .unwrap();
+        let account = stored_key
+            .add_new_account_of_coin_and_derivation_path_by_password("", &bitcoin, "", "")
+            .unwrap();
+        assert_eq!(
+            account.address,
+            "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu"
+        );
+
+        assert_eq!(
+            StoredKey::create_watch_only_with_public_key("0330d54f", &bitcoin).err(),
+            Some(Error::CryptoError(CryptoError::InvalidPublicKey))
+        );
+        assert_eq!(
+            StoredKey::create_watch_only_with_extended_public_key(&zpub[1..]).err(),
+            Some(Error::CryptoError(CryptoError::InvalidPublicKey))
+        );
+    }
+
+    #[test]
+    fn test_watch_only_network() {
+        // Account keys of the BIP39 test vector "abandon abandon ... about", the vpub of BIP84
+        // and the xpub of BIP86
+        let vpub = "vpub5Y6cjg78GGuNLsaPhmYsiw4gYX3HoQiRBiSwDaBXKUafCt9bNwWQiitDk5VZ5BVxYnQdwoTyXSs2JHRPAgjAvtbBrf8ZhDYe2jWAqvZVnsc";
+        let tpub = "tpubDC5FSnBiZDMmhiuCmWAYsLwgLYrrT9rAqvTySfuCCrgsWz8wxMXUS9Tb9iVMvcRbvFcAHGkMD5Kx8koh4GquNGNTfohfk7pgjhaPCdXpoba";
+        let zpub = "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs";
+        let taproot_xpub = "xpub6BgBgsespWvERF3LHQu6CnqdvfEvtMcQjYrcRzx53QJjSxarj2afYWcLteoGVky7D3UKDP9QyrLprQ3VCECoY49yfdDEHGCtMMj92pReUsQ";
+        let bitcoin = Coin {
+            id: "bitcoin".to_owned(),
+            name: "Bitcoin".to_owned(),
+            coin_id: 0,
+            symbol: "BTC".to_owned(),
+            decimals: 8,
+            blockchain: "Bitcoin".to_owned(),
+            derivation_path: "m/84'/0'/0'/0/0".to_owned(),
+            curve: "secp256k1".to_owned(),
+            public_key_type: "secp256k1".to_owned(),
+            all_info: HashMap::new(),
+        };
+        let testnet = Coin {
+            coin_id: 1,
+            derivation_path: "m/84'/1'/0'/0/0".to_owned(),
+            all_info: HashMap::from([
+                ("p2pkh".to_owned(), serde_json::json!(111)),
+                ("p2sh".to_owned(), serde_json::json!(196)),
+                ("hrp".to_owned(), serde_json::json!("tb")),
+            ]),
+            ..bitcoin.clone()
+        };
+        let ethereum = Coin {
+            id: "60".to_owned(),
+            name: "ethereum".to_owned(),
+            coin_id: 60,
+            symbol: "ETH".to_owned(),
+            decimals: 18,
+            blockchain: "Ethereum".to_owned(),
+            derivation_path: "m/44'/60'/0'/0/0".to_owned(),
+            curve: "secp256k1".to_owned(),
+            public_key_type: "secp256k1Extended".to_owned(),
+            all_info: HashMap::new(),
+        };
+
+        let mut stored_key = StoredKey::create_watch_only_with_extended_public_key(vpub).unwrap();
+        let account = stored_key
+            .add_new_account_of_coin_and_derivation_path_by_password("", &testnet, "", "")
+            .unwrap();
+        assert_eq!(
+            account.address,
+            "tb1q6rz28mcfaxtmd6v789l9rrlrusdprr9pqcpvkl"
+        );
+        assert_eq!(account.derivation_path.to_string(), "m/84'/1'/0'/0/0");
+        assert_eq!(
+            stored_key
+                .add_new_account_of_coin_and_derivation_path_by_password("", &bitcoin, "", "")
+                .err(),
+            Some(Error::CryptoError(CryptoError::InvalidPublicKey))
+        );
+
+        // Mainnet keys aren't keys of the testnet, and segwit keys aren't keys of ethereum
+        let mut stored_key = StoredKey::create_watch_only_with_extended_public_key(zpub).unwrap();
+        for coin in [&testnet, &ethereum] {
+            assert_eq!(
+                stored_key
+                    .add_new_account_of_coin_and_derivation_path_by_password("", coin, "", "")
+                    .err(),
+                Some(Error::CryptoError(CryptoError::InvalidPublicKey))
+            );
+        }
+        let mut stored_key = StoredKey::create_watch_only_with_extended_public_key(tpub).unwrap();
+        assert_eq!(
+            stored_key
+                .add_new_account_of_coin_and_derivation_path_by_password("", &ethereum, "", "")
+                .err(),
+            Some(Error::CryptoError(CryptoError::InvalidPublicKey))
+        );
+
+        // The purpose of the path tells taproot keys from legacy ones, which share the xpub version
+        let mut stored_key =
+            StoredKey::create_watch_only_with_extended_public_key(taproot_xpub).unwrap();
+        let account = stored_key
+            .add_new_account_of_coin_and_derivation_path_by_password(
+                "",
+                &bitcoin,
+                "m/86'/0'/0'/0/0",
+                "",
+            )
+            .unwrap();
+        assert_eq!(
+            account.address,
+            "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr"
+        );
+        assert_eq!(
+            stored_key
+                .add_new_account_of_coin_and_derivation_path_by_password(
+                    "",
+                    &ethereum,
+                    "m/86'/60'/0'/0/0",
+                    "",
+                )
+                .err(),
+            Some(Error::CryptoError(CryptoError::InvalidPublicKey))
+        );
+    }
+
+    #[test]
+    fn test_export_extended_public_key() {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let password = "password";
+        let derivation_path = "m/84'/0'/0'/0/0";
+        let coin = Coin {
+            id: "bitcoin".to_owned(),
+            name: "Bitcoin".to_owned(),
+            coin_id: 0,
+            symbol: "BTC".to_owned(),
+            decimals: 8,
+            blockchain: "Bitcoin".to_owned(),
+            derivation_path: derivation_path.to_owned(),
+            curve: "secp256k1".to_owned(),
+            public_key_type: "secp256k1".to_owned(),
+            all_info: HashMap::new(),
+        };
+        let mut stored_key = StoredKey::create_with_mnemonic(password, mnemonic).unwrap();
+        assert_eq!(
+            stored_key
+                .export_extended_public_key(password, &coin, derivation_path, 3, None)
+                .unwrap(),
+            "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs"
+        );
+        assert_eq!(
+            stored_key
+                .export_extended_public_key(password, &coin, derivation_path, 4, None)
+                .unwrap(),
+            "zpub6u4KbU8TSgNuZSxzv7HaGq5Tk361gMHdZxnM4UYuwzg5CMLcNytzhobitV4Zq6vWtWHpG9QijsigkxAzXvQWyLRfLq1L7VxPP1tky1hPfD4"
+        );
+        let account = stored_key
+            .add_new_account_of_coin_and_derivation_path_by_password(
+                "",
+                &coin,
+                derivation_path,
+                password,
+            )
+            .unwrap();
+        assert_eq!(account.extended_public_key, "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs");
+        assert_eq!(
+            stored_key
+                .export_extended_public_key("wrong password", &coin, derivation_path, 3, None)
+                .err(),
+            Some(Error::CryptoError(CryptoError::PasswordIncorrect))
+        );
+
+        let private_key_stored_key = StoredKey::create_with_private_key_and_coin(
+            password,
+            "3a1076bf45ab87712ad64ccb3b10217737f7faacbf2872e88fdd9a537d8fe266",
+            &coin,
+            KdfPolicy::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            private_key_stored_key
+                .export_extended_public_key(password, &coin, derivation_path, 3, None)
+                .err(),
+            Some(Error::RequestNotSupportedOnPrivateKeyTypeStoredKey)
+        );
+    }
+
+    #[test]
+    fn test_sign_message() {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let password = "password";
+        let derivation_path = "m/44'/60'/0'/0/0";
+        let coin = Coin {
+            id: "60".to_owned(),
+            name: "ethereum".to_owned(),
+            coin_id: 60,
+            symbol: "ETH".to_owned(),
+            decimals: 18,
+            blockchain: "Ethereum".to_owned(),
+            derivation_path: derivation_path.to_owned(),
+            curve: "secp256k1".to_owned(),
+            public_key_type: "secp256k1Extended".to_owned(),
+            all_info: HashMap::new(),
+        };
+        let stored_key = StoredKey::create_with_mnemonic(password, mnemonic).unwrap();
+        let signature = stored_key
+            .sign_message(&coin, password, derivation_path, b"MaskWallet", false)
+            .unwrap();
+        assert_eq!(signature.len(), 65);
+        let entry = CoinDispatcher::get_entry(&coin).unwrap();
+        assert_eq!(
+            entry
+                .recover_message_signer(&coin, b"MaskWallet", &signature, false)
+                .unwrap(),
+            "0x9858EfFD232B4033E47d90003D41EC34EcaEda94"
+        );
+        assert_eq!(
+            stored_key
+                .sign_message(
+                    &coin,
+                    "wrong password",
+                    derivation_path,
+                    b"MaskWallet",
+                    false
+                )
+                .err(),
+            Some(Error::CryptoError(CryptoError::PasswordIncorrect))
+        );
+
+        let solana = Coin {
+            id: "solana".to_owned(),
+            name: "solana".to_owned(),
+            coin_id: 501,
+            symbol: "SOL".to_owned(),
+            decimals: 9,
+            blockchain: "Solana".to_owned(),
+            derivation_path: "m/44'/501'/0'/0'".to_owned(),
+            curve: "ed25519".to_owned(),
+            public_key_type: "ed25519".to_owned(),
+            all_info: HashMap::new(),
+        };
+        assert_eq!(
+            stored_key
+                .sign_message(&solana, password, "m/44'/501'/0'/0'", b"MaskWallet", false)
+                .err(),
+            Some(Error::CryptoError(CryptoError::NotSupportedMessageSigning))
+        );
+    }
+
+    #[test]
+    fn test_sign_typed_data() {
+        let password = "password";
+        let coin = Coin {
+            id: "60".to_owned(),
+            name: "ethereum".to_owned(),
+            coin_id: 60,
+            symbol: "ETH".to_owned(),
+            decimals: 18,
+            blockchain: "Ethereum".to_owned(),
+            derivation_path: "m/44'/60'/0'/0/0".to_owned(),
+            curve: "secp256k1".to_owned(),
+            public_key_type: "secp256k1Extended".to_owned(),
+            all_info: HashMap::new(),
+        };
+        // The example of EIP-712 signed by keccak256("cow")
+        let typed_data = r#"{"types": {"EIP712Domain": [{"name": "name", "type": "string"}, {"name": "version", "type": "string"}, {"name": "chainId", "type": "uint256"}, {"name": "verifyingContract", "type": "address"}], "Person": [{"name": "name", "type": "string"}, {"name": "wallet", "type": "address"}], "Mail": [{"name": "from", "type": "Person"}, {"name": "to", "type": "Person"}, {"name": "contents", "type": "string"}]}, "primaryType": "Mail", "domain": {"name": "Ether Mail", "version": "1", "chainId": 1, "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"}, "message": {"from": {"name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"}, "to": {"name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"}, "contents": "Hello, Bob!"}}"#;
+        let stored_key = StoredKey::create_with_private_key_and_coin(
+            password,
+            "c85ef7d79691fe79573b1a7064c19c1a9819ebdbd1faaab1a8ec92344438aaf4",
+            &coin,
+            KdfPolicy::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            hex::encode(
+                stored_key
+                    .sign_typed_data(&coin, password, "", typed_data)
+                    .unwrap()
+            ),
+            "4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b915621c"
+        );
+        assert_eq!(
+            stored_key.sign_typed_data(&coin, password, "", "{}").err(),
+            Some(Error::CryptoError(CryptoError::InvalidTypedData))
+        );
+    }
 }